@@ -9,6 +9,26 @@ mod m20250527_094216_add_role_ref_to_users;
 mod m20250601_172250_tasks;
 mod m20250602_133444_accesses;
 mod m20250605_151704_attachments;
+mod m20250612_120000_solutions;
+mod m20250615_090000_due_date_reminders;
+mod m20250618_100000_task_search;
+mod m20250620_100000_add_parent_ref_to_tasks;
+mod m20250622_100000_add_status_to_tasks;
+mod m20250624_100000_audit_events;
+mod m20250626_100000_purchases;
+mod m20250628_100000_groups;
+mod m20250630_100000_invitations;
+mod m20250702_100000_add_window_to_accesses;
+mod m20250704_100000_add_owner_to_tasks;
+mod m20250706_100000_webhooks;
+mod m20250708_100000_comments;
+mod m20250710_100000_add_forked_from_to_tasks;
+mod m20250712_100000_templates;
+mod m20250714_100000_add_calendar_token_to_users;
+mod m20250716_100000_add_due_at_to_attachments;
+mod m20250718_100000_task_attachment_text;
+mod m20250720_100000_unique_solution_file_names;
+mod m20250722_100000_unique_accesses;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -21,6 +41,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20250601_172250_tasks::Migration),
             Box::new(m20250602_133444_accesses::Migration),
             Box::new(m20250605_151704_attachments::Migration),
+            Box::new(m20250612_120000_solutions::Migration),
+            Box::new(m20250615_090000_due_date_reminders::Migration),
+            Box::new(m20250618_100000_task_search::Migration),
+            Box::new(m20250620_100000_add_parent_ref_to_tasks::Migration),
+            Box::new(m20250622_100000_add_status_to_tasks::Migration),
+            Box::new(m20250624_100000_audit_events::Migration),
+            Box::new(m20250626_100000_purchases::Migration),
+            Box::new(m20250628_100000_groups::Migration),
+            Box::new(m20250630_100000_invitations::Migration),
+            Box::new(m20250702_100000_add_window_to_accesses::Migration),
+            Box::new(m20250704_100000_add_owner_to_tasks::Migration),
+            Box::new(m20250706_100000_webhooks::Migration),
+            Box::new(m20250708_100000_comments::Migration),
+            Box::new(m20250710_100000_add_forked_from_to_tasks::Migration),
+            Box::new(m20250712_100000_templates::Migration),
+            Box::new(m20250714_100000_add_calendar_token_to_users::Migration),
+            Box::new(m20250716_100000_add_due_at_to_attachments::Migration),
+            Box::new(m20250718_100000_task_attachment_text::Migration),
+            Box::new(m20250720_100000_unique_solution_file_names::Migration),
+            Box::new(m20250722_100000_unique_accesses::Migration),
             // inject-above (do not remove this comment)
 
             // Register OAuth2 sessions migration
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'solution_status_enum' enum
+        m.create_type(
+            Type::create()
+                .as_enum(Alias::new("solution_status_enum"))
+                .values(vec![
+                    Alias::new("Pending"),
+                    Alias::new("Accepted"),
+                    Alias::new("Rejected"),
+                ])
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'solutions' table
+        m.create_table(
+            Table::create()
+                .table(Alias::new("solutions"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Alias::new("body")).text().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("status"))
+                        .enumeration(
+                            Alias::new("solution_status_enum"),
+                            vec![
+                                Alias::new("Pending"),
+                                Alias::new("Accepted"),
+                                Alias::new("Rejected"),
+                            ],
+                        )
+                        .not_null()
+                        .default(Value::String(Some(Box::new("Pending".to_owned())))),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                // Foreign Key for 'author'
+                .col(ColumnDef::new(Alias::new("author_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("solutions"))
+                        .from_col(Alias::new("author_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                // Foreign Key for 'task'
+                .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("solutions"))
+                        .from_col(Alias::new("task_id"))
+                        .to_tbl(Alias::new("tasks"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'solution_files' table
+        m.create_table(
+            Table::create()
+                .table(Alias::new("solution_files"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Alias::new("file_name")).string().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                // Foreign Key for 'solution'
+                .col(
+                    ColumnDef::new(Alias::new("solution_id"))
+                        .integer()
+                        .not_null(),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("solution_files"))
+                        .from_col(Alias::new("solution_id"))
+                        .to_tbl(Alias::new("solutions"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("solution_files")).to_owned())
+            .await?;
+
+        m.drop_table(Table::drop().table(Alias::new("solutions")).to_owned())
+            .await?;
+
+        m.drop_type(
+            Type::drop()
+                .name(Alias::new("solution_status_enum"))
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'due_date_reminders' table
+        m.create_table(
+            Table::create()
+                .table(Alias::new("due_date_reminders"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("due_at"))
+                        .timestamp_with_time_zone()
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                // Foreign Key for 'attachment'
+                .col(
+                    ColumnDef::new(Alias::new("attachment_id"))
+                        .integer()
+                        .not_null(),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("due_date_reminders"))
+                        .from_col(Alias::new("attachment_id"))
+                        .to_tbl(Alias::new("attachments"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                // Foreign Key for 'user'
+                .col(ColumnDef::new(Alias::new("user_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("due_date_reminders"))
+                        .from_col(Alias::new("user_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // A reminder is sent once per user for every due date of the attachment
+        m.create_index(
+            Index::create()
+                .name("idx_due_date_reminders_unique")
+                .table(Alias::new("due_date_reminders"))
+                .col(Alias::new("attachment_id"))
+                .col(Alias::new("user_id"))
+                .col(Alias::new("due_at"))
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("due_date_reminders"))
+                .to_owned(),
+        )
+        .await
+    }
+}
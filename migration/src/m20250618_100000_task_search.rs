@@ -0,0 +1,85 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Keeps 'tasks.search_vector' in sync with the task name (weight A)
+// and its Description, Text, Tip and Hint attachments (weight B)
+const UP: &str = r"
+ALTER TABLE tasks ADD COLUMN search_vector tsvector;
+
+CREATE FUNCTION task_search_document(task_id integer, task_name text) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', coalesce(task_name, '')), 'A')
+        || setweight(to_tsvector('english', coalesce((
+            SELECT string_agg(a.data, ' ')
+            FROM attachments a
+            WHERE a.task_id = $1
+              AND a.attachment_type IN ('Description', 'Text', 'Tip', 'Hint')
+        ), '')), 'B')
+$$ LANGUAGE sql STABLE;
+
+CREATE FUNCTION tasks_search_vector_trigger() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := task_search_document(NEW.id, NEW.name);
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tasks_search_vector_update
+    BEFORE INSERT OR UPDATE OF name ON tasks
+    FOR EACH ROW EXECUTE FUNCTION tasks_search_vector_trigger();
+
+CREATE FUNCTION attachments_search_vector_trigger() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        UPDATE tasks SET search_vector = task_search_document(id, name) WHERE id = OLD.task_id;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        UPDATE tasks SET search_vector = task_search_document(id, name) WHERE id = NEW.task_id;
+    END IF;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER attachments_search_vector_update
+    AFTER INSERT OR UPDATE OR DELETE ON attachments
+    FOR EACH ROW EXECUTE FUNCTION attachments_search_vector_trigger();
+
+UPDATE tasks SET search_vector = task_search_document(id, name);
+
+CREATE INDEX idx_tasks_search_vector ON tasks USING GIN (search_vector);
+";
+
+const DOWN: &str = r"
+DROP TRIGGER IF EXISTS attachments_search_vector_update ON attachments;
+DROP FUNCTION IF EXISTS attachments_search_vector_trigger();
+DROP TRIGGER IF EXISTS tasks_search_vector_update ON tasks;
+DROP FUNCTION IF EXISTS tasks_search_vector_trigger();
+DROP FUNCTION IF EXISTS task_search_document(integer, text);
+DROP INDEX IF EXISTS idx_tasks_search_vector;
+ALTER TABLE tasks DROP COLUMN IF EXISTS search_vector;
+";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite has no tsvector, search falls back to LIKE there
+        if m.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        m.get_connection().execute_unprepared(UP).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        if m.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        m.get_connection().execute_unprepared(DOWN).await?;
+
+        Ok(())
+    }
+}
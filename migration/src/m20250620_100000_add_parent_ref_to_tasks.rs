@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Add the nullable 'parent_id' column, root Tasks have none
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .add_column(ColumnDef::new(Alias::new("parent_id")).integer().null())
+                .add_foreign_key(
+                    TableForeignKey::new()
+                        .name("fk-tasks-parent_id-to-tasks")
+                        .from_tbl(Alias::new("tasks"))
+                        .from_col(Alias::new("parent_id"))
+                        .to_tbl(Alias::new("tasks"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_tasks_parent_id")
+                .table(Alias::new("tasks"))
+                .col(Alias::new("parent_id"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx_tasks_parent_id")
+                .table(Alias::new("tasks"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .drop_foreign_key(Alias::new("fk-tasks-parent_id-to-tasks"))
+                .drop_column(Alias::new("parent_id"))
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn statuses() -> Vec<Alias> {
+    vec![
+        Alias::new("Draft"),
+        Alias::new("Open"),
+        Alias::new("InProgress"),
+        Alias::new("Review"),
+        Alias::new("Done"),
+        Alias::new("Archived"),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'task_status_enum' enum
+        m.create_type(
+            Type::create()
+                .as_enum(Alias::new("task_status_enum"))
+                .values(statuses())
+                .to_owned(),
+        )
+        .await?;
+
+        // New Tasks start as drafts
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .add_column(
+                    ColumnDef::new(Alias::new("status"))
+                        .enumeration(Alias::new("task_status_enum"), statuses())
+                        .not_null()
+                        .default(Value::String(Some(Box::new("Draft".to_owned())))),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // Existing Tasks are already in use
+        m.exec_stmt(
+            Query::update()
+                .table(Alias::new("tasks"))
+                .value(
+                    Alias::new("status"),
+                    Expr::val("Open").as_enum(Alias::new("task_status_enum")),
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .drop_column(Alias::new("status"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.drop_type(Type::drop().name(Alias::new("task_status_enum")).to_owned())
+            .await
+    }
+}
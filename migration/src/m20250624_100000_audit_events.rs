@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn entities() -> Vec<Alias> {
+    vec![
+        Alias::new("Task"),
+        Alias::new("Access"),
+        Alias::new("Attachment"),
+    ]
+}
+
+fn actions() -> Vec<Alias> {
+    vec![
+        Alias::new("Create"),
+        Alias::new("Update"),
+        Alias::new("Delete"),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_type(
+            Type::create()
+                .as_enum(Alias::new("audit_entity_enum"))
+                .values(entities())
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_type(
+            Type::create()
+                .as_enum(Alias::new("audit_action_enum"))
+                .values(actions())
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'audit_events' table. 'task_id' has no foreign key
+        // so the history outlives removed Tasks
+        m.create_table(
+            Table::create()
+                .table(Alias::new("audit_events"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Alias::new("actor_pid")).uuid().not_null())
+                .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("entity"))
+                        .enumeration(Alias::new("audit_entity_enum"), entities())
+                        .not_null(),
+                )
+                .col(ColumnDef::new(Alias::new("entity_id")).integer().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("action"))
+                        .enumeration(Alias::new("audit_action_enum"), actions())
+                        .not_null(),
+                )
+                .col(ColumnDef::new(Alias::new("before")).json_binary().null())
+                .col(ColumnDef::new(Alias::new("after")).json_binary().null())
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_audit_events_task_id")
+                .table(Alias::new("audit_events"))
+                .col(Alias::new("task_id"))
+                .col(Alias::new("created_at"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("audit_events")).to_owned())
+            .await?;
+
+        m.drop_type(
+            Type::drop()
+                .name(Alias::new("audit_action_enum"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.drop_type(
+            Type::drop()
+                .name(Alias::new("audit_entity_enum"))
+                .to_owned(),
+        )
+        .await
+    }
+}
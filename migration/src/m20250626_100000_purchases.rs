@@ -0,0 +1,123 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn statuses() -> Vec<Alias> {
+    vec![
+        Alias::new("Pending"),
+        Alias::new("Paid"),
+        Alias::new("Failed"),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Price of Paid Tasks, in the smallest currency unit
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .add_column(ColumnDef::new(Alias::new("price_cents")).integer().null())
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'purchase_status_enum' enum
+        m.create_type(
+            Type::create()
+                .as_enum(Alias::new("purchase_status_enum"))
+                .values(statuses())
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'purchases' table
+        m.create_table(
+            Table::create()
+                .table(Alias::new("purchases"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("amount_cents"))
+                        .integer()
+                        .not_null(),
+                )
+                .col(ColumnDef::new(Alias::new("currency")).string().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("status"))
+                        .enumeration(Alias::new("purchase_status_enum"), statuses())
+                        .not_null()
+                        .default(Value::String(Some(Box::new("Pending".to_owned())))),
+                )
+                // Reference of the checkout at the payment provider
+                .col(
+                    ColumnDef::new(Alias::new("provider_ref"))
+                        .string()
+                        .null()
+                        .unique_key(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                // Foreign Key for 'task'
+                .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("purchases"))
+                        .from_col(Alias::new("task_id"))
+                        .to_tbl(Alias::new("tasks"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                // Foreign Key for 'user'
+                .col(ColumnDef::new(Alias::new("user_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("purchases"))
+                        .from_col(Alias::new("user_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("purchases")).to_owned())
+            .await?;
+
+        m.drop_type(
+            Type::drop()
+                .name(Alias::new("purchase_status_enum"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .drop_column(Alias::new("price_cents"))
+                .to_owned(),
+        )
+        .await
+    }
+}
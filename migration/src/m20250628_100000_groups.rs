@@ -0,0 +1,154 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn timestamps(table: &mut TableCreateStatement) -> &mut TableCreateStatement {
+    table
+        .col(
+            ColumnDef::new(Alias::new("created_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+        .col(
+            ColumnDef::new(Alias::new("updated_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+}
+
+fn reference(table: &str, column: &str, to_table: &str) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from_tbl(Alias::new(table))
+        .from_col(Alias::new(column))
+        .to_tbl(Alias::new(to_table))
+        .to_col(Alias::new("id"))
+        .on_delete(ForeignKeyAction::Cascade)
+        .on_update(ForeignKeyAction::Cascade)
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'groups' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("groups"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("name")).string().not_null()),
+            )
+            // Foreign Key for 'owner'
+            .col(ColumnDef::new(Alias::new("owner_id")).integer().not_null())
+            .foreign_key(&mut reference("groups", "owner_id", "users"))
+            .to_owned(),
+        )
+        .await?;
+
+        // Create the 'group_members' table
+        m.create_table(
+            timestamps(
+                Table::create().table(Alias::new("group_members")).col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                ),
+            )
+            .col(ColumnDef::new(Alias::new("group_id")).integer().not_null())
+            .foreign_key(&mut reference("group_members", "group_id", "groups"))
+            .col(ColumnDef::new(Alias::new("user_id")).integer().not_null())
+            .foreign_key(&mut reference("group_members", "user_id", "users"))
+            .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_group_members_unique")
+                .table(Alias::new("group_members"))
+                .col(Alias::new("group_id"))
+                .col(Alias::new("user_id"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        // Create the 'group_accesses' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("group_accesses"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("accesslevel"))
+                            .enumeration(
+                                Alias::new("access_level_enum"),
+                                vec![
+                                    Alias::new("View"),
+                                    Alias::new("AddSolution"),
+                                    Alias::new("Edit"),
+                                    Alias::new("AddUser"),
+                                    Alias::new("FullAccess"),
+                                ],
+                            )
+                            .not_null(),
+                    ),
+            )
+            .col(ColumnDef::new(Alias::new("group_id")).integer().not_null())
+            .foreign_key(&mut reference("group_accesses", "group_id", "groups"))
+            .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+            .foreign_key(&mut reference("group_accesses", "task_id", "tasks"))
+            .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_group_accesses_unique")
+                .table(Alias::new("group_accesses"))
+                .col(Alias::new("group_id"))
+                .col(Alias::new("task_id"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        // Group grants show up in the audit log too
+        m.alter_type(
+            Type::alter()
+                .name(Alias::new("audit_entity_enum"))
+                .add_value(Alias::new("GroupAccess"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can not drop values of an enum, 'GroupAccess' stays in 'audit_entity_enum'
+        m.drop_table(Table::drop().table(Alias::new("group_accesses")).to_owned())
+            .await?;
+
+        m.drop_table(Table::drop().table(Alias::new("group_members")).to_owned())
+            .await?;
+
+        m.drop_table(Table::drop().table(Alias::new("groups")).to_owned())
+            .await
+    }
+}
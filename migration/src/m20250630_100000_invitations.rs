@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'invitations' table, pending accesses of unregistered emails
+        m.create_table(
+            Table::create()
+                .table(Alias::new("invitations"))
+                .col(
+                    ColumnDef::new(Alias::new("id"))
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Alias::new("email")).string().not_null())
+                .col(
+                    ColumnDef::new(Alias::new("token"))
+                        .string()
+                        .not_null()
+                        .unique_key(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("accesslevel"))
+                        .enumeration(
+                            Alias::new("access_level_enum"),
+                            vec![
+                                Alias::new("View"),
+                                Alias::new("AddSolution"),
+                                Alias::new("Edit"),
+                                Alias::new("AddUser"),
+                                Alias::new("FullAccess"),
+                            ],
+                        )
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("created_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    ColumnDef::new(Alias::new("updated_at"))
+                        .timestamp_with_time_zone()
+                        .not_null()
+                        .default(Expr::current_timestamp()),
+                )
+                // Foreign Key for 'task'
+                .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("invitations"))
+                        .from_col(Alias::new("task_id"))
+                        .to_tbl(Alias::new("tasks"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                // Foreign Key for the inviting 'user'
+                .col(
+                    ColumnDef::new(Alias::new("invited_by_id"))
+                        .integer()
+                        .not_null(),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .from_tbl(Alias::new("invitations"))
+                        .from_col(Alias::new("invited_by_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_invitations_task_email")
+                .table(Alias::new("invitations"))
+                .col(Alias::new("task_id"))
+                .col(Alias::new("email"))
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("invitations")).to_owned())
+            .await
+    }
+}
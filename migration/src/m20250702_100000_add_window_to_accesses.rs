@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Accesses only apply between 'starts_at' and 'expires_at', empty bounds are open
+        for table in ["accesses", "invitations"] {
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .add_column(
+                        ColumnDef::new(Alias::new("starts_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Alias::new("expires_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        // Lets the expiry job find the expired grants quickly
+        m.create_index(
+            Index::create()
+                .name("idx_accesses_expires_at")
+                .table(Alias::new("accesses"))
+                .col(Alias::new("expires_at"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx_accesses_expires_at")
+                .table(Alias::new("accesses"))
+                .to_owned(),
+        )
+        .await?;
+
+        for table in ["accesses", "invitations"] {
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .drop_column(Alias::new("starts_at"))
+                    .drop_column(Alias::new("expires_at"))
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
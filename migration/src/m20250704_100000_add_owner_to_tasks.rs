@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const BACKFILL: &str = r"
+UPDATE tasks SET owner_id = (
+    SELECT a.user_id
+    FROM accesses a
+    WHERE a.task_id = tasks.id
+      AND a.accesslevel = 'FullAccess'
+    ORDER BY a.id
+    LIMIT 1
+);
+";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Subtasks without an owner belong to the owner of the closest owned ancestor
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .add_column(ColumnDef::new(Alias::new("owner_id")).integer().null())
+                .add_foreign_key(
+                    TableForeignKey::new()
+                        .name("fk-tasks-owner_id-to-users")
+                        .from_tbl(Alias::new("tasks"))
+                        .from_col(Alias::new("owner_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                // Recipient of an ownership transfer that waits for confirmation
+                .add_column(
+                    ColumnDef::new(Alias::new("pending_owner_id"))
+                        .integer()
+                        .null(),
+                )
+                .add_foreign_key(
+                    TableForeignKey::new()
+                        .name("fk-tasks-pending_owner_id-to-users")
+                        .from_tbl(Alias::new("tasks"))
+                        .from_col(Alias::new("pending_owner_id"))
+                        .to_tbl(Alias::new("users"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // The first user who got FullAccess created the Task
+        m.get_connection().execute_unprepared(BACKFILL).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .drop_foreign_key(Alias::new("fk-tasks-pending_owner_id-to-users"))
+                .drop_column(Alias::new("pending_owner_id"))
+                .drop_foreign_key(Alias::new("fk-tasks-owner_id-to-users"))
+                .drop_column(Alias::new("owner_id"))
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,144 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn timestamps(table: &mut TableCreateStatement) -> &mut TableCreateStatement {
+    table
+        .col(
+            ColumnDef::new(Alias::new("created_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+        .col(
+            ColumnDef::new(Alias::new("updated_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+}
+
+fn reference(table: &str, column: &str, to_table: &str) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from_tbl(Alias::new(table))
+        .from_col(Alias::new(column))
+        .to_tbl(Alias::new(to_table))
+        .to_col(Alias::new("id"))
+        .on_delete(ForeignKeyAction::Cascade)
+        .on_update(ForeignKeyAction::Cascade)
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'webhooks' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("webhooks"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("url")).string().not_null())
+                    .col(ColumnDef::new(Alias::new("secret")).string().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("enabled"))
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    // Failed deliveries in a row, the hook is disabled after too many
+                    .col(
+                        ColumnDef::new(Alias::new("failure_count"))
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    ),
+            )
+            // Foreign Key for 'owner'
+            .col(ColumnDef::new(Alias::new("owner_id")).integer().not_null())
+            .foreign_key(&mut reference("webhooks", "owner_id", "users"))
+            // Foreign Key for 'task', hooks without one get events of all Tasks of the owner
+            .col(ColumnDef::new(Alias::new("task_id")).integer().null())
+            .foreign_key(&mut reference("webhooks", "task_id", "tasks"))
+            .to_owned(),
+        )
+        .await?;
+
+        // Create the 'webhook_deliveries' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("webhook_deliveries"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("event")).string().not_null())
+                    .col(ColumnDef::new(Alias::new("payload")).text().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("attempts"))
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Alias::new("status_code")).integer().null())
+                    .col(ColumnDef::new(Alias::new("error")).text().null())
+                    .col(
+                        ColumnDef::new(Alias::new("delivered_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    // Empty once the delivery succeeded or was given up
+                    .col(
+                        ColumnDef::new(Alias::new("next_attempt_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    ),
+            )
+            // Foreign Key for 'webhook'
+            .col(
+                ColumnDef::new(Alias::new("webhook_id"))
+                    .integer()
+                    .not_null(),
+            )
+            .foreign_key(&mut reference(
+                "webhook_deliveries",
+                "webhook_id",
+                "webhooks",
+            ))
+            .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_webhook_deliveries_next_attempt_at")
+                .table(Alias::new("webhook_deliveries"))
+                .col(Alias::new("next_attempt_at"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("webhook_deliveries"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.drop_table(Table::drop().table(Alias::new("webhooks")).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn timestamps(table: &mut TableCreateStatement) -> &mut TableCreateStatement {
+    table
+        .col(
+            ColumnDef::new(Alias::new("created_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+        .col(
+            ColumnDef::new(Alias::new("updated_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+}
+
+fn reference(table: &str, column: &str, to_table: &str) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from_tbl(Alias::new(table))
+        .from_col(Alias::new(column))
+        .to_tbl(Alias::new(to_table))
+        .to_col(Alias::new("id"))
+        .on_delete(ForeignKeyAction::Cascade)
+        .on_update(ForeignKeyAction::Cascade)
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'comments' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("comments"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("body")).text().not_null())
+                    .col(
+                        ColumnDef::new(Alias::new("edited_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    // Removed comments with replies stay in the thread without their body
+                    .col(
+                        ColumnDef::new(Alias::new("deleted_at"))
+                            .timestamp_with_time_zone()
+                            .null(),
+                    ),
+            )
+            // Foreign Key for 'task'
+            .col(ColumnDef::new(Alias::new("task_id")).integer().not_null())
+            .foreign_key(&mut reference("comments", "task_id", "tasks"))
+            // Foreign Key for 'author'
+            .col(ColumnDef::new(Alias::new("author_id")).integer().not_null())
+            .foreign_key(&mut reference("comments", "author_id", "users"))
+            // Foreign Key for 'parent', top level comments have none
+            .col(ColumnDef::new(Alias::new("parent_id")).integer().null())
+            .foreign_key(&mut reference("comments", "parent_id", "comments"))
+            .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_comments_task_id")
+                .table(Alias::new("comments"))
+                .col(Alias::new("task_id"))
+                .col(Alias::new("created_at"))
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("comments")).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Task a fork was copied from, cleared when the source is removed
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .add_column(
+                    ColumnDef::new(Alias::new("forked_from_id"))
+                        .integer()
+                        .null(),
+                )
+                .add_foreign_key(
+                    TableForeignKey::new()
+                        .name("fk-tasks-forked_from_id-to-tasks")
+                        .from_tbl(Alias::new("tasks"))
+                        .from_col(Alias::new("forked_from_id"))
+                        .to_tbl(Alias::new("tasks"))
+                        .to_col(Alias::new("id"))
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tasks"))
+                .drop_foreign_key(Alias::new("fk-tasks-forked_from_id-to-tasks"))
+                .drop_column(Alias::new("forked_from_id"))
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn timestamps(table: &mut TableCreateStatement) -> &mut TableCreateStatement {
+    table
+        .col(
+            ColumnDef::new(Alias::new("created_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+        .col(
+            ColumnDef::new(Alias::new("updated_at"))
+                .timestamp_with_time_zone()
+                .not_null()
+                .default(Expr::current_timestamp()),
+        )
+}
+
+fn reference(table: &str, column: &str, to_table: &str) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from_tbl(Alias::new(table))
+        .from_col(Alias::new(column))
+        .to_tbl(Alias::new(to_table))
+        .to_col(Alias::new("id"))
+        .on_delete(ForeignKeyAction::Cascade)
+        .on_update(ForeignKeyAction::Cascade)
+        .to_owned()
+}
+
+fn attachment_types() -> Vec<Alias> {
+    vec![
+        Alias::new("Description"),
+        Alias::new("DueDate"),
+        Alias::new("File"),
+        Alias::new("Url"),
+        Alias::new("Text"),
+        Alias::new("Tip"),
+        Alias::new("Hint"),
+        Alias::new("Warning"),
+        Alias::new("Progress"),
+        Alias::new("Importance"),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Create the 'templates' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("templates"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Alias::new("name")).string().not_null()),
+            )
+            // Foreign Key for 'owner'
+            .col(ColumnDef::new(Alias::new("owner_id")).integer().not_null())
+            .foreign_key(&mut reference("templates", "owner_id", "users"))
+            .to_owned(),
+        )
+        .await?;
+
+        // Create the 'template_attachments' table
+        m.create_table(
+            timestamps(
+                Table::create()
+                    .table(Alias::new("template_attachments"))
+                    .col(
+                        ColumnDef::new(Alias::new("id"))
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new("attachment_type"))
+                            .enumeration(Alias::new("attachment_type_enum"), attachment_types())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Alias::new("data")).text().not_null())
+                    // DueDates are stored as an offset from the creation of the Task
+                    .col(
+                        ColumnDef::new(Alias::new("due_offset_secs"))
+                            .big_integer()
+                            .null(),
+                    ),
+            )
+            // Foreign Key for 'template'
+            .col(
+                ColumnDef::new(Alias::new("template_id"))
+                    .integer()
+                    .not_null(),
+            )
+            .foreign_key(&mut reference(
+                "template_attachments",
+                "template_id",
+                "templates",
+            ))
+            .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("template_attachments"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.drop_table(Table::drop().table(Alias::new("templates")).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Secret of the calendar feed URL, none until the User asks for a feed
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .add_column(ColumnDef::new(Alias::new("calendar_token")).string().null())
+                .to_owned(),
+        )
+        .await?;
+
+        // SQLite can not add a UNIQUE column, so the index is created on its own
+        m.create_index(
+            Index::create()
+                .name("idx-users-calendar_token")
+                .table(Alias::new("users"))
+                .col(Alias::new("calendar_token"))
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx-users-calendar_token")
+                .table(Alias::new("users"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .drop_column(Alias::new("calendar_token"))
+                .to_owned(),
+        )
+        .await
+    }
+}
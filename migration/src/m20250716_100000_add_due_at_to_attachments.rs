@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, sea_orm::prelude::DateTimeWithTimeZone};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Parsed `data` of DueDate attachments, so that due windows are queried by date
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("attachments"))
+                .add_column(
+                    ColumnDef::new(Alias::new("due_at"))
+                        .timestamp_with_time_zone()
+                        .null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-attachments-due_at")
+                .table(Alias::new("attachments"))
+                .col(Alias::new("due_at"))
+                .to_owned(),
+        )
+        .await?;
+
+        // Parsed here rather than cast in SQL, rows written before the payloads were
+        // validated may not hold a date
+        let due_dates = Query::select()
+            .columns([Alias::new("id"), Alias::new("data")])
+            .from(Alias::new("attachments"))
+            .and_where(
+                Expr::col(Alias::new("attachment_type"))
+                    .eq(Expr::val("DueDate").as_enum(Alias::new("attachment_type_enum"))),
+            )
+            .to_owned();
+        let rows = m
+            .get_connection()
+            .query_all(m.get_database_backend().build(&due_dates))
+            .await?;
+
+        for row in rows {
+            let id: i32 = row.try_get("", "id")?;
+            let data: String = row.try_get("", "data")?;
+            let Ok(due_at) = DateTimeWithTimeZone::parse_from_rfc3339(data.trim()) else {
+                continue;
+            };
+
+            m.exec_stmt(
+                Query::update()
+                    .table(Alias::new("attachments"))
+                    .value(Alias::new("due_at"), due_at)
+                    .and_where(Expr::col(Alias::new("id")).eq(id))
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx-attachments-due_at")
+                .table(Alias::new("attachments"))
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("attachments"))
+                .drop_column(Alias::new("due_at"))
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// The searched attachment text gets its own function, so that search snippets
+// are built from the same text as 'tasks.search_vector'
+const UP: &str = r"
+CREATE FUNCTION task_attachment_text(task_id integer) RETURNS text AS $$
+    SELECT coalesce(string_agg(a.data, ' '), '')
+    FROM attachments a
+    WHERE a.task_id = $1
+      AND a.attachment_type IN ('Description', 'Text', 'Tip', 'Hint')
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION task_search_document(task_id integer, task_name text) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', coalesce(task_name, '')), 'A')
+        || setweight(to_tsvector('english', task_attachment_text($1)), 'B')
+$$ LANGUAGE sql STABLE;
+";
+
+const DOWN: &str = r"
+CREATE OR REPLACE FUNCTION task_search_document(task_id integer, task_name text) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', coalesce(task_name, '')), 'A')
+        || setweight(to_tsvector('english', coalesce((
+            SELECT string_agg(a.data, ' ')
+            FROM attachments a
+            WHERE a.task_id = $1
+              AND a.attachment_type IN ('Description', 'Text', 'Tip', 'Hint')
+        ), '')), 'B')
+$$ LANGUAGE sql STABLE;
+
+DROP FUNCTION IF EXISTS task_attachment_text(integer);
+";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        if m.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        m.get_connection().execute_unprepared(UP).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        if m.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        m.get_connection().execute_unprepared(DOWN).await?;
+
+        Ok(())
+    }
+}
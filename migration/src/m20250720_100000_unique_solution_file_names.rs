@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // Files of a Solution are found by name, earlier submissions may have
+        // stored the same name twice, only the first row is kept
+        m.get_connection()
+            .execute_unprepared(
+                "DELETE FROM solution_files WHERE id NOT IN \
+                 (SELECT MIN(id) FROM solution_files GROUP BY solution_id, file_name)",
+            )
+            .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_solution_files_solution_file_name")
+                .table(Alias::new("solution_files"))
+                .col(Alias::new("solution_id"))
+                .col(Alias::new("file_name"))
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx_solution_files_solution_file_name")
+                .table(Alias::new("solution_files"))
+                .to_owned(),
+        )
+        .await
+    }
+}
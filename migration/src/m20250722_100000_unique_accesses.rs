@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // A User holds one Access per Task, earlier grants may have added more rows,
+        // only the latest one is kept
+        m.get_connection()
+            .execute_unprepared(
+                "DELETE FROM accesses WHERE id NOT IN \
+                 (SELECT MAX(id) FROM accesses GROUP BY task_id, user_id)",
+            )
+            .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx_accesses_task_user")
+                .table(Alias::new("accesses"))
+                .col(Alias::new("task_id"))
+                .col(Alias::new("user_id"))
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx_accesses_task_user")
+                .table(Alias::new("accesses"))
+                .to_owned(),
+        )
+        .await
+    }
+}
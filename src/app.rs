@@ -9,7 +9,6 @@ use loco_rs::{
     controller::AppRoutes,
     db::{self, truncate_table},
     environment::Environment,
-    storage,
     task::Tasks,
     Result,
 };
@@ -19,8 +18,20 @@ use std::path::Path;
 use tower_cookies::CookieManagerLayer;
 
 #[allow(unused_imports)]
-use crate::{controllers, models::_entities::users, tasks, workers::downloader::DownloadWorker};
-use crate::{initializers, models::roles};
+use crate::{
+    controllers,
+    models::_entities::users,
+    tasks,
+    workers::{
+        access_expiry::AccessExpiryWorker, downloader::DownloadWorker,
+        due_reminder::DueReminderWorker, webhook_delivery::WebhookDeliveryWorker,
+    },
+};
+use crate::{
+    common::{self, settings::Settings},
+    initializers,
+    models::roles,
+};
 
 pub struct App;
 #[async_trait]
@@ -68,16 +79,25 @@ impl Hooks for App {
             )),
             Box::new(initializers::axum_session::AxumSessionInitializer),
             Box::new(initializers::oauth2::OAuth2StoreInitializer),
+            Box::new(initializers::webhooks::WebhookDispatchInitializer),
         ])
     }
 
     fn routes(_ctx: &AppContext) -> AppRoutes {
         AppRoutes::with_default_routes() // controller routes below
             .add_route(controllers::attachments::routes())
+            .add_route(controllers::solutions::routes())
+            .add_route(controllers::comments::routes())
             .add_route(controllers::users::routes())
             .add_route(controllers::tasks::routes())
+            .add_route(controllers::templates::routes())
+            .add_route(controllers::events::routes())
             .add_route(controllers::accesses::routes())
+            .add_route(controllers::groups::routes())
+            .add_route(controllers::payments::routes())
             .add_route(controllers::roles::routes())
+            .add_route(controllers::webhooks::routes())
+            .add_route(controllers::calendar::routes())
             .add_route(controllers::auth::routes())
             .add_route(controllers::oauth2::routes())
     }
@@ -91,19 +111,32 @@ impl Hooks for App {
     }
 
     async fn after_context(ctx: AppContext) -> Result<AppContext> {
+        let settings = Settings::from_opt_json(&ctx.config.settings)?;
+
+        let storage_settings = common::storage::configured(&ctx.environment, settings.storage)?;
+        let (storage, files) = common::storage::build(&storage_settings)?;
+        common::storage::check_writable(&storage).await?;
+        ctx.shared_store.insert(files);
+
         Ok(AppContext {
-            storage: storage::Storage::single(storage::drivers::mem::new()).into(),
+            storage: storage.into(),
             ..ctx
         })
     }
 
     async fn connect_workers(ctx: &AppContext, queue: &Queue) -> Result<()> {
         queue.register(DownloadWorker::build(ctx)).await?;
+        queue.register(DueReminderWorker::build(ctx)).await?;
+        queue.register(AccessExpiryWorker::build(ctx)).await?;
+        queue.register(WebhookDeliveryWorker::build(ctx)).await?;
         Ok(())
     }
 
     #[allow(unused_variables)]
     fn register_tasks(tasks: &mut Tasks) {
+        tasks.register(tasks::due_reminders::DueReminders);
+        tasks.register(tasks::expire_accesses::ExpireAccesses);
+        tasks.register(tasks::retry_webhooks::RetryWebhooks);
         // tasks-inject (do not remove)
     }
     async fn truncate(ctx: &AppContext) -> Result<()> {
@@ -0,0 +1,184 @@
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Read, Write},
+};
+
+use loco_rs::{model::ModelError, Error, Result};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::models::_entities::sea_orm_active_enums::{
+    AccessLevelEnum, AttachmentTypeEnum, TaskStatusEnum, TaskVisibilityEnum,
+};
+
+/// Version of the manifest written by [`Archive::write`]
+pub const FORMAT_VERSION: u32 = 1;
+pub const MANIFEST_NAME: &str = "manifest.json";
+/// Largest archive accepted for import in bytes
+pub const MAX_ARCHIVE_SIZE: usize = 10 * 1024 * 1024;
+/// Largest total size of the unpacked entries of an archive in bytes
+pub const MAX_UNPACKED_SIZE: u64 = 50 * 1024 * 1024;
+/// Most entries, and most Attachments in the manifest, of an archive
+pub const MAX_ENTRIES: usize = 1000;
+
+/// Describes the exported Task, the content of its File attachments is stored in the
+/// archive under the entry names given by `file`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub task: TaskEntry,
+    pub attachments: Vec<AttachmentEntry>,
+    /// Only present when the Accesses were exported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accesses: Option<Vec<AccessEntry>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TaskEntry {
+    pub name: String,
+    pub visibility: TaskVisibilityEnum,
+    pub status: TaskStatusEnum,
+    pub price_cents: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttachmentEntry {
+    pub attachment_type: AttachmentTypeEnum,
+    pub data: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// Access of a User, who is found by email on import
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccessEntry {
+    pub email: String,
+    pub accesslevel: AccessLevelEnum,
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    pub expires_at: Option<DateTimeWithTimeZone>,
+}
+
+/// ZIP archive of a Task: a `manifest.json` next to the content of its File attachments
+#[derive(Debug)]
+pub struct Archive {
+    pub manifest: Manifest,
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Entry name of the content of the attachment at `index` of the manifest
+#[must_use]
+pub fn file_entry(index: usize) -> String {
+    format!("files/{index}")
+}
+
+fn zip_error(e: &zip::result::ZipError) -> Error {
+    tracing::error!(error = ?e, "could not write task archive");
+    Error::Message("could not write task archive".to_string())
+}
+
+impl Archive {
+    /// Packs the manifest and the files into a ZIP archive
+    ///
+    /// # Errors
+    ///
+    /// When the archive could not be written
+    pub fn write(&self) -> Result<Vec<u8>> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+        zip.start_file(MANIFEST_NAME, options)
+            .map_err(|e| zip_error(&e))?;
+        zip.write_all(&serde_json::to_vec_pretty(&self.manifest)?)?;
+
+        for (name, content) in &self.files {
+            zip.start_file(name.as_str(), options)
+                .map_err(|e| zip_error(&e))?;
+            zip.write_all(content)?;
+        }
+
+        Ok(zip.finish().map_err(|e| zip_error(&e))?.into_inner())
+    }
+
+    /// Unpacks a ZIP archive written by [`Archive::write`], checking its size, its
+    /// manifest and that every File attachment has its content in the archive
+    ///
+    /// # Errors
+    ///
+    /// [`ModelError::Message`] when the archive is invalid or too large
+    pub fn read(bytes: &[u8]) -> std::result::Result<Self, ModelError> {
+        if bytes.len() > MAX_ARCHIVE_SIZE {
+            return Err(ModelError::msg("Archive is too large"));
+        }
+
+        let mut zip = ZipArchive::new(Cursor::new(bytes))
+            .map_err(|_| ModelError::msg("Archive is not a valid ZIP file"))?;
+        if zip.len() > MAX_ENTRIES + 1 {
+            return Err(ModelError::msg("Archive has too many entries"));
+        }
+
+        let mut manifest = None;
+        let mut files = BTreeMap::new();
+        let mut remaining = MAX_UNPACKED_SIZE;
+        for index in 0..zip.len() {
+            let entry = zip
+                .by_index(index)
+                .map_err(|_| ModelError::msg("Archive is not a valid ZIP file"))?;
+            if entry.is_dir() {
+                continue;
+            }
+
+            let name = entry.name().to_string();
+
+            // the declared size can not be trusted, so read at most what is left
+            let mut content = Vec::new();
+            entry
+                .take(remaining + 1)
+                .read_to_end(&mut content)
+                .map_err(|_| ModelError::msg("Archive is not a valid ZIP file"))?;
+            remaining = remaining
+                .checked_sub(content.len() as u64)
+                .ok_or_else(|| ModelError::msg("Archive content is too large"))?;
+
+            if name == MANIFEST_NAME {
+                manifest = Some(
+                    serde_json::from_slice::<Manifest>(&content)
+                        .map_err(|e| ModelError::msg(&format!("Invalid manifest: {e}")))?,
+                );
+            } else {
+                files.insert(name, content);
+            }
+        }
+
+        let manifest = manifest.ok_or_else(|| ModelError::msg("Archive has no manifest"))?;
+        if manifest.version != FORMAT_VERSION {
+            return Err(ModelError::msg(&format!(
+                "Unsupported archive version {}",
+                manifest.version
+            )));
+        }
+        if manifest.attachments.len() > MAX_ENTRIES {
+            return Err(ModelError::msg("Archive has too many attachments"));
+        }
+
+        for attachment in &manifest.attachments {
+            match (attachment.attachment_type, &attachment.file) {
+                (AttachmentTypeEnum::File, Some(file)) if files.contains_key(file) => {}
+                (AttachmentTypeEnum::File, _) => {
+                    return Err(ModelError::msg(&format!(
+                        "Content of the file `{}` is missing",
+                        attachment.data
+                    )))
+                }
+                (_, Some(_)) => {
+                    return Err(ModelError::msg(
+                        "Only File attachments can have content in the archive",
+                    ))
+                }
+                (_, None) => {}
+            }
+        }
+
+        Ok(Self { manifest, files })
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::BTreeSet, sync::OnceLock};
+
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+use crate::{common::policy::Action, models::tasks};
+
+/// Events a subscriber may fall behind by before it starts missing them
+const HUB_CAPACITY: usize = 1024;
+
+/// Change to a Task pushed to the Users who follow it. Only ids are sent,
+/// subscribers load whatever they need through the regular endpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TaskEvent {
+    TaskUpdated {
+        task_id: i32,
+    },
+    /// The Task and its subtasks are removed, no more events of it follow
+    TaskRemoved {
+        task_id: i32,
+    },
+    AttachmentAdded {
+        task_id: i32,
+        attachment_id: i32,
+    },
+    AttachmentUpdated {
+        task_id: i32,
+        attachment_id: i32,
+    },
+    AttachmentRemoved {
+        task_id: i32,
+        attachment_id: i32,
+    },
+    CommentAdded {
+        task_id: i32,
+        comment_id: i32,
+    },
+    CommentUpdated {
+        task_id: i32,
+        comment_id: i32,
+    },
+    CommentRemoved {
+        task_id: i32,
+        comment_id: i32,
+    },
+    AccessChanged {
+        task_id: i32,
+        /// User whose Access changed, empty when it is a Group Access
+        #[serde(skip)]
+        user_id: Option<i32>,
+    },
+    /// The subscriber lost access to the Task, no more events of it follow
+    AccessRevoked {
+        task_id: i32,
+    },
+}
+
+impl TaskEvent {
+    #[must_use]
+    pub const fn task_id(&self) -> i32 {
+        match self {
+            Self::TaskUpdated { task_id }
+            | Self::TaskRemoved { task_id }
+            | Self::AttachmentAdded { task_id, .. }
+            | Self::AttachmentUpdated { task_id, .. }
+            | Self::AttachmentRemoved { task_id, .. }
+            | Self::CommentAdded { task_id, .. }
+            | Self::CommentUpdated { task_id, .. }
+            | Self::CommentRemoved { task_id, .. }
+            | Self::AccessChanged { task_id, .. }
+            | Self::AccessRevoked { task_id } => *task_id,
+        }
+    }
+
+    /// Name of the event, the same as its `kind` field
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::TaskUpdated { .. } => "task_updated",
+            Self::TaskRemoved { .. } => "task_removed",
+            Self::AttachmentAdded { .. } => "attachment_added",
+            Self::AttachmentUpdated { .. } => "attachment_updated",
+            Self::AttachmentRemoved { .. } => "attachment_removed",
+            Self::CommentAdded { .. } => "comment_added",
+            Self::CommentUpdated { .. } => "comment_updated",
+            Self::CommentRemoved { .. } => "comment_removed",
+            Self::AccessChanged { .. } => "access_changed",
+            Self::AccessRevoked { .. } => "access_revoked",
+        }
+    }
+
+    /// Whether the event may take away access of the User to some Task
+    #[must_use]
+    pub fn may_revoke(&self, user_id: i32) -> bool {
+        match self {
+            Self::AccessChanged {
+                user_id: changed, ..
+            } => changed.is_none_or(|changed| changed == user_id),
+            // subtasks go along with the removed Task
+            Self::TaskRemoved { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// Fans Task events out to every subscriber of this process. Implementations
+/// backed by something shared, e.g. Postgres `LISTEN/NOTIFY`, relay the events
+/// of other processes into the same receivers
+pub trait EventHub: Send + Sync {
+    fn publish(&self, event: TaskEvent);
+
+    fn subscribe(&self) -> broadcast::Receiver<TaskEvent>;
+}
+
+/// Hub that only reaches subscribers of the current process
+pub struct LocalHub {
+    sender: broadcast::Sender<TaskEvent>,
+}
+
+impl LocalHub {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+
+        Self { sender }
+    }
+}
+
+impl EventHub for LocalHub {
+    fn publish(&self, event: TaskEvent) {
+        // fails only when nobody listens
+        self.sender.send(event).ok();
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<TaskEvent> {
+        self.sender.subscribe()
+    }
+}
+
+static HUB: OnceLock<Box<dyn EventHub>> = OnceLock::new();
+
+/// Hub shared by the whole application
+pub fn hub() -> &'static dyn EventHub {
+    HUB.get_or_init(|| Box::new(LocalHub::new(HUB_CAPACITY)))
+        .as_ref()
+}
+
+pub fn publish(event: TaskEvent) {
+    hub().publish(event);
+}
+
+/// Tasks a User follows, limited to the ones they can still read
+#[derive(Debug)]
+pub struct Subscription {
+    pub user_id: i32,
+    pub task_ids: BTreeSet<i32>,
+}
+
+impl Subscription {
+    #[must_use]
+    pub fn wants(&self, event: &TaskEvent) -> bool {
+        self.task_ids.contains(&event.task_id())
+    }
+
+    /// Drops the Tasks the User can no longer read and returns their ids
+    ///
+    /// # Errors
+    ///
+    /// When the Accesses can not be loaded
+    pub async fn recheck(&mut self, db: &DatabaseConnection) -> ModelResult<Vec<i32>> {
+        let mut revoked = Vec::new();
+
+        for &task_id in &self.task_ids {
+            let level = match tasks::Model::effective_access(db, self.user_id, task_id).await {
+                Ok(level) => level,
+                Err(ModelError::EntityNotFound) => None,
+                Err(err) => return Err(err),
+            };
+
+            if !level.is_some_and(|level| Action::ReadTask.allows(level)) {
+                revoked.push(task_id);
+            }
+        }
+
+        for task_id in &revoked {
+            self.task_ids.remove(task_id);
+        }
+
+        Ok(revoked)
+    }
+}
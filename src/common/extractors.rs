@@ -1,10 +1,15 @@
+use std::{marker::PhantomData, ops::Deref};
+
 use axum::extract::{FromRef, OptionalFromRequestParts};
 use axum::{extract::FromRequestParts, http::request::Parts};
 
 use loco_rs::controller::extractor::auth;
 use loco_rs::{app::AppContext, errors::Error, prelude::*};
 
-use crate::models;
+use crate::{
+    common::policy::Permission,
+    models::{self, tasks::AccessLevelEnum},
+};
 
 pub struct AdminUser {
     pub jwt: auth::JWT,
@@ -61,3 +66,46 @@ where
         }
     }
 }
+
+/// Authenticated User who is allowed the action of `P` on the Task from the `{id}` path,
+/// dereferences to the JWT of the User
+pub struct Authorized<P: Permission> {
+    pub jwt: auth::JWT,
+    /// Effective Access of the User to the Task
+    pub level: AccessLevelEnum,
+    permission: PhantomData<P>,
+}
+
+impl<S, P> FromRequestParts<S> for Authorized<P>
+where
+    AppContext: FromRef<S>,
+    S: Send + Sync,
+    P: Permission,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let jwt = auth::JWT::from_request_parts(parts, state).await?;
+        let Path(task_id) = Path::<i32>::from_request_parts(parts, state)
+            .await
+            .map_err(|err| Error::BadRequest(err.body_text()))?;
+        let ctx = AppContext::from_ref(state);
+
+        let level =
+            models::tasks::Model::authorize(&ctx.db, &jwt.claims.pid, task_id, P::ACTION).await?;
+
+        Ok(Self {
+            jwt,
+            level,
+            permission: PhantomData,
+        })
+    }
+}
+
+impl<P: Permission> Deref for Authorized<P> {
+    type Target = auth::JWT;
+
+    fn deref(&self) -> &Self::Target {
+        &self.jwt
+    }
+}
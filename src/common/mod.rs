@@ -1,3 +1,9 @@
+pub mod archive;
+pub mod events;
 pub mod extractors;
+pub mod pagination;
+pub mod payments;
+pub mod policy;
 pub mod responses;
 pub mod settings;
+pub mod storage;
@@ -0,0 +1,77 @@
+use loco_rs::model::query::PaginationQuery;
+use sea_orm::Order;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+pub const DEFAULT_PAGE_SIZE: u64 = 25;
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Name,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Page, page size and sorting of list endpoints
+#[derive(Debug, Deserialize, Serialize, Clone, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    /// Page number, starting from 1
+    #[serde(default = "default_page")]
+    pub page: u64,
+    /// Items per page, at most 100
+    #[serde(default = "default_page_size")]
+    pub page_size: u64,
+    #[serde(default)]
+    pub sort_by: SortBy,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl Default for ListParams {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            page_size: default_page_size(),
+            sort_by: SortBy::default(),
+            order: SortOrder::default(),
+        }
+    }
+}
+
+impl ListParams {
+    #[must_use]
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery {
+            page: self.page.max(1),
+            page_size: self.page_size.clamp(1, MAX_PAGE_SIZE),
+        }
+    }
+
+    #[must_use]
+    pub fn order(&self) -> Order {
+        match self.order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        }
+    }
+}
+
+const fn default_page() -> u64 {
+    1
+}
+
+const fn default_page_size() -> u64 {
+    DEFAULT_PAGE_SIZE
+}
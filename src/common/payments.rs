@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use loco_rs::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::common::settings::{PaymentProviderSettings, PaymentSettings};
+
+/// Header carrying the hex encoded HMAC-SHA256 of the webhook body
+pub const SIGNATURE_HEADER: &str = "x-payment-signature";
+
+/// What the User is about to pay for
+#[derive(Debug)]
+pub struct Checkout<'a> {
+    pub purchase_id: i32,
+    pub description: &'a str,
+    pub amount_cents: i32,
+    pub currency: &'a str,
+}
+
+#[derive(Debug)]
+pub struct CheckoutSession {
+    /// Identifies the checkout in webhooks of the provider
+    pub reference: String,
+    /// Page where the User pays
+    pub url: String,
+}
+
+/// Outcome of a checkout reported by the provider
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PaymentEvent {
+    Succeeded { reference: String },
+    Failed { reference: String },
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Starts a checkout at the provider
+    ///
+    /// # Errors
+    ///
+    /// When the provider refuses the checkout
+    async fn create_checkout(&self, checkout: &Checkout<'_>) -> Result<CheckoutSession>;
+
+    /// Checks that the webhook comes from the provider and reads the event from it
+    ///
+    /// # Errors
+    ///
+    /// When the signature does not match the payload or the payload is malformed
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<PaymentEvent>;
+}
+
+/// Provider that never charges anyone. Checkouts only succeed
+/// when a webhook signed with the shared secret says so
+pub struct FakeProvider {
+    webhook_secret: String,
+    frontend: String,
+}
+
+impl FakeProvider {
+    #[must_use]
+    pub fn new(webhook_secret: &str, frontend: &str) -> Self {
+        Self {
+            webhook_secret: webhook_secret.to_string(),
+            frontend: frontend.to_string(),
+        }
+    }
+
+    /// Signs a webhook payload the way the provider does
+    ///
+    /// # Errors
+    ///
+    /// When the secret can not be used as a key
+    pub fn sign(&self, payload: &[u8]) -> Result<String> {
+        Ok(hex::encode(self.mac(payload)?.finalize().into_bytes()))
+    }
+
+    fn mac(&self, payload: &[u8]) -> Result<Hmac<Sha256>> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.webhook_secret.as_bytes())
+            .map_err(|_| Error::Message("invalid webhook secret".to_string()))?;
+        mac.update(payload);
+
+        Ok(mac)
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for FakeProvider {
+    async fn create_checkout(&self, checkout: &Checkout<'_>) -> Result<CheckoutSession> {
+        let reference = format!("fake_{}", Uuid::new_v4());
+
+        tracing::info!(
+            purchase_id = checkout.purchase_id,
+            amount_cents = checkout.amount_cents,
+            currency = checkout.currency,
+            reference,
+            "fake checkout started"
+        );
+
+        Ok(CheckoutSession {
+            url: format!("{}/checkout/{reference}", self.frontend),
+            reference,
+        })
+    }
+
+    fn verify_webhook(&self, payload: &[u8], signature: &str) -> Result<PaymentEvent> {
+        let signature = hex::decode(signature.trim())
+            .map_err(|_| Error::Unauthorized("invalid webhook signature".to_string()))?;
+
+        self.mac(payload)?
+            .verify_slice(&signature)
+            .map_err(|_| Error::Unauthorized("invalid webhook signature".to_string()))?;
+
+        serde_json::from_slice(payload)
+            .map_err(|_| Error::BadRequest("malformed webhook payload".to_string()))
+    }
+}
+
+/// Builds the provider from the `settings.payments` section of the config
+#[must_use]
+pub fn build(settings: &PaymentSettings, frontend: &str) -> Box<dyn PaymentProvider> {
+    match &settings.provider {
+        PaymentProviderSettings::Fake { webhook_secret } => {
+            Box::new(FakeProvider::new(webhook_secret, frontend))
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::models::_entities::sea_orm_active_enums::AccessLevelEnum::{
+    self, AddSolution, AddUser, Edit, FullAccess, View,
+};
+
+/// Everything a User can do with a Task that depends on their Access to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// See the Task, its subtasks and Attachments
+    ReadTask,
+    /// Change name, visibility and price of the Task
+    EditTask,
+    DeleteTask,
+    /// Create subtasks under the Task, or move other Tasks under it
+    AddSubtask,
+    MoveTask,
+    ViewHistory,
+    /// Grant, change and deny Accesses, Group Accesses and invitations
+    ManageAccess,
+    UploadFile,
+    /// Change or remove Attachments of the Task
+    EditAttachment,
+    SubmitSolution,
+    ReviewSolutions,
+    /// Take an open Task into work and hand it in for review
+    StartWork,
+    /// Reopen the Task, accept or return work under review
+    ReviewWork,
+    /// Archive the Task or bring it back from the archive
+    ArchiveTask,
+    /// Comment on the Task and reply to comments, without being able to change it
+    AddComment,
+    /// Remove comments of other Users
+    ModerateComments,
+}
+
+impl Action {
+    pub const ALL: [Self; 16] = [
+        Self::ReadTask,
+        Self::EditTask,
+        Self::DeleteTask,
+        Self::AddSubtask,
+        Self::MoveTask,
+        Self::ViewHistory,
+        Self::ManageAccess,
+        Self::UploadFile,
+        Self::EditAttachment,
+        Self::SubmitSolution,
+        Self::ReviewSolutions,
+        Self::StartWork,
+        Self::ReviewWork,
+        Self::ArchiveTask,
+        Self::AddComment,
+        Self::ModerateComments,
+    ];
+
+    /// Access levels that allow the action
+    #[must_use]
+    pub const fn levels(self) -> &'static [AccessLevelEnum] {
+        match self {
+            Self::ReadTask | Self::AddComment => &[FullAccess, AddUser, Edit, AddSolution, View],
+            Self::EditTask | Self::UploadFile | Self::EditAttachment => {
+                &[FullAccess, AddUser, Edit]
+            }
+            Self::AddSubtask | Self::ReviewSolutions | Self::ReviewWork => &[FullAccess, Edit],
+            Self::ManageAccess => &[FullAccess, AddUser],
+            Self::SubmitSolution => &[AddSolution],
+            Self::StartWork => &[FullAccess, Edit, AddSolution],
+            Self::DeleteTask
+            | Self::MoveTask
+            | Self::ViewHistory
+            | Self::ArchiveTask
+            | Self::ModerateComments => &[FullAccess],
+        }
+    }
+
+    #[must_use]
+    pub fn allows(self, level: AccessLevelEnum) -> bool {
+        self.levels().contains(&level)
+    }
+}
+
+/// Ties a marker type to an [`Action`], so that handlers can demand it in their
+/// signature with [`crate::common::extractors::Authorized`]
+pub trait Permission {
+    const ACTION: Action;
+}
+
+macro_rules! permissions {
+    ($($action:ident),* $(,)?) => {
+        $(
+            pub struct $action;
+
+            impl Permission for $action {
+                const ACTION: Action = Action::$action;
+            }
+        )*
+    };
+}
+
+permissions!(
+    ReadTask,
+    EditTask,
+    DeleteTask,
+    AddSubtask,
+    MoveTask,
+    ViewHistory,
+    ManageAccess,
+    UploadFile,
+    EditAttachment,
+    SubmitSolution,
+    ReviewSolutions,
+    StartWork,
+    ReviewWork,
+    ArchiveTask,
+    AddComment,
+    ModerateComments,
+);
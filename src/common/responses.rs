@@ -1,5 +1,6 @@
 use axum::http::StatusCode;
 use loco_rs::{controller::ErrorDetail, errors::Error, Result};
+use validator::ValidationErrors;
 
 pub fn unauthorized<T: Into<String>, U>(msg: T) -> Result<U> {
     Err(Error::CustomError(
@@ -52,3 +53,14 @@ pub fn internal<U>() -> Result<U> {
 pub fn bad_request<T: Into<String>, U>(msg: T) -> Result<U> {
     Err(Error::BadRequest(msg.into()))
 }
+
+pub fn invalid<U>(errors: &ValidationErrors) -> Result<U> {
+    Err(Error::CustomError(
+        StatusCode::BAD_REQUEST,
+        ErrorDetail {
+            error: Some("Bad Request".to_string()),
+            description: Some("Validation failed".to_string()),
+            errors: serde_json::to_value(errors).ok(),
+        },
+    ))
+}
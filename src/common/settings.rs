@@ -5,6 +5,59 @@ use serde::{Deserialize, Serialize};
 pub struct Settings {
     pub frontend: String,
     pub backend: String,
+    /// Required outside the test environment, where files are kept in memory without it
+    pub storage: Option<StorageSettings>,
+    #[serde(default)]
+    pub reminders: ReminderSettings,
+    /// Selling Paid Tasks is disabled without it
+    pub payments: Option<PaymentSettings>,
+}
+
+/// Checkout of Paid Tasks
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaymentSettings {
+    /// ISO 4217 code of the currency Task prices are in
+    pub currency: String,
+    pub provider: PaymentProviderSettings,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PaymentProviderSettings {
+    /// Local provider without real payments, webhooks are signed with `webhook_secret`.
+    /// Meant for development and tests
+    Fake { webhook_secret: String },
+}
+
+/// Due date reminders sent by the `DueReminderWorker`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReminderSettings {
+    /// Tasks due within this many hours get a reminder
+    pub window_hours: i64,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self { window_hours: 24 }
+    }
+}
+
+/// Storage backend used for uploaded files
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StorageSettings {
+    /// Keeps files in memory, everything is lost on restart. Meant for tests
+    Memory,
+    /// Keeps files in a directory on the local disk
+    Local { path: String },
+    /// Keeps files in an S3 bucket. `endpoint` allows S3-compatible services such as MinIO
+    S3 {
+        bucket: String,
+        region: String,
+        endpoint: Option<String>,
+        access_key_id: Option<String>,
+        secret_access_key: Option<String>,
+    },
 }
 
 impl Settings {
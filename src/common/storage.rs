@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use axum::body::Body;
+use bytes::Bytes;
+use loco_rs::{
+    app::AppContext,
+    environment::Environment,
+    storage::{drivers::opendal_adapter::OpendalAdapter, Storage},
+    Error, Result,
+};
+use opendal::Operator;
+
+use crate::common::settings::StorageSettings;
+
+const WRITE_CHECK_FILE: &str = ".write-check";
+
+/// Files behind the Storage of the application, for reads that the Storage does not offer.
+/// Kept in the shared store of the [`AppContext`], so that each booted application reads
+/// its own files
+#[derive(Clone, Debug)]
+pub struct StoredFiles {
+    operator: Operator,
+}
+
+fn operator_error(e: &opendal::Error, message: &str) -> Error {
+    tracing::error!(error = ?e, message);
+    Error::Message(message.to_string())
+}
+
+/// Storage settings to boot with. Only the test environment may leave them out,
+/// it then keeps files in memory
+///
+/// # Errors
+///
+/// When `settings.storage` is absent outside the test environment
+pub fn configured(
+    environment: &Environment,
+    settings: Option<StorageSettings>,
+) -> Result<StorageSettings> {
+    match settings {
+        Some(settings) => Ok(settings),
+        None if matches!(environment, Environment::Test) => Ok(StorageSettings::Memory),
+        None => Err(Error::Message(format!(
+            "`settings.storage` is required in the {environment} environment"
+        ))),
+    }
+}
+
+/// Builds the Storage from the `settings.storage` section of the config,
+/// along with the [`StoredFiles`] it keeps
+///
+/// # Errors
+///
+/// When the configured driver could not be created
+pub fn build(settings: &StorageSettings) -> Result<(Storage, StoredFiles)> {
+    let operator = match settings {
+        StorageSettings::Memory => Operator::new(opendal::services::Memory::default())
+            .map_err(|e| operator_error(&e, "could not create memory storage driver"))?
+            .finish(),
+        StorageSettings::Local { path } => {
+            std::fs::create_dir_all(path).map_err(|e| {
+                tracing::error!(error = ?e, path, "could not create storage directory");
+                Error::Message(format!("could not create storage directory `{path}`"))
+            })?;
+
+            Operator::new(opendal::services::Fs::default().root(path))
+                .map_err(|e| operator_error(&e, "could not create local storage driver"))?
+                .finish()
+        }
+        StorageSettings::S3 {
+            bucket,
+            region,
+            endpoint,
+            access_key_id,
+            secret_access_key,
+        } => {
+            let mut builder = opendal::services::S3::default()
+                .bucket(bucket)
+                .region(region);
+
+            if let Some(endpoint) = endpoint {
+                builder = builder.endpoint(endpoint);
+            }
+            if let Some(access_key_id) = access_key_id {
+                builder = builder.access_key_id(access_key_id);
+            }
+            if let Some(secret_access_key) = secret_access_key {
+                builder = builder.secret_access_key(secret_access_key);
+            }
+
+            Operator::new(builder)
+                .map_err(|e| operator_error(&e, "could not create s3 storage driver"))?
+                .finish()
+        }
+    };
+
+    let files = StoredFiles {
+        operator: operator.clone(),
+    };
+
+    Ok((
+        Storage::single(Box::new(OpendalAdapter::new(operator))),
+        files,
+    ))
+}
+
+impl StoredFiles {
+    /// Files of the application booted with the context
+    ///
+    /// # Errors
+    ///
+    /// When the context was created without building the Storage
+    pub fn from_context(ctx: &AppContext) -> Result<Self> {
+        ctx.shared_store
+            .get::<Self>()
+            .ok_or_else(|| Error::Message("storage is not built".to_string()))
+    }
+
+    /// Size of the stored file in bytes
+    ///
+    /// # Errors
+    ///
+    /// When the file does not exist or could not be read
+    pub async fn size(&self, path: &Path) -> Result<u64> {
+        let meta = self
+            .operator
+            .stat(&path.display().to_string())
+            .await
+            .map_err(|e| operator_error(&e, "could not read stored file metadata"))?;
+
+        Ok(meta.content_length())
+    }
+
+    /// Reads only the bytes `start..=end` of the stored file
+    ///
+    /// # Errors
+    ///
+    /// When the file does not exist or could not be read
+    pub async fn read_range(&self, path: &Path, start: u64, end: u64) -> Result<Vec<u8>> {
+        let buffer = self
+            .operator
+            .read_with(&path.display().to_string())
+            .range(start..=end)
+            .await
+            .map_err(|e| operator_error(&e, "could not read stored file"))?;
+
+        Ok(buffer.to_vec())
+    }
+
+    /// Streams the whole stored file without loading it into memory
+    ///
+    /// # Errors
+    ///
+    /// When the file does not exist or could not be opened
+    pub async fn stream(&self, path: &Path) -> Result<Body> {
+        let stream = self
+            .operator
+            .reader(&path.display().to_string())
+            .await
+            .map_err(|e| operator_error(&e, "could not open stored file"))?
+            .into_bytes_stream(..)
+            .await
+            .map_err(|e| operator_error(&e, "could not read stored file"))?;
+
+        Ok(Body::from_stream(stream))
+    }
+}
+
+/// Verifies that the Storage accepts writes by uploading and removing a probe file
+///
+/// # Errors
+///
+/// When the probe file could not be written or removed
+pub async fn check_writable(storage: &Storage) -> Result<()> {
+    let path = Path::new(WRITE_CHECK_FILE);
+
+    storage
+        .upload(path, &Bytes::from_static(b"ok"))
+        .await
+        .map_err(|e| {
+            tracing::error!(error = ?e, "storage is not writable");
+            Error::Message("storage is not writable".to_string())
+        })?;
+
+    storage.delete(path).await.map_err(|e| {
+        tracing::error!(error = ?e, "could not remove storage write check file");
+        Error::Message("could not remove storage write check file".to_string())
+    })?;
+
+    Ok(())
+}
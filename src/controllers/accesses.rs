@@ -3,7 +3,9 @@ use loco_openapi::prelude::*;
 use loco_rs::prelude::*;
 
 use crate::{
-    models::{accesses, tasks},
+    common::{extractors::Authorized, policy::ManageAccess, responses},
+    mailers::task::TaskMailer,
+    models::{accesses, group_accesses, groups, invitations, tasks, users},
     views,
 };
 
@@ -25,21 +27,10 @@ use crate::{
 )]
 #[debug_handler]
 pub async fn list_accesses(
-    auth: auth::JWT,
+    auth: Authorized<ManageAccess>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-        ],
-    )
-    .await?;
-
     let accesses = accesses::Model::list_for_task(&ctx.db, task_id).await?;
 
     format::json(views::access::AccessResponse::from_vec(accesses))
@@ -47,14 +38,18 @@ pub async fn list_accesses(
 
 /// Grant Access
 ///
-/// Grant Access to the Task for the User
+/// Grant Access to the Task for the User. Emails without an account get an
+/// invitation, the Access is granted once they register
 #[utoipa::path(
     post,
     path = "/api/tasks/access",
     tag = "tasks",
     responses(
-        (status = 200, description = "Access is grant"),
+        (status = 200, description = "Access is grant or invitation is sent"),
+        (status = 400, description = "Access expires before it starts"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 409, description = "User already has Access to the Task"),
         (status = 500, description = "Internal server error")
     ),
     params(
@@ -64,23 +59,43 @@ pub async fn list_accesses(
 )]
 #[debug_handler]
 pub async fn grant_access(
-    auth: auth::JWT,
+    auth: Authorized<ManageAccess>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<accesses::GrantParams>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-        ],
-    )
-    .await?;
+    if let Err(err) = accesses::check_window(params.starts_at, params.expires_at) {
+        return responses::bad_request(err.to_string());
+    }
 
-    accesses::Model::grant_access(&ctx.db, task_id, params).await?;
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    granter.check_level(params.accesslevel)?;
+
+    match users::Model::find_by_email(&ctx.db, &params.email).await {
+        Ok(user) => {
+            if user.id == granter.owner_id {
+                return responses::forbidden("Access of the owner can not be managed");
+            }
+
+            match accesses::Model::grant_access(&ctx.db, &auth.claims.pid, task_id, params).await {
+                Ok(_) => {}
+                Err(ModelError::EntityAlreadyExists) => {
+                    return responses::conflict("User already has Access to the Task")
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(ModelError::EntityNotFound) => {
+            let invitation =
+                invitations::Model::invite(&ctx.db, &auth.claims.pid, task_id, &params).await?;
+
+            let inviter = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+            let task = tasks::Model::load(&ctx.db, task_id).await?;
+
+            TaskMailer::send_invitation(&ctx, &inviter, &task, &invitation).await?;
+        }
+        Err(err) => return Err(err.into()),
+    }
 
     format::empty()
 }
@@ -94,7 +109,10 @@ pub async fn grant_access(
     tag = "tasks",
     responses(
         (status = 200, description = "Access is updated", body = views::access::AccessResponse),
+        (status = 400, description = "Access expires before it starts"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 409, description = "Task would be left without FullAccess"),
         (status = 500, description = "Internal server error")
     ),
     params(
@@ -104,23 +122,32 @@ pub async fn grant_access(
 )]
 #[debug_handler]
 pub async fn update_access(
-    auth: auth::JWT,
+    auth: Authorized<ManageAccess>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<accesses::UpdateParams>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
+    if let Err(err) = accesses::check_window(params.starts_at, params.expires_at) {
+        return responses::bad_request(err.to_string());
+    }
+
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    let access = accesses::Model::find_by_pid(&ctx.db, task_id, &params.pid).await?;
+    granter.check_access(&access)?;
+    granter.check_level(params.accesslevel)?;
+
+    let access = match accesses::ActiveModel::update_access(
         &ctx.db,
         &auth.claims.pid,
         task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-        ],
+        params,
     )
-    .await?;
-
-    let access = accesses::ActiveModel::update_access(&ctx.db, task_id, params).await?;
+    .await
+    {
+        Ok(access) => access,
+        Err(ModelError::Message(msg)) => return responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    };
 
     format::json(views::access::AccessResponse::new(access))
 }
@@ -135,6 +162,8 @@ pub async fn update_access(
     responses(
         (status = 200, description = "Access is Denied"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 409, description = "Task would be left without FullAccess"),
         (status = 500, description = "Internal server error")
     ),
     params(
@@ -144,23 +173,190 @@ pub async fn update_access(
 )]
 #[debug_handler]
 pub async fn deny_access(
-    auth: auth::JWT,
+    auth: Authorized<ManageAccess>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<accesses::DenyParams>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-        ],
-    )
-    .await?;
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    let access = accesses::Model::find_by_pid(&ctx.db, task_id, &params.pid).await?;
+    granter.check_access(&access)?;
+
+    match accesses::ActiveModel::deny_access(&ctx.db, &auth.claims.pid, task_id, params).await {
+        Ok(()) => {}
+        Err(ModelError::Message(msg)) => return responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    }
+
+    format::empty()
+}
+
+/// List Task Group Accesses
+///
+/// List the Groups the Task is shared with
+#[utoipa::path(
+    get,
+    path = "/api/tasks/access/{id}/groups",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Array of Group Access objects", body = Vec<views::access::GroupAccessResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn list_group_accesses(
+    auth: Authorized<ManageAccess>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let accesses = group_accesses::Model::list_for_task(&ctx.db, task_id).await?;
+
+    format::json(views::access::GroupAccessResponse::from_vec(&accesses))
+}
+
+/// Grant Group Access
+///
+/// Grant Access to the Task for every member of the Group, or change the one it has
+#[utoipa::path(
+    post,
+    path = "/api/tasks/access/{id}/groups",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Access is granted", body = views::access::GroupAccessResponse),
+        (status = 401, description = "Unauthorized, or the Group is owned by someone else"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 404, description = "Task or Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = group_accesses::GrantParams
+)]
+#[debug_handler]
+pub async fn grant_group_access(
+    auth: Authorized<ManageAccess>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<group_accesses::GrantParams>,
+) -> Result<Response> {
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    granter.check_level(params.accesslevel)?;
+
+    // the owner of a Group decides who is in it, so only they can give it a Task
+    groups::Model::load_owned(&ctx.db, &auth.claims.pid, params.group_id).await?;
+
+    match group_accesses::Model::find_by_group(&ctx.db, task_id, params.group_id).await {
+        Ok(existing) => granter.check_level(existing.accesslevel)?,
+        Err(ModelError::EntityNotFound) => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let access =
+        group_accesses::Model::grant_access(&ctx.db, &auth.claims.pid, task_id, &params).await?;
+
+    format::json(views::access::GroupAccessResponse::new(&access))
+}
+
+/// Deny Group Access
+///
+/// Deny Access to the Task for the Group
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/access/{id}/groups",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Access is Denied"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 404, description = "Group Access not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = group_accesses::DenyParams
+)]
+#[debug_handler]
+pub async fn deny_group_access(
+    auth: Authorized<ManageAccess>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<group_accesses::DenyParams>,
+) -> Result<Response> {
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    let access = group_accesses::Model::find_by_group(&ctx.db, task_id, params.group_id).await?;
+    granter.check_level(access.accesslevel)?;
+
+    group_accesses::ActiveModel::deny_access(&ctx.db, &auth.claims.pid, task_id, &params).await?;
+
+    format::empty()
+}
+
+/// List Invitations
+///
+/// List pending invitations to the Task of emails without an account
+#[utoipa::path(
+    get,
+    path = "/api/tasks/access/{id}/invitations",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Array of Invitation objects", body = Vec<views::invitation::InvitationResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn list_invitations(
+    auth: Authorized<ManageAccess>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let invitations = invitations::Model::list_for_task(&ctx.db, task_id).await?;
 
-    accesses::ActiveModel::deny_access(&ctx.db, task_id, params).await?;
+    format::json(views::invitation::InvitationResponse::from_vec(
+        &invitations,
+    ))
+}
+
+/// Revoke Invitation
+///
+/// Revoke the pending invitation of the email to the Task
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/access/{id}/invitations",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Invitation is revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access level is not below the own one"),
+        (status = 404, description = "Invitation not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = invitations::RevokeParams
+)]
+#[debug_handler]
+pub async fn revoke_invitation(
+    auth: Authorized<ManageAccess>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<invitations::RevokeParams>,
+) -> Result<Response> {
+    let granter = accesses::Granter::load(&ctx.db, &auth.claims.pid, task_id).await?;
+    let invitation = invitations::Model::find_by_email(&ctx.db, task_id, &params.email).await?;
+    granter.check_level(invitation.accesslevel)?;
+
+    invitations::ActiveModel::revoke(&ctx.db, task_id, &params).await?;
 
     format::empty()
 }
@@ -176,4 +372,24 @@ pub fn routes() -> Routes {
             openapi(patch(update_access), routes!(update_access)),
         )
         .add("{id}", put(update_access))
+        .add(
+            "{id}/groups",
+            openapi(get(list_group_accesses), routes!(list_group_accesses)),
+        )
+        .add(
+            "{id}/groups",
+            openapi(post(grant_group_access), routes!(grant_group_access)),
+        )
+        .add(
+            "{id}/groups",
+            openapi(delete(deny_group_access), routes!(deny_group_access)),
+        )
+        .add(
+            "{id}/invitations",
+            openapi(get(list_invitations), routes!(list_invitations)),
+        )
+        .add(
+            "{id}/invitations",
+            openapi(delete(revoke_invitation), routes!(revoke_invitation)),
+        )
 }
@@ -3,60 +3,224 @@
 #![allow(clippy::unused_async)]
 use std::path::PathBuf;
 
-use axum::debug_handler;
+use axum::{
+    body::Body,
+    debug_handler,
+    extract::Query,
+    http::{header, HeaderMap, StatusCode},
+};
 
 use axum_typed_multipart::TypedMultipart;
 use loco_openapi::prelude::*;
 use loco_rs::prelude::*;
 
 use crate::{
-    common::responses,
+    common::{
+        extractors::Authorized,
+        pagination::ListParams,
+        policy::{Action, ReadTask, UploadFile},
+        responses,
+        storage::StoredFiles,
+    },
     models::{
         attachments::{self, *},
         tasks,
     },
-    views::attachment::*,
+    views::{attachment::*, page::PageResponse},
 };
 
 /// List Attachments
 ///
-/// List Attachments of the Task
+/// List a page of Attachments of the Task
 #[utoipa::path(
     get,
     path = "/api/tasks/attachments/{id}",
     tag = "attachments",
     responses(
-        (status = 200, description = "Array of Attachment objects", body = Vec<AttachmentResponse>),
+        (status = 200, description = "Page of Attachment objects", body = PageResponse<AttachmentResponse>),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     ),
     params(
         ("id" = i32, Path, description = "Task id"),
+        ListParams,
+        AttachmentFilter,
     ),
 )]
 #[debug_handler]
 pub async fn list(
-    auth: auth::JWT,
+    auth: Authorized<ReadTask>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+    Query(filter): Query<AttachmentFilter>,
+) -> Result<Response> {
+    let page = attachments::Model::page_attachments(&ctx.db, task_id, &list, &filter).await?;
+
+    format::json(PageResponse::new(page, &list, AttachmentResponse::new))
+}
+
+/// Download Attachment
+///
+/// Download the contents of a File Attachment.
+/// Supports conditional requests via `ETag` and partial content via `Range`
+#[utoipa::path(
+    get,
+    path = "/api/tasks/attachments/{id}/content",
+    tag = "attachments",
+    responses(
+        (status = 200, description = "File contents", content_type = "application/octet-stream"),
+        (status = 206, description = "Partial file contents", content_type = "application/octet-stream"),
+        (status = 304, description = "File is not modified"),
+        (status = 400, description = "Attachment is not a File"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Attachment not found"),
+        (status = 416, description = "Range not satisfiable"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Attachment id"),
+    ),
+)]
+#[debug_handler]
+pub async fn content(
+    auth: auth::JWT,
+    Path(attachment_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    headers: HeaderMap,
 ) -> Result<Response> {
-    tasks::Model::has_access(
+    let attachment = match attachments::Model::load(&ctx.db, attachment_id).await {
+        Ok(att) => att,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Attachment not found."),
+        _ => return responses::internal(),
+    };
+
+    tasks::Model::authorize(
         &ctx.db,
         &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-            tasks::AccessLevelEnum::Edit,
-            tasks::AccessLevelEnum::AddSolution,
-            tasks::AccessLevelEnum::View,
-        ],
+        attachment.task_id,
+        Action::ReadTask,
     )
     .await?;
 
-    let attachment = attachments::Model::list_attachments(&ctx.db, task_id).await?;
+    if attachment.attachment_type != AttachmentTypeEnum::File {
+        return responses::bad_request("Only File Attachments have downloadable content");
+    }
+
+    let etag = format!(
+        "\"{}-{}\"",
+        attachment.id,
+        attachment.updated_at.timestamp_millis()
+    );
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+
+    if not_modified {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, &etag)
+            .body(Body::empty())?);
+    }
 
-    format::json(AttachmentResponse::from_vec(attachment))
+    let files = StoredFiles::from_context(&ctx)?;
+    let path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+    let Ok(total) = files.size(path.as_path()).await else {
+        tracing::warn!(
+            attachment_id = attachment.id,
+            "could not read attachment content from storage"
+        );
+        return responses::notfound("Attachment content not found.");
+    };
+    let content_type = mime_guess::from_path(&attachment.data).first_or_octet_stream();
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        attachment.data.replace(['"', '\\'], "_")
+    );
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, content_type.as_ref())
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .header(header::ETAG, &etag)
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    // Only honour the Range header when it still refers to the current file version
+    let range = headers
+        .get(header::RANGE)
+        .filter(|_| {
+            headers
+                .get(header::IF_RANGE)
+                .is_none_or(|value| value.to_str().is_ok_and(|tag| tag.trim() == etag))
+        })
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| value.trim_start().starts_with("bytes="));
+
+    match range.map(|range| parse_range(range, total)) {
+        None => {
+            let body = files.stream(path.as_path()).await?;
+
+            Ok(response
+                .status(StatusCode::OK)
+                .header(header::CONTENT_LENGTH, total)
+                .body(body)?)
+        }
+        Some(Some((start, end))) => {
+            // only the requested part is read from the storage
+            let chunk = files.read_range(path.as_path(), start, end).await?;
+
+            Ok(response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {start}-{end}/{total}"),
+                )
+                .header(header::CONTENT_LENGTH, chunk.len())
+                .body(Body::from(chunk))?)
+        }
+        Some(None) => Ok(Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{total}"))
+            .body(Body::empty())?),
+    }
+}
+
+/// Parses a single `bytes=` range against the content length.
+/// Returns inclusive `(start, end)` offsets, or `None` when the range is not satisfiable
+fn parse_range(range: &str, total: u64) -> Option<(u64, u64)> {
+    let spec = range.trim().strip_prefix("bytes=")?;
+
+    // Multipart ranges are not supported
+    if spec.contains(',') || total == 0 {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix = suffix.parse::<u64>().ok()?.min(total);
+            if suffix == 0 {
+                return None;
+            }
+            (total - suffix, total - 1)
+        }
+        (start, "") => (start.parse::<u64>().ok()?, total - 1),
+        (start, end) => (
+            start.parse::<u64>().ok()?,
+            end.parse::<u64>().ok()?.min(total - 1),
+        ),
+    };
+
+    if start > end || start >= total {
+        return None;
+    }
+
+    Some((start, end))
 }
 
 /// Add Attachment
@@ -73,7 +237,7 @@ pub async fn list(
     ),
     responses(
         (status = 200, description = "Attachment is added/uploaded", body = AttachmentResponse),
-        (status = 400, description = "Bad Request"),
+        (status = 400, description = "Bad Request, lists the invalid fields"),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     ),
@@ -83,23 +247,11 @@ pub async fn list(
 )]
 #[debug_handler]
 pub async fn add(
-    auth: auth::JWT,
+    auth: Authorized<UploadFile>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
     TypedMultipart(form): TypedMultipart<attachments::AttachmentAddForm>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-            tasks::AccessLevelEnum::Edit,
-        ],
-    )
-    .await?;
-
     match form.attachment_type {
         AttachmentTypeEnum::File => {
             let (file_name, content) = if let Some(field) = form.file {
@@ -123,16 +275,35 @@ pub async fn add(
                 data: file_name.clone(),
             };
 
-            let attachment =
-                attachments::Model::add_attachment(&ctx.db, &auth.claims.pid, task_id, params)
-                    .await?;
+            let attachment = match attachments::Model::add_attachment(
+                &ctx.db,
+                &auth.claims.pid,
+                task_id,
+                params,
+            )
+            .await
+            {
+                Ok(attachment) => attachment,
+                Err(AttachmentError::Invalid(errors)) => return responses::invalid(&errors),
+                Err(AttachmentError::Model(ModelError::Message(msg))) => {
+                    return responses::bad_request(msg)
+                }
+                Err(AttachmentError::Model(err)) => return Err(err.into()),
+            };
 
             let path = PathBuf::from(attachment.id.to_string()).join(&file_name);
 
-            ctx.storage
-                .as_ref()
-                .upload(path.as_path(), &content)
-                .await?;
+            if let Err(e) = ctx.storage.as_ref().upload(path.as_path(), &content).await {
+                tracing::error!(error = ?e, attachment_id = attachment.id, "could not upload attachment file");
+
+                ctx.storage.as_ref().delete(path.as_path()).await.ok();
+                attachment
+                    .into_active_model()
+                    .remove_attachment(&ctx.db, &auth.claims.pid)
+                    .await?;
+
+                return responses::internal();
+            }
 
             format::json(AttachmentResponse::new(attachment))
         }
@@ -148,9 +319,21 @@ pub async fn add(
                 data: form.data,
             };
 
-            let attachment =
-                attachments::Model::add_attachment(&ctx.db, &auth.claims.pid, task_id, params)
-                    .await?;
+            let attachment = match attachments::Model::add_attachment(
+                &ctx.db,
+                &auth.claims.pid,
+                task_id,
+                params,
+            )
+            .await
+            {
+                Ok(attachment) => attachment,
+                Err(AttachmentError::Invalid(errors)) => return responses::invalid(&errors),
+                Err(AttachmentError::Model(ModelError::Message(msg))) => {
+                    return responses::bad_request(msg)
+                }
+                Err(AttachmentError::Model(err)) => return Err(err.into()),
+            };
 
             format::json(AttachmentResponse::new(attachment))
         }
@@ -171,7 +354,7 @@ pub async fn add(
     ),
     responses(
         (status = 200, description = "Attachment is updated", body = AttachmentResponse),
-        (status = 400, description = "Bad Request"),
+        (status = 400, description = "Bad Request, lists the invalid fields"),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     ),
@@ -192,19 +375,16 @@ pub async fn update(
         _ => return responses::internal(),
     };
 
-    tasks::Model::has_access(
+    tasks::Model::authorize(
         &ctx.db,
         &auth.claims.pid,
         attachment.task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-            tasks::AccessLevelEnum::Edit,
-        ],
+        Action::EditAttachment,
     )
     .await?;
 
-    let data = match attachment.attachment_type {
+    // the row is switched to a stored file, the replaced one is only removed afterwards
+    let (data, replaced) = match attachment.attachment_type {
         AttachmentTypeEnum::File => match form.file {
             Some(field) => {
                 let file_name = field
@@ -213,15 +393,21 @@ pub async fn update(
                     .ok_or_else(|| Error::BadRequest("File field missing filename".into()))?
                     .to_string();
 
-                let bytes = field.contents;
+                // the name becomes part of the storage path, check it before touching the files
+                if let Err(errors) = AttachmentData::parse(AttachmentTypeEnum::File, &file_name) {
+                    return responses::invalid(&errors);
+                }
 
-                let old_path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
-                ctx.storage.as_ref().delete(old_path.as_path()).await.ok();
+                let bytes = field.contents;
 
                 let path = PathBuf::from(attachment.id.to_string()).join(&file_name);
                 ctx.storage.as_ref().upload(path.as_path(), &bytes).await?;
 
-                file_name
+                // uploading under the same name already replaced the content
+                let old_path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+                let replaced = (old_path != path).then_some((old_path, path));
+
+                (file_name, replaced)
             }
             None => return responses::bad_request("File content is required for such Attachment"),
         },
@@ -230,16 +416,43 @@ pub async fn update(
                 return responses::bad_request("File content is not expected for such Attachment");
             }
 
-            form.data
+            (form.data, None)
         }
     };
 
     let params = AttachmentUpdateParams { data };
 
-    let updated_attachment = attachment
+    let result = attachment
         .into_active_model()
         .update_attachment(&ctx.db, &auth.claims.pid, params)
-        .await?;
+        .await;
+
+    let updated_attachment = match result {
+        Ok(attachment) => attachment,
+        Err(err) => {
+            if let Some((_, path)) = &replaced {
+                ctx.storage.as_ref().delete(path.as_path()).await.ok();
+            }
+
+            match err {
+                AttachmentError::Invalid(errors) => return responses::invalid(&errors),
+                AttachmentError::Model(ModelError::Message(msg)) => {
+                    return responses::bad_request(msg)
+                }
+                AttachmentError::Model(err) => return Err(err.into()),
+            }
+        }
+    };
+
+    if let Some((old_path, _)) = replaced {
+        if let Err(e) = ctx.storage.as_ref().delete(old_path.as_path()).await {
+            tracing::warn!(
+                error = ?e,
+                attachment_id = updated_attachment.id,
+                "could not remove replaced attachment file"
+            );
+        }
+    }
 
     format::json(AttachmentResponse::new(updated_attachment))
 }
@@ -266,15 +479,11 @@ pub async fn remove(
     Path(attachment_id): Path<i32>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    attachments::Model::has_access(
+    attachments::Model::authorize(
         &ctx.db,
         &auth.claims.pid,
         attachment_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::AddUser,
-            tasks::AccessLevelEnum::Edit,
-        ],
+        Action::EditAttachment,
     )
     .await?;
 
@@ -289,7 +498,10 @@ pub async fn remove(
         ctx.storage.as_ref().delete(path.as_path()).await.ok();
     }
 
-    attachment.into_active_model().delete(&ctx.db).await?;
+    attachment
+        .into_active_model()
+        .remove_attachment(&ctx.db, &auth.claims.pid)
+        .await?;
 
     format::empty()
 }
@@ -298,6 +510,7 @@ pub fn routes() -> Routes {
     Routes::new()
         .prefix("api/tasks/attachments/")
         .add("{id}", openapi(get(list), routes!(list)))
+        .add("{id}/content", openapi(get(content), routes!(content)))
         .add("{id}", openapi(post(add), routes!(add)))
         .add("{id}", openapi(patch(update), routes!(update)))
         .add("{id}", put(update))
@@ -0,0 +1,125 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::{
+    body::Body,
+    debug_handler,
+    http::{header, StatusCode},
+};
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{responses, settings::Settings},
+    models::{attachments, users},
+    views::calendar::{self, CalendarTokenResponse},
+};
+
+/// Calendar Feed
+///
+/// iCalendar feed with an event at every DueDate of the Tasks the User can access.
+/// The URL contains the secret token of the User instead of requiring authentication,
+/// so that calendar apps can subscribe to it
+#[utoipa::path(
+    get,
+    path = "/api/calendar/{feed}",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "iCalendar feed", content_type = "text/calendar"),
+        (status = 404, description = "Calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("feed" = String, Path, description = "Calendar token followed by `.ics`"),
+    ),
+)]
+#[debug_handler]
+pub async fn feed(Path(feed): Path<String>, State(ctx): State<AppContext>) -> Result<Response> {
+    let Some(token) = feed.strip_suffix(".ics") else {
+        return responses::notfound("Calendar not found");
+    };
+
+    let user = match users::Model::find_by_calendar_token(&ctx.db, token).await {
+        Ok(user) => user,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Calendar not found"),
+        Err(err) => return Err(err.into()),
+    };
+
+    let settings = Settings::from_opt_json(&ctx.config.settings)?;
+    let entries = attachments::Model::list_due_for_user(&ctx.db, user.id).await?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(
+            header::CONTENT_DISPOSITION,
+            "inline; filename=\"taskhub.ics\"",
+        )
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from(calendar::ics(&entries, &settings.frontend)))?)
+}
+
+/// Rotate Calendar Token
+///
+/// Create the secret calendar feed URL of the User. A new URL is returned on every
+/// call, the previous one stops working
+#[utoipa::path(
+    post,
+    path = "/api/calendar/token",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "Calendar feed URL", body = CalendarTokenResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn rotate_token(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let settings = Settings::from_opt_json(&ctx.config.settings)?;
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+
+    let user = user
+        .into_active_model()
+        .rotate_calendar_token(&ctx.db)
+        .await?;
+    let Some(token) = user.calendar_token else {
+        return responses::internal();
+    };
+
+    format::json(CalendarTokenResponse::new(&settings.backend, &token))
+}
+
+/// Revoke Calendar Token
+///
+/// Turn the calendar feed of the User off, its URL stops working
+#[utoipa::path(
+    delete,
+    path = "/api/calendar/token",
+    tag = "calendar",
+    responses(
+        (status = 200, description = "Calendar feed turned off"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn revoke_token(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+
+    user.into_active_model()
+        .clear_calendar_token(&ctx.db)
+        .await?;
+
+    format::empty()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/calendar/")
+        .add("token", openapi(post(rotate_token), routes!(rotate_token)))
+        .add(
+            "token",
+            openapi(delete(revoke_token), routes!(revoke_token)),
+        )
+        .add("{feed}", openapi(get(feed), routes!(feed)))
+}
@@ -0,0 +1,206 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::debug_handler;
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{
+        extractors::Authorized,
+        policy::{AddComment, ReadTask},
+        responses,
+    },
+    mailers::task::TaskMailer,
+    models::{
+        comments::{self, CreateParams, UpdateParams},
+        tasks, users,
+    },
+    views::comment::CommentResponse,
+};
+
+/// Emails the Users mentioned in the comment, except the ones already notified
+async fn notify_mentions(
+    ctx: &AppContext,
+    author_pid: &str,
+    comment: &comments::Model,
+    notified: &[users::Model],
+) -> Result<()> {
+    let mentioned = comment.mentioned_users(&ctx.db).await?;
+    if mentioned.is_empty() {
+        return Ok(());
+    }
+
+    let author = users::Model::find_by_pid(&ctx.db, author_pid).await?;
+    let task = tasks::Model::load(&ctx.db, comment.task_id).await?;
+
+    for user in mentioned
+        .iter()
+        .filter(|user| !notified.iter().any(|notified| notified.id == user.id))
+    {
+        if let Err(err) = TaskMailer::send_mention(ctx, user, &author, &task, comment).await {
+            tracing::error!(error = ?err, user_id = user.id, "could not send mention email");
+        }
+    }
+
+    Ok(())
+}
+
+/// List Comments
+///
+/// List all comments of the Task, oldest first. Replies reference their parent
+/// comment, removed comments are kept without their body while they have replies
+#[utoipa::path(
+    get,
+    path = "/api/tasks/comments/{id}",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Array of Comment objects", body = Vec<CommentResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn list(
+    _auth: Authorized<ReadTask>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let comments = comments::Model::list_for_task(&ctx.db, task_id).await?;
+
+    format::json(CommentResponse::from_vec(comments))
+}
+
+/// Add Comment
+///
+/// Comment on the Task or reply to one of its comments. Users with access to the
+/// Task who are mentioned as `@name`, by their name without spaces or by their
+/// email up to the `@`, are notified by email
+#[utoipa::path(
+    post,
+    path = "/api/tasks/comments/{id}",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Comment created", body = CommentResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = CreateParams
+)]
+#[debug_handler]
+pub async fn add(
+    auth: Authorized<AddComment>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    let comment = match comments::Model::add(&ctx.db, &auth.claims.pid, task_id, &params).await {
+        Ok(comment) => comment,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    notify_mentions(&ctx, &auth.claims.pid, &comment, &[]).await?;
+
+    format::json(CommentResponse::new(comment))
+}
+
+/// Edit Comment
+///
+/// Change the body of the own comment. Users newly mentioned by the change are notified
+#[utoipa::path(
+    patch,
+    path = "/api/tasks/comments/{id}",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Comment updated", body = CommentResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Comment id"),
+    ),
+    request_body = UpdateParams
+)]
+#[debug_handler]
+pub async fn update(
+    auth: auth::JWT,
+    Path(comment_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<UpdateParams>,
+) -> Result<Response> {
+    let comment = match comments::Model::load(&ctx.db, comment_id).await {
+        Ok(comment) => comment,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Comment not found"),
+        Err(err) => return Err(err.into()),
+    };
+
+    comment.authorize_edit(&ctx.db, &auth.claims.pid).await?;
+
+    let notified = comment.mentioned_users(&ctx.db).await?;
+
+    let comment = match comments::ActiveModel::edit(&ctx.db, comment, &params).await {
+        Ok(comment) => comment,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    notify_mentions(&ctx, &auth.claims.pid, &comment, &notified).await?;
+
+    format::json(CommentResponse::new(comment))
+}
+
+/// Remove Comment
+///
+/// Remove the own comment, or any comment of the Task as a moderator with FullAccess
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/comments/{id}",
+    tag = "comments",
+    responses(
+        (status = 200, description = "Comment removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Comment id"),
+    ),
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: auth::JWT,
+    Path(comment_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let comment = match comments::Model::load(&ctx.db, comment_id).await {
+        Ok(comment) => comment,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Comment not found"),
+        Err(err) => return Err(err.into()),
+    };
+
+    comment.authorize_remove(&ctx.db, &auth.claims.pid).await?;
+
+    comments::ActiveModel::remove(&ctx.db, comment).await?;
+
+    format::empty()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/tasks/comments/")
+        .add("{id}", openapi(get(list), routes!(list)))
+        .add("{id}", openapi(post(add), routes!(add)))
+        .add("{id}", openapi(patch(update), routes!(update)))
+        .add("{id}", put(update))
+        .add("{id}", openapi(delete(remove), routes!(remove)))
+}
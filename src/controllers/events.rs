@@ -0,0 +1,190 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use std::{collections::BTreeSet, convert::Infallible};
+
+use axum::{
+    debug_handler,
+    extract::Query,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+};
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+
+use crate::{
+    common::{
+        events::{self, Subscription, TaskEvent},
+        policy::Action,
+        responses,
+    },
+    models::{tasks, users},
+};
+
+/// Tasks one stream can follow at most
+const MAX_FOLLOWED_TASKS: usize = 50;
+/// Events kept for a client that reads slower than they happen
+const STREAM_BUFFER: usize = 64;
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct SubscribeParams {
+    /// Comma separated ids of the Tasks to follow
+    pub tasks: String,
+}
+
+impl SubscribeParams {
+    fn task_ids(&self) -> Option<BTreeSet<i32>> {
+        self.tasks
+            .split(',')
+            .map(|id| id.trim().parse().ok())
+            .collect()
+    }
+}
+
+/// Follow Tasks
+///
+/// Stream changes of the Tasks as Server-Sent Events, each one a JSON object with its `kind`
+/// and ids. When access to a Task is revoked an `access_revoked` event is sent, when it is
+/// removed a `task_removed` one, and the Task is not followed anymore. The stream ends once
+/// no Tasks are left
+#[utoipa::path(
+    get,
+    path = "/api/tasks/events",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Stream of Task events", content_type = "text/event-stream", body = TaskEvent),
+        (status = 400, description = "Bad Request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(SubscribeParams),
+)]
+#[debug_handler]
+pub async fn subscribe(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Query(params): Query<SubscribeParams>,
+) -> Result<Response> {
+    let Some(task_ids) = params.task_ids() else {
+        return responses::bad_request("Tasks must be comma separated ids");
+    };
+
+    if task_ids.len() > MAX_FOLLOWED_TASKS {
+        return responses::bad_request(format!(
+            "At most {MAX_FOLLOWED_TASKS} Tasks can be followed at once"
+        ));
+    }
+
+    for &task_id in &task_ids {
+        tasks::Model::authorize(&ctx.db, &auth.claims.pid, task_id, Action::ReadTask).await?;
+    }
+
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let subscription = Subscription {
+        user_id: user.id,
+        task_ids,
+    };
+
+    let receiver = events::hub().subscribe();
+    let (sender, stream) = mpsc::channel(STREAM_BUFFER);
+
+    tokio::spawn(forward(ctx.db.clone(), subscription, receiver, sender));
+
+    let stream = ReceiverStream::new(stream).map(Ok::<_, Infallible>);
+
+    Ok(Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+/// Passes events of the followed Tasks to the client. Access is checked again whenever
+/// it may have been revoked, or when events were skipped, the loop ends once the client
+/// is gone
+pub async fn forward(
+    db: DatabaseConnection,
+    mut subscription: Subscription,
+    mut receiver: broadcast::Receiver<TaskEvent>,
+    sender: mpsc::Sender<Event>,
+) {
+    while !sender.is_closed() {
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    skipped,
+                    user_id = subscription.user_id,
+                    "task events lagged"
+                );
+                // the skipped events may have revoked access
+                if !revoke(&db, &mut subscription, &sender).await {
+                    return;
+                }
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        // followers of a removed Task learn it is gone, rather than that they lost access
+        if let TaskEvent::TaskRemoved { task_id } = event {
+            if subscription.task_ids.remove(&task_id) && !send(&sender, &event).await {
+                return;
+            }
+        }
+
+        let may_revoke = event.may_revoke(subscription.user_id);
+        if may_revoke && !revoke(&db, &mut subscription, &sender).await {
+            return;
+        }
+
+        if subscription.wants(&event) && !send(&sender, &event).await {
+            return;
+        }
+    }
+}
+
+/// Stops following the Tasks the User lost access to and tells the client about them,
+/// `false` once the stream should end
+async fn revoke(
+    db: &DatabaseConnection,
+    subscription: &mut Subscription,
+    sender: &mpsc::Sender<Event>,
+) -> bool {
+    let revoked = match subscription.recheck(db).await {
+        Ok(revoked) => revoked,
+        Err(e) => {
+            tracing::error!(error = ?e, "could not recheck followed tasks");
+            return false;
+        }
+    };
+
+    for task_id in revoked {
+        if !send(sender, &TaskEvent::AccessRevoked { task_id }).await {
+            return false;
+        }
+    }
+
+    !subscription.task_ids.is_empty()
+}
+
+/// Sends the event to the client, `false` once the client is gone
+async fn send(sender: &mpsc::Sender<Event>, event: &TaskEvent) -> bool {
+    match Event::default().json_data(event) {
+        Ok(event) => sender.send(event).await.is_ok(),
+        Err(e) => {
+            tracing::error!(error = ?e, "could not serialize task event");
+            true
+        }
+    }
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/tasks/")
+        .add("/events", openapi(get(subscribe), routes!(subscribe)))
+}
@@ -0,0 +1,204 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::debug_handler;
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::responses,
+    models::groups::{self, AddMembersParams, CreateParams, RemoveMemberParams},
+    views::group::{GroupFullResponse, GroupResponse},
+};
+
+/// List Groups
+///
+/// List the Groups the User owns or is a member of
+#[utoipa::path(
+    get,
+    path = "/api/groups",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Array of Group objects", body = Vec<GroupResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let groups = groups::Model::list_for_user(&ctx.db, &auth.claims.pid).await?;
+
+    format::json(GroupResponse::from_vec(&groups))
+}
+
+/// Create Group
+///
+/// Create new Group owned by the User
+#[utoipa::path(
+    post,
+    path = "/api/groups",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Group created", body = GroupResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = CreateParams
+)]
+#[debug_handler]
+pub async fn add(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    if params.name.trim().is_empty() {
+        return responses::bad_request("Group name can not be empty");
+    }
+
+    let group = groups::Model::add(&ctx.db, &auth.claims.pid, &params).await?;
+
+    format::json(GroupResponse::new(&group))
+}
+
+/// Get Group
+///
+/// Get the Group with its members
+#[utoipa::path(
+    get,
+    path = "/api/groups",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Group object", body = GroupFullResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Group id"),
+    ),
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let group = groups::Model::load_visible(&ctx.db, &auth.claims.pid, id).await?;
+    let members = group.members(&ctx.db).await?;
+
+    format::json(GroupFullResponse::new(&group, &members))
+}
+
+/// Delete Group
+///
+/// Delete the Group, Tasks shared with it are no longer accessible to its members
+#[utoipa::path(
+    delete,
+    path = "/api/groups",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Group deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Group id"),
+    ),
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let group = groups::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    groups::ActiveModel::remove(&ctx.db, group).await?;
+
+    format::empty()
+}
+
+/// Add Members
+///
+/// Add the Users with the given emails to the Group
+#[utoipa::path(
+    post,
+    path = "/api/groups/{id}/members",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Group with its members", body = GroupFullResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Group or User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Group id"),
+    ),
+    request_body = AddMembersParams
+)]
+#[debug_handler]
+pub async fn add_members(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<AddMembersParams>,
+) -> Result<Response> {
+    let group = groups::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    groups::ActiveModel::add_members(&ctx.db, &group, &params).await?;
+
+    let members = group.members(&ctx.db).await?;
+
+    format::json(GroupFullResponse::new(&group, &members))
+}
+
+/// Remove Member
+///
+/// Remove the User from the Group
+#[utoipa::path(
+    delete,
+    path = "/api/groups/{id}/members",
+    tag = "groups",
+    responses(
+        (status = 200, description = "Member removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Group or member not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Group id"),
+    ),
+    request_body = RemoveMemberParams
+)]
+#[debug_handler]
+pub async fn remove_member(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<RemoveMemberParams>,
+) -> Result<Response> {
+    let group = groups::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    groups::ActiveModel::remove_member(&ctx.db, &group, &params).await?;
+
+    format::empty()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/groups/")
+        .add("/", openapi(get(list), routes!(list)))
+        .add("/", openapi(post(add), routes!(add)))
+        .add("{id}", openapi(get(get_one), routes!(get_one)))
+        .add("{id}", openapi(delete(remove), routes!(remove)))
+        .add(
+            "{id}/members",
+            openapi(post(add_members), routes!(add_members)),
+        )
+        .add(
+            "{id}/members",
+            openapi(delete(remove_member), routes!(remove_member)),
+        )
+}
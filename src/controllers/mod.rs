@@ -1,9 +1,17 @@
 pub mod auth;
 
 pub mod accesses;
+pub mod calendar;
+pub mod comments;
+pub mod events;
+pub mod groups;
 pub mod oauth2;
+pub mod payments;
 pub mod roles;
+pub mod solutions;
 pub mod tasks;
+pub mod templates;
 pub mod users;
+pub mod webhooks;
 
 pub mod attachments;
\ No newline at end of file
@@ -0,0 +1,143 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::{body::Bytes, debug_handler, http::HeaderMap};
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{
+        payments::{self, Checkout, PaymentEvent, SIGNATURE_HEADER},
+        responses,
+        settings::Settings,
+    },
+    models::purchases,
+    views::purchase::CheckoutResponse,
+};
+
+/// Checkout Task
+///
+/// Start a purchase of the Paid Task, the User gets `View` access once it is paid
+#[utoipa::path(
+    post,
+    path = "/api/payments/checkout/{id}",
+    tag = "payments",
+    responses(
+        (status = 200, description = "Checkout is started", body = CheckoutResponse),
+        (status = 400, description = "Task can not be purchased"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "User already has access to the Task"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn checkout(
+    auth: auth::JWT,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let settings = Settings::from_opt_json(&ctx.config.settings)?;
+    let Some(payment_settings) = settings.payments else {
+        return responses::bad_request("Payments are not configured");
+    };
+
+    let (purchase, task) = match purchases::Model::start(
+        &ctx.db,
+        &auth.claims.pid,
+        task_id,
+        &payment_settings.currency,
+    )
+    .await
+    {
+        Ok(started) => started,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Task not found."),
+        Err(ModelError::EntityAlreadyExists) => {
+            return responses::conflict("User already has access to the Task")
+        }
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    let provider = payments::build(&payment_settings, &settings.frontend);
+    let session = provider
+        .create_checkout(&Checkout {
+            purchase_id: purchase.id,
+            description: &task.name,
+            amount_cents: purchase.amount_cents,
+            currency: &purchase.currency,
+        })
+        .await?;
+
+    let purchase = purchase
+        .into_active_model()
+        .set_reference(&ctx.db, session.reference)
+        .await?;
+
+    format::json(CheckoutResponse::new(purchase, session.url))
+}
+
+/// Payment Webhook
+///
+/// Receive the outcome of a checkout from the payment provider
+#[utoipa::path(
+    post,
+    path = "/api/payments/webhook",
+    tag = "payments",
+    responses(
+        (status = 200, description = "Event is processed"),
+        (status = 400, description = "Malformed event"),
+        (status = 401, description = "Invalid signature"),
+        (status = 404, description = "Purchase not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("x-payment-signature" = String, Header, description = "Hex encoded HMAC-SHA256 of the body"),
+    ),
+    request_body = PaymentEvent
+)]
+#[debug_handler]
+pub async fn webhook(
+    State(ctx): State<AppContext>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response> {
+    let settings = Settings::from_opt_json(&ctx.config.settings)?;
+    let Some(payment_settings) = settings.payments else {
+        return responses::bad_request("Payments are not configured");
+    };
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let provider = payments::build(&payment_settings, &settings.frontend);
+    let event = provider.verify_webhook(&body, signature)?;
+
+    let reference = match &event {
+        PaymentEvent::Succeeded { reference } | PaymentEvent::Failed { reference } => reference,
+    };
+
+    let purchase = match purchases::Model::find_by_reference(&ctx.db, reference).await {
+        Ok(purchase) => purchase,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Purchase not found."),
+        Err(err) => return Err(err.into()),
+    };
+
+    match event {
+        PaymentEvent::Succeeded { .. } => purchase.into_active_model().complete(&ctx.db).await?,
+        PaymentEvent::Failed { .. } => purchase.into_active_model().fail(&ctx.db).await?,
+    };
+
+    format::empty()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/payments/")
+        .add("checkout/{id}", openapi(post(checkout), routes!(checkout)))
+        .add("webhook", openapi(post(webhook), routes!(webhook)))
+}
@@ -0,0 +1,266 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::{
+    body::Body,
+    debug_handler,
+    http::{header, StatusCode},
+};
+
+use axum_typed_multipart::TypedMultipart;
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{
+        extractors::Authorized,
+        policy::{Action, ReviewSolutions, SubmitSolution},
+        responses,
+    },
+    models::{
+        solutions::{self, *},
+        tasks, users,
+    },
+    views::solution::*,
+};
+
+/// List Solutions
+///
+/// List Solutions submitted to the Task
+#[utoipa::path(
+    get,
+    path = "/api/tasks/solutions/{id}",
+    tag = "solutions",
+    responses(
+        (status = 200, description = "Array of Solution objects", body = Vec<SolutionResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn list(
+    auth: Authorized<ReviewSolutions>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let solutions = solutions::Model::list_for_task(&ctx.db, task_id).await?;
+
+    format::json(SolutionResponse::from_vec(solutions))
+}
+
+/// Submit Solution
+///
+/// Submit a Solution to the Task, optionally with files (multipart)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/solutions/{id}",
+    tag = "solutions",
+    request_body(
+        content_type = "multipart/form-data",
+        content = SolutionAddForm
+    ),
+    responses(
+        (status = 200, description = "Solution is submitted", body = SolutionResponse),
+        (status = 400, description = "Bad Request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn add(
+    auth: Authorized<SubmitSolution>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    TypedMultipart(form): TypedMultipart<solutions::SolutionAddForm>,
+) -> Result<Response> {
+    let mut uploads = Vec::with_capacity(form.files.len());
+    for field in form.files {
+        let file_name = field
+            .metadata
+            .file_name
+            .ok_or_else(|| Error::BadRequest("File field missing filename".into()))?
+            .to_string();
+
+        uploads.push((file_name, field.contents));
+    }
+
+    let params = SolutionAddParams {
+        body: form.body,
+        file_names: uploads.iter().map(|(name, _)| name.clone()).collect(),
+    };
+
+    let (solution, files) =
+        match solutions::Model::add_solution(&ctx.db, &auth.claims.pid, task_id, params).await {
+            Ok(added) => added,
+            Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+            Err(err) => return Err(err.into()),
+        };
+
+    for (file, (_, content)) in files.iter().zip(&uploads) {
+        if let Err(e) = ctx
+            .storage
+            .as_ref()
+            .upload(file.path().as_path(), content)
+            .await
+        {
+            tracing::error!(error = ?e, solution_id = solution.id, "could not upload solution file");
+
+            for file in &files {
+                ctx.storage
+                    .as_ref()
+                    .delete(file.path().as_path())
+                    .await
+                    .ok();
+            }
+            solution.delete(&ctx.db).await?;
+
+            return responses::internal();
+        }
+    }
+
+    format::json(SolutionResponse::new(solution, files))
+}
+
+/// Download Solution File
+///
+/// Download a file of the Solution, only its author and the reviewers of the Task can
+#[utoipa::path(
+    get,
+    path = "/api/tasks/solutions/{id}/files/{name}",
+    tag = "solutions",
+    responses(
+        (status = 200, description = "File contents", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Solution or file not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Solution id"),
+        ("name" = String, Path, description = "File name"),
+    ),
+)]
+#[debug_handler]
+pub async fn download(
+    auth: auth::JWT,
+    Path((solution_id, file_name)): Path<(i32, String)>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let solution = match solutions::Model::load(&ctx.db, solution_id).await {
+        Ok(solution) => solution,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Solution not found."),
+        _ => return responses::internal(),
+    };
+
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let level = tasks::Model::authorize(
+        &ctx.db,
+        &auth.claims.pid,
+        solution.task_id,
+        Action::ReadTask,
+    )
+    .await?;
+    if solution.author_id != user.id && !Action::ReviewSolutions.allows(level) {
+        return responses::unauthorized("unauthorized");
+    }
+
+    let file = match solution.find_file(&ctx.db, &file_name).await {
+        Ok(file) => file,
+        Err(ModelError::EntityNotFound) => return responses::notfound("File not found."),
+        Err(err) => return Err(err.into()),
+    };
+
+    let bytes: Vec<u8> = match ctx.storage.as_ref().download(file.path().as_path()).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::warn!(
+                solution_id = solution.id,
+                error = err.to_string(),
+                "could not read solution file from storage"
+            );
+            return responses::notfound("File content not found.");
+        }
+    };
+
+    let content_type = mime_guess::from_path(&file.file_name).first_or_octet_stream();
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        file.file_name.replace(['"', '\\'], "_")
+    );
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type.as_ref())
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .header(header::CONTENT_LENGTH, bytes.len())
+        .body(Body::from(bytes))?)
+}
+
+/// Review Solution
+///
+/// Accept or reject the Solution
+#[utoipa::path(
+    method(put, patch),
+    path = "/api/tasks/solutions/{id}",
+    tag = "solutions",
+    responses(
+        (status = 200, description = "Solution is reviewed", body = SolutionResponse),
+        (status = 400, description = "Bad Request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Solution not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Solution id"),
+    ),
+    request_body = ReviewParams
+)]
+#[debug_handler]
+pub async fn review(
+    auth: auth::JWT,
+    Path(solution_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ReviewParams>,
+) -> Result<Response> {
+    let solution = match solutions::Model::load(&ctx.db, solution_id).await {
+        Ok(solution) => solution,
+        Err(ModelError::EntityNotFound) => return responses::notfound("Solution not found."),
+        _ => return responses::internal(),
+    };
+
+    tasks::Model::authorize(
+        &ctx.db,
+        &auth.claims.pid,
+        solution.task_id,
+        Action::ReviewSolutions,
+    )
+    .await?;
+
+    let files = solution.files(&ctx.db).await?;
+
+    let solution = match solution.into_active_model().review(&ctx.db, params).await {
+        Ok(solution) => solution,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(SolutionResponse::new(solution, files))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/tasks/solutions/")
+        .add("{id}", openapi(get(list), routes!(list)))
+        .add("{id}", openapi(post(add), routes!(add)))
+        .add(
+            "{id}/files/{name}",
+            openapi(get(download), routes!(download)),
+        )
+        .add("{id}", openapi(patch(review), routes!(review)))
+        .add("{id}", put(review))
+}
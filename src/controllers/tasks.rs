@@ -1,18 +1,43 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
-use axum::debug_handler;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use axum::{
+    body::{Body, Bytes},
+    debug_handler,
+    extract::Query,
+    http::header,
+};
+use axum_typed_multipart::TypedMultipart;
 use loco_openapi::prelude::*;
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    common,
+    common::{
+        self,
+        archive::Archive,
+        extractors::Authorized,
+        pagination::ListParams,
+        policy::{Action, AddSubtask, DeleteTask, EditTask, MoveTask, ReadTask, ViewHistory},
+    },
     models::{
-        accesses, attachments,
-        tasks::{self, users, CreateParams, SearchParams, UpdateParams},
+        accesses, attachments, audit_events,
+        tasks::{
+            self, users, CreateParams, ExportParams, ImportForm, MoveParams, SearchParams,
+            TaskFilter, TransferParams, TransitionParams, TreeParams, UpdateParams,
+        },
+    },
+    views::{
+        self,
+        audit_event::AuditEventResponse,
+        page::PageResponse,
+        task::{
+            ForkSourceResponse, TaskImportResponse, TaskResponse, TaskSearchResponse,
+            TaskTreeResponse,
+        },
     },
-    views,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -28,47 +53,89 @@ pub struct FullParams {
 
 /// Get Full Task
 ///
-/// Get the Task by id
+/// Get the Task by id. Attachments of Paid Tasks are only shown to Users who have access,
+/// Private Tasks are not found for Users without one
 #[utoipa::path(
     post,
     path = "/api/tasks/full",
     tag = "tasks",
     responses(
         (status = 200, description = "Task full object", body = views::task::TaskFullResponse),
+        (status = 404, description = "Task not found"),
         (status = 500, description = "Internal server error")
     ),
     request_body = FullParams
 )]
 #[debug_handler]
 pub async fn get_full(
+    auth: Option<common::extractors::OptJWT>,
     State(ctx): State<AppContext>,
     Json(params): Json<FullParams>,
 ) -> Result<Response> {
-    let task = tasks::Model::load(&ctx.db, params.task_id).await?;
+    let viewer_pid = auth.as_ref().map(|opt_jwt| opt_jwt.jwt.claims.pid.as_str());
+    let task = match tasks::Model::load_visible(&ctx.db, viewer_pid, params.task_id).await {
+        Ok(task) => task,
+        Err(ModelError::EntityNotFound) => return common::responses::notfound("Task not found"),
+        Err(err) => return Err(err.into()),
+    };
 
     let owner = accesses::Model::find_task_owner(&ctx.db, task.id).await?;
 
     let (user, role) = users::Model::find_by_id_with_role(&ctx.db, owner.id).await?;
 
-    let attachments = attachments::Model::list_attachments(&ctx.db, task.id).await?;
+    let forked_from = match task.forked_from_id {
+        Some(source_id) => match tasks::Model::load_visible(&ctx.db, viewer_pid, source_id).await {
+            Ok(source) => {
+                let author = accesses::Model::find_task_owner(&ctx.db, source.id)
+                    .await
+                    .ok();
+
+                Some(ForkSourceResponse::new(source, author.as_ref()))
+            }
+            Err(ModelError::EntityNotFound) => None,
+            Err(err) => return Err(err.into()),
+        },
+        None => None,
+    };
+
+    let locked = match (task.visibility, auth) {
+        (tasks::TaskVisibilityEnum::Paid, Some(opt_jwt)) => {
+            let viewer = users::Model::find_by_pid(&ctx.db, &opt_jwt.jwt.claims.pid).await?;
+
+            tasks::Model::effective_access(&ctx.db, viewer.id, task.id)
+                .await?
+                .is_none()
+        }
+        (tasks::TaskVisibilityEnum::Paid, None) => true,
+        _ => false,
+    };
+
+    let attachments = if locked {
+        Vec::new()
+    } else {
+        attachments::Model::list_attachments(&ctx.db, task.id).await?
+    };
 
     format::json(views::task::TaskFullResponse::new(
         task,
         user,
         role,
         attachments,
+        locked,
+        forked_from,
     ))
 }
 
 /// Get Task
 ///
-/// Get the Task by id
+/// Get the Task by id. Private Tasks are not found for Users without access
 #[utoipa::path(
     get,
     path = "/api/tasks",
     tag = "tasks",
     responses(
         (status = 200, description = "Task object", body = views::task::TaskResponse),
+        (status = 404, description = "Task not found"),
         (status = 500, description = "Internal server error")
     ),
     params(
@@ -76,53 +143,77 @@ pub async fn get_full(
     )
 )]
 #[debug_handler]
-pub async fn get_one(Path(id): Path<i32>, State(ctx): State<AppContext>) -> Result<Response> {
-    let task = tasks::Model::load(&ctx.db, id).await?;
+pub async fn get_one(
+    auth: Option<common::extractors::OptJWT>,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let viewer_pid = auth.as_ref().map(|opt_jwt| opt_jwt.jwt.claims.pid.as_str());
+    let task = match tasks::Model::load_visible(&ctx.db, viewer_pid, id).await {
+        Ok(task) => task,
+        Err(ModelError::EntityNotFound) => return common::responses::notfound("Task not found"),
+        Err(err) => return Err(err.into()),
+    };
+
     format::json(views::task::TaskResponse::new(task))
 }
 
 /// List Tasks
 ///
-/// List all Tasks
+/// List a page of all public Tasks
 #[utoipa::path(
     get,
     path = "/api/tasks/list",
     tag = "tasks",
     responses(
-        (status = 200, description = "Array of Task objects", body = Vec<views::task::TaskResponse>),
+        (status = 200, description = "Page of Task objects", body = PageResponse<TaskResponse>),
         (status = 500, description = "Internal server error")
     ),
+    params(ListParams, TaskFilter),
 )]
 #[debug_handler]
-pub async fn list(State(ctx): State<AppContext>) -> Result<Response> {
-    format::json(tasks::Model::list_public(&ctx.db).await?)
+pub async fn list(
+    State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+    Query(filter): Query<TaskFilter>,
+) -> Result<Response> {
+    let page = tasks::Model::list_public(&ctx.db, &list, &filter).await?;
+
+    format::json(PageResponse::new(page, &list, TaskResponse::new))
 }
 
 /// Search Tasks
 ///
-/// Search Tasks
+/// Full-text search over Task names and their Description, Text, Tip and Hint attachments.
+/// Results are ordered by relevance
 #[utoipa::path(
     post,
     path = "/api/tasks/search",
     tag = "tasks",
     responses(
-        (status = 200, description = "Array of Task objects", body = Vec<views::task::TaskResponse>),
+        (status = 200, description = "Page of found Tasks", body = PageResponse<TaskSearchResponse>),
         (status = 500, description = "Internal server error")
     ),
+    params(ListParams, TaskFilter),
     request_body = SearchParams
 )]
 #[debug_handler]
 pub async fn search(
     auth: Option<common::extractors::OptJWT>,
     State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+    Query(filter): Query<TaskFilter>,
     Json(params): Json<SearchParams>,
 ) -> Result<Response> {
-    match auth {
-        Some(opt_jwt) => format::json(
-            tasks::Model::search_for_user(&ctx.db, &opt_jwt.jwt.claims.pid, &params).await?,
-        ),
-        None => format::json(tasks::Model::search_for_anon(&ctx.db, &params).await?),
-    }
+    let page = match auth {
+        Some(opt_jwt) => {
+            tasks::Model::search_for_user(&ctx.db, &opt_jwt.jwt.claims.pid, &params, &list, &filter)
+                .await?
+        }
+        None => tasks::Model::search_for_anon(&ctx.db, &params, &list, &filter).await?,
+    };
+
+    format::json(PageResponse::new(page, &list, TaskSearchResponse::new))
 }
 
 /// Create Task
@@ -145,6 +236,10 @@ pub async fn add(
     State(ctx): State<AppContext>,
     Json(params): Json<CreateParams>,
 ) -> Result<Response> {
+    if params.price_cents.is_some_and(|price| price < 0) {
+        return common::responses::bad_request("Price can not be negative");
+    }
+
     let task = tasks::Model::add(&ctx.db, &auth.claims.pid, params).await?;
 
     format::json(views::task::TaskResponse::new(task))
@@ -169,24 +264,16 @@ pub async fn add(
 )]
 #[debug_handler]
 pub async fn update(
-    auth: auth::JWT,
+    auth: Authorized<EditTask>,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
     Json(params): Json<UpdateParams>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![
-            tasks::AccessLevelEnum::FullAccess,
-            tasks::AccessLevelEnum::Edit,
-            tasks::AccessLevelEnum::AddUser,
-        ],
-    )
-    .await?;
+    if params.price_cents.is_some_and(|price| price < 0) {
+        return common::responses::bad_request("Price can not be negative");
+    }
 
-    let task = tasks::ActiveModel::update(&ctx.db, params, task_id).await?;
+    let task = tasks::ActiveModel::update(&ctx.db, &auth.claims.pid, params, task_id).await?;
 
     format::json(task)
 }
@@ -209,21 +296,494 @@ pub async fn update(
 )]
 #[debug_handler]
 pub async fn remove(
+    auth: Authorized<DeleteTask>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    tasks::ActiveModel::remove(&ctx.db, &auth.claims.pid, task_id).await?;
+
+    format::empty()
+}
+
+/// Add Subtask
+///
+/// Create a Task under an existing one, it inherits accesses of the parent
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/children",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Subtask created", body = TaskResponse),
+        (status = 400, description = "Bad Request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Parent Task id"),
+    ),
+    request_body = CreateParams
+)]
+#[debug_handler]
+pub async fn add_child(
+    auth: Authorized<AddSubtask>,
+    Path(parent_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    let task = match tasks::Model::add_child(&ctx.db, &auth.claims.pid, parent_id, params).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Move Task
+///
+/// Move the Task with its subtasks under another parent, or make it a root Task
+#[utoipa::path(
+    method(put, patch),
+    path = "/api/tasks/{id}/move",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Task moved", body = TaskResponse),
+        (status = 400, description = "Bad Request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = MoveParams
+)]
+#[debug_handler]
+pub async fn move_task(
+    auth: Authorized<MoveTask>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<MoveParams>,
+) -> Result<Response> {
+    if let Some(parent_id) = params.parent_id {
+        tasks::Model::authorize(&ctx.db, &auth.claims.pid, parent_id, Action::AddSubtask).await?;
+    }
+
+    let task = match tasks::ActiveModel::move_to(&ctx.db, &auth.claims.pid, task_id, params).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Task Tree
+///
+/// Get the Task with its subtasks
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/tree",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Task tree", body = TaskTreeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+        TreeParams,
+    ),
+)]
+#[debug_handler]
+pub async fn tree(
+    auth: Authorized<ReadTask>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<TreeParams>,
+) -> Result<Response> {
+    let tree = tasks::Model::tree(&ctx.db, task_id, params.depth).await?;
+
+    format::json(TaskTreeResponse::new(tree))
+}
+
+/// Change Task Status
+///
+/// Move the Task to another status. Only transitions of the Task lifecycle are allowed,
+/// each one to its own access levels
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/status",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Task status changed", body = TaskResponse),
+        (status = 400, description = "Transition is not allowed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task not found"),
+        (status = 409, description = "Task status was changed concurrently"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = TransitionParams
+)]
+#[debug_handler]
+pub async fn transition(
     auth: auth::JWT,
     Path(task_id): Path<i32>,
     State(ctx): State<AppContext>,
+    Json(params): Json<TransitionParams>,
 ) -> Result<Response> {
-    tasks::Model::has_access(
-        &ctx.db,
-        &auth.claims.pid,
-        task_id,
-        vec![tasks::AccessLevelEnum::FullAccess],
-    )
-    .await?;
+    // the status is only revealed to callers that could read it anyway
+    let task = match tasks::Model::load_visible(&ctx.db, Some(&auth.claims.pid), task_id).await {
+        Ok(task) => task,
+        Err(ModelError::EntityNotFound) => return common::responses::notfound("Task not found"),
+        Err(err) => return Err(err.into()),
+    };
+    let level =
+        tasks::Model::authorize(&ctx.db, &auth.claims.pid, task.id, Action::ReadTask).await?;
 
-    tasks::ActiveModel::remove(&ctx.db, task_id).await?;
+    let Some(action) = tasks::allowed_transition(task.status, params.status) else {
+        return common::responses::bad_request(format!(
+            "Task can not go from {:?} to {:?}",
+            task.status, params.status
+        ));
+    };
 
-    format::empty()
+    if !action.allows(level) {
+        return common::responses::unauthorized("unauthorized");
+    }
+
+    let task = match tasks::ActiveModel::transition(&ctx.db, &auth.claims.pid, task, params).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Transfer Task
+///
+/// Hand the Task over to another User. Only the owner can transfer it, with
+/// `require_confirmation` the recipient has to accept it first
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/owner",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Task transferred or waiting for confirmation", body = TaskResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Recipient not found"),
+        (status = 409, description = "Recipient already owns the Task"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+    request_body = TransferParams
+)]
+#[debug_handler]
+pub async fn transfer(
+    auth: auth::JWT,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<TransferParams>,
+) -> Result<Response> {
+    tasks::Model::check_owner(&ctx.db, &auth.claims.pid, task_id).await?;
+
+    let task = tasks::Model::load(&ctx.db, task_id).await?;
+
+    let task = match tasks::ActiveModel::transfer(&ctx.db, &auth.claims.pid, task, &params).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Fork Task
+///
+/// Copy the Task with its Attachments into a new Private Task owned by the User, which
+/// credits the source Task. Public Tasks can be forked by anyone, other ones only with
+/// an Access to them. Subtasks are not copied
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/fork",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Forked Task", body = TaskResponse),
+        (status = 400, description = "Some Attachments have invalid data"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn fork(
+    auth: auth::JWT,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let fork = match tasks::Model::fork(&ctx.db, &auth.claims.pid, task_id).await {
+        Ok(fork) => fork,
+        Err(Error::Model(ModelError::EntityNotFound)) => {
+            return common::responses::notfound("Task not found")
+        }
+        Err(err) => return Err(err),
+    };
+
+    for (original, copy) in &fork.files {
+        let from = PathBuf::from(original.id.to_string()).join(&original.data);
+        let to = PathBuf::from(copy.id.to_string()).join(&copy.data);
+
+        if let Err(e) = ctx
+            .storage
+            .as_ref()
+            .copy(from.as_path(), to.as_path())
+            .await
+        {
+            tracing::error!(error = ?e, attachment_id = original.id, "could not copy forked file");
+
+            // leave no fork behind whose files are missing
+            for (_, copy) in &fork.files {
+                let path = PathBuf::from(copy.id.to_string()).join(&copy.data);
+                ctx.storage.as_ref().delete(path.as_path()).await.ok();
+            }
+            tasks::ActiveModel::remove(&ctx.db, &auth.claims.pid, fork.task.id).await?;
+
+            return common::responses::internal();
+        }
+    }
+
+    format::json(TaskResponse::new(fork.task))
+}
+
+/// Export Task
+///
+/// Download the Task with its Attachments as a ZIP archive: a `manifest.json` next to
+/// the content of the File attachments. The archive can be imported again on this or
+/// another instance. Listing who has Access to the Task with `include_accesses` needs
+/// the right to manage Accesses. Subtasks are not exported
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/export",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "ZIP archive of the Task", content_type = "application/zip"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task or Attachment content not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+        ExportParams,
+    ),
+)]
+#[debug_handler]
+pub async fn export(
+    auth: Authorized<ReadTask>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response> {
+    if params.include_accesses {
+        tasks::Model::authorize(&ctx.db, &auth.claims.pid, task_id, Action::ManageAccess).await?;
+    }
+
+    let export = tasks::Model::export(&ctx.db, task_id, params.include_accesses).await?;
+
+    let mut files = BTreeMap::new();
+    for (entry, attachment) in export.files {
+        let path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+        let content: Vec<u8> = match ctx.storage.as_ref().download(path.as_path()).await {
+            Ok(content) => content,
+            Err(err) => {
+                tracing::warn!(
+                    attachment_id = attachment.id,
+                    error = err.to_string(),
+                    "could not read attachment content from storage"
+                );
+                return common::responses::notfound("Attachment content not found.");
+            }
+        };
+        files.insert(entry, content);
+    }
+
+    let bytes = Archive {
+        manifest: export.manifest,
+        files,
+    }
+    .write()?;
+
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"task-{task_id}.zip\""),
+        )
+        .header(header::CONTENT_LENGTH, bytes.len())
+        .body(Body::from(bytes))?)
+}
+
+/// Import Task
+///
+/// Recreate a Task from an archive written by the export as a new root Task owned by
+/// the User, with its Attachments and files. Archived Accesses are not granted, their
+/// emails are listed in the response so that the Task can be shared with them
+#[utoipa::path(
+    post,
+    path = "/api/tasks/import",
+    tag = "tasks",
+    request_body(
+        content_type = "multipart/form-data",
+        content = ImportForm
+    ),
+    responses(
+        (status = 200, description = "Imported Task", body = TaskImportResponse),
+        (status = 400, description = "Invalid or too large archive"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn import(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    TypedMultipart(form): TypedMultipart<ImportForm>,
+) -> Result<Response> {
+    let archive = match Archive::read(&form.archive.contents) {
+        Ok(archive) => archive,
+        Err(ModelError::Message(msg)) => return common::responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    let import = match tasks::Model::import(&ctx.db, &auth.claims.pid, &archive.manifest).await {
+        Ok(import) => import,
+        Err(ModelError::Message(msg)) => return common::responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    for (entry, attachment) in &import.files {
+        let path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+        let content = Bytes::from(archive.files[entry].clone());
+
+        if let Err(e) = ctx.storage.as_ref().upload(path.as_path(), &content).await {
+            tracing::error!(error = ?e, attachment_id = attachment.id, "could not store imported file");
+
+            // leave no import behind whose files are missing
+            for (_, attachment) in &import.files {
+                let path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+                ctx.storage.as_ref().delete(path.as_path()).await.ok();
+            }
+            tasks::ActiveModel::remove(&ctx.db, &auth.claims.pid, import.task.id).await?;
+
+            return common::responses::internal();
+        }
+    }
+
+    format::json(TaskImportResponse::new(import))
+}
+
+/// Accept Task Transfer
+///
+/// Become the owner of the Task transferred to the User
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/owner/accept",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Task transferred", body = TaskResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Task is not being transferred to the User"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn accept_transfer(
+    auth: auth::JWT,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let task = match tasks::ActiveModel::accept_transfer(&ctx.db, &auth.claims.pid, task_id).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Cancel Task Transfer
+///
+/// Withdraw the transfer of the Task that waits for confirmation
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}/owner",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Transfer cancelled", body = TaskResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Task is not being transferred"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+    ),
+)]
+#[debug_handler]
+pub async fn cancel_transfer(
+    auth: auth::JWT,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    tasks::Model::check_owner(&ctx.db, &auth.claims.pid, task_id).await?;
+
+    let task = tasks::Model::load(&ctx.db, task_id).await?;
+
+    let task = match tasks::ActiveModel::cancel_transfer(&ctx.db, &auth.claims.pid, task).await {
+        Ok(task) => task,
+        Err(ModelError::Message(msg)) => return common::responses::conflict(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(TaskResponse::new(task))
+}
+
+/// Task History
+///
+/// List changes made to the Task, its Accesses and Attachments
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/history",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Page of audit events", body = PageResponse<AuditEventResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Task id"),
+        ListParams,
+    ),
+)]
+#[debug_handler]
+pub async fn history(
+    auth: Authorized<ViewHistory>,
+    Path(task_id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+) -> Result<Response> {
+    let page = audit_events::Model::list_for_task(&ctx.db, task_id, &list).await?;
+
+    format::json(PageResponse::new(page, &list, AuditEventResponse::new))
 }
 
 pub fn routes() -> Routes {
@@ -237,4 +797,28 @@ pub fn routes() -> Routes {
         .add("{id}", openapi(delete(remove), routes!(remove)))
         .add("{id}", openapi(put(update), routes!(update)))
         .add("{id}", patch(update))
+        .add(
+            "{id}/children",
+            openapi(post(add_child), routes!(add_child)),
+        )
+        .add("{id}/move", openapi(put(move_task), routes!(move_task)))
+        .add("{id}/move", patch(move_task))
+        .add("{id}/tree", openapi(get(tree), routes!(tree)))
+        .add(
+            "{id}/status",
+            openapi(post(transition), routes!(transition)),
+        )
+        .add("{id}/history", openapi(get(history), routes!(history)))
+        .add("{id}/owner", openapi(post(transfer), routes!(transfer)))
+        .add(
+            "{id}/owner",
+            openapi(delete(cancel_transfer), routes!(cancel_transfer)),
+        )
+        .add("{id}/fork", openapi(post(fork), routes!(fork)))
+        .add("{id}/export", openapi(get(export), routes!(export)))
+        .add("/import", openapi(post(import), routes!(import)))
+        .add(
+            "{id}/owner/accept",
+            openapi(post(accept_transfer), routes!(accept_transfer)),
+        )
 }
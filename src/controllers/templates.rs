@@ -0,0 +1,241 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use std::path::PathBuf;
+
+use axum::debug_handler;
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{policy::Action, responses},
+    models::{
+        tasks, template_attachments,
+        templates::{self, CreateParams, InstantiateParams},
+    },
+    views::{task::TaskResponse, template::TemplateResponse},
+};
+
+/// List Templates
+///
+/// List the templates the User saved
+#[utoipa::path(
+    get,
+    path = "/api/templates",
+    tag = "templates",
+    responses(
+        (status = 200, description = "Array of Template objects", body = Vec<TemplateResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let templates = templates::Model::list_for_user(&ctx.db, &auth.claims.pid).await?;
+
+    format::json(TemplateResponse::from_vec(templates))
+}
+
+/// Create Template
+///
+/// Save the Task with its Attachments as a reusable template. Description and Text
+/// attachments, and the name, may use `{{variables}}` that are filled in when the
+/// template is instantiated. DueDates are kept relative to the creation of the Task
+#[utoipa::path(
+    post,
+    path = "/api/templates",
+    tag = "templates",
+    responses(
+        (status = 200, description = "Template created", body = TemplateResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = CreateParams
+)]
+#[debug_handler]
+pub async fn add(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    tasks::Model::authorize(&ctx.db, &auth.claims.pid, params.task_id, Action::ReadTask).await?;
+
+    let saved = match templates::Model::create_from_task(&ctx.db, &auth.claims.pid, &params).await {
+        Ok(saved) => saved,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    for (attachment, copy) in &saved.files {
+        let from = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+        let Some(to) = copy.file_path() else {
+            continue;
+        };
+
+        if let Err(e) = ctx
+            .storage
+            .as_ref()
+            .copy(from.as_path(), to.as_path())
+            .await
+        {
+            tracing::error!(error = ?e, attachment_id = attachment.id, "could not copy template file");
+
+            remove_files(&ctx, &saved.attachments).await;
+            saved.template.delete(&ctx.db).await?;
+
+            return responses::internal();
+        }
+    }
+
+    format::json(TemplateResponse::new(saved.template, saved.attachments))
+}
+
+/// Get Template
+///
+/// Get the template with its attachments and variables
+#[utoipa::path(
+    get,
+    path = "/api/templates/{id}",
+    tag = "templates",
+    responses(
+        (status = 200, description = "Template object", body = TemplateResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Template id"),
+    ),
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let template = templates::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+    let attachments = template.attachments(&ctx.db).await?;
+
+    format::json(TemplateResponse::new(template, attachments))
+}
+
+/// Delete Template
+///
+/// Delete the template, Tasks created from it are kept
+#[utoipa::path(
+    delete,
+    path = "/api/templates/{id}",
+    tag = "templates",
+    responses(
+        (status = 200, description = "Template deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Template id"),
+    ),
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let template = templates::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+    let attachments = template.attachments(&ctx.db).await?;
+
+    template.delete(&ctx.db).await?;
+    remove_files(&ctx, &attachments).await;
+
+    format::empty()
+}
+
+/// Instantiate Template
+///
+/// Create a new Task owned by the User from the template, with the given values for
+/// its variables. DueDates are set relative to now
+#[utoipa::path(
+    post,
+    path = "/api/templates/{id}/instantiate",
+    tag = "templates",
+    responses(
+        (status = 200, description = "Created Task", body = TaskResponse),
+        (status = 400, description = "Values of some variables are missing"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Template id"),
+    ),
+    request_body = InstantiateParams
+)]
+#[debug_handler]
+pub async fn instantiate(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Json(params): Json<InstantiateParams>,
+) -> Result<Response> {
+    let template = templates::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    let instance = match template
+        .instantiate(&ctx.db, &auth.claims.pid, &params)
+        .await
+    {
+        Ok(instance) => instance,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    for (template_attachment, attachment) in &instance.files {
+        let Some(from) = template_attachment.file_path() else {
+            continue;
+        };
+        let to = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+
+        if let Err(e) = ctx
+            .storage
+            .as_ref()
+            .copy(from.as_path(), to.as_path())
+            .await
+        {
+            tracing::error!(error = ?e, template_id = template.id, "could not copy template file");
+
+            for (_, attachment) in &instance.files {
+                let path = PathBuf::from(attachment.id.to_string()).join(&attachment.data);
+                ctx.storage.as_ref().delete(path.as_path()).await.ok();
+            }
+            tasks::ActiveModel::remove(&ctx.db, &auth.claims.pid, instance.task.id).await?;
+
+            return responses::internal();
+        }
+    }
+
+    format::json(TaskResponse::new(instance.task))
+}
+
+/// Removes the stored content of File attachments of a template
+async fn remove_files(ctx: &AppContext, attachments: &[template_attachments::Model]) {
+    for path in attachments
+        .iter()
+        .filter_map(|attachment| attachment.file_path())
+    {
+        ctx.storage.as_ref().delete(path.as_path()).await.ok();
+    }
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/templates/")
+        .add("/", openapi(get(list), routes!(list)))
+        .add("/", openapi(post(add), routes!(add)))
+        .add("{id}", openapi(get(get_one), routes!(get_one)))
+        .add("{id}", openapi(delete(remove), routes!(remove)))
+        .add(
+            "{id}/instantiate",
+            openapi(post(instantiate), routes!(instantiate)),
+        )
+}
@@ -1,13 +1,17 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
-use axum::debug_handler;
+use axum::{debug_handler, extract::Query};
 use loco_openapi::prelude::*;
 use loco_rs::prelude::*;
 
 use crate::{
-    models::{tasks, users},
-    views::{self, user::GetResponse},
+    common::pagination::ListParams,
+    models::{
+        tasks::{self, TaskFilter},
+        users,
+    },
+    views::{page::PageResponse, task::TaskResponse, user::GetResponse},
 };
 
 /// Get User
@@ -54,44 +58,59 @@ pub async fn get_me(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Re
 
 /// User's Tasks
 ///
-/// Get the list of user's tasks by pid
+/// Get a page of user's tasks by pid
 #[utoipa::path(
     get,
     path = "/api/user/tasks",
     tag = "users",
     responses(
-        (status = 200, description = "Array of Task objects", body = Vec<views::task::TaskResponse>),
+        (status = 200, description = "Page of Task objects", body = PageResponse<TaskResponse>),
         (status = 500, description = "Internal server error")
     ),
     params(
         ("pid" = String, Path, description = "User's pid"),
+        ListParams,
+        TaskFilter,
     ),
 )]
 #[debug_handler]
-pub async fn tasks(Path(pid): Path<String>, State(ctx): State<AppContext>) -> Result<Response> {
-    let tasks = tasks::Model::list_for_anon(&ctx.db, &pid).await?;
+pub async fn tasks(
+    Path(pid): Path<String>,
+    State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+    Query(filter): Query<TaskFilter>,
+) -> Result<Response> {
+    let page = tasks::Model::list_for_anon(&ctx.db, &pid, &list, &filter).await?;
 
-    format::json(views::task::TaskResponse::from_vec(tasks))
+    format::json(PageResponse::new(page, &list, TaskResponse::new))
 }
 
 /// My Tasks
 ///
-/// Get the list of current user's tasks
+/// Get a page of current user's tasks
 #[utoipa::path(
     get,
     path = "/api/user/tasks/me",
     tag = "users",
     responses(
-        (status = 200, description = "Array of Task objects", body = Vec<views::task::TaskResponse>),
+        (status = 200, description = "Page of Task objects", body = PageResponse<TaskResponse>),
         (status = 401, description = "Unathorised"),
         (status = 500, description = "Internal server error")
     ),
+    params(ListParams, TaskFilter),
 )]
 #[debug_handler]
-pub async fn tasks_me(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
-    let tasks = tasks::Model::list_for_user(&ctx.db, &auth.claims.pid, &auth.claims.pid).await?;
+pub async fn tasks_me(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+    Query(filter): Query<TaskFilter>,
+) -> Result<Response> {
+    let page =
+        tasks::Model::list_for_user(&ctx.db, &auth.claims.pid, &auth.claims.pid, &list, &filter)
+            .await?;
 
-    format::json(views::task::TaskResponse::from_vec(tasks))
+    format::json(PageResponse::new(page, &list, TaskResponse::new))
 }
 
 pub fn routes() -> Routes {
@@ -0,0 +1,182 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unnecessary_struct_initialization)]
+#![allow(clippy::unused_async)]
+use axum::{debug_handler, extract::Query};
+use loco_openapi::prelude::*;
+use loco_rs::prelude::*;
+
+use crate::{
+    common::{pagination::ListParams, responses},
+    models::{
+        tasks, webhook_deliveries,
+        webhooks::{self, CreateParams},
+    },
+    views::{
+        page::PageResponse,
+        webhook::{DeliveryResponse, WebhookResponse},
+    },
+};
+
+/// List Webhooks
+///
+/// List the Webhooks the User registered
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Array of Webhook objects", body = Vec<WebhookResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+#[debug_handler]
+pub async fn list(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let hooks = webhooks::Model::list_for_user(&ctx.db, &auth.claims.pid).await?;
+
+    format::json(WebhookResponse::from_vec(hooks))
+}
+
+/// Create Webhook
+///
+/// Register a URL that receives the events of a Task and its subtasks, or of all Tasks the
+/// User owns when no Task is given. Every delivery is a JSON POST signed with HMAC-SHA256 of
+/// the body in the `x-taskhub-signature` header, the secret is only returned here.
+/// Failed deliveries are retried with an exponential backoff, the hook is disabled after
+/// too many failures in a row
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhook created", body = WebhookResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = CreateParams
+)]
+#[debug_handler]
+pub async fn add(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateParams>,
+) -> Result<Response> {
+    if let Some(task_id) = params.task_id {
+        tasks::Model::check_owner(&ctx.db, &auth.claims.pid, task_id).await?;
+    }
+
+    let hook = match webhooks::Model::add(&ctx.db, &auth.claims.pid, &params).await {
+        Ok(hook) => hook,
+        Err(ModelError::Message(msg)) => return responses::bad_request(msg),
+        Err(err) => return Err(err.into()),
+    };
+
+    format::json(WebhookResponse::with_secret(hook))
+}
+
+/// Delete Webhook
+///
+/// Delete the Webhook with its delivery log
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhook deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Webhook id"),
+    ),
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let hook = webhooks::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    hook.delete(&ctx.db).await?;
+
+    format::empty()
+}
+
+/// Enable Webhook
+///
+/// Turn the Webhook back on after it was disabled for failing, pending deliveries are
+/// retried again
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/enable",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhook enabled", body = WebhookResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Webhook id"),
+    ),
+)]
+#[debug_handler]
+pub async fn enable(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+) -> Result<Response> {
+    let hook = webhooks::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    let hook = webhooks::ActiveModel::enable(&ctx.db, hook).await?;
+
+    format::json(WebhookResponse::new(hook))
+}
+
+/// Webhook Deliveries
+///
+/// List the deliveries of the Webhook with the result of their last attempt
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Page of deliveries", body = PageResponse<DeliveryResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = i32, Path, description = "Webhook id"),
+        ListParams,
+    ),
+)]
+#[debug_handler]
+pub async fn deliveries(
+    auth: auth::JWT,
+    Path(id): Path<i32>,
+    State(ctx): State<AppContext>,
+    Query(list): Query<ListParams>,
+) -> Result<Response> {
+    let hook = webhooks::Model::load_owned(&ctx.db, &auth.claims.pid, id).await?;
+
+    let page = webhook_deliveries::Model::list_for_webhook(&ctx.db, hook.id, &list).await?;
+
+    format::json(PageResponse::new(page, &list, DeliveryResponse::new))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/webhooks/")
+        .add("/", openapi(get(list), routes!(list)))
+        .add("/", openapi(post(add), routes!(add)))
+        .add("{id}", openapi(delete(remove), routes!(remove)))
+        .add("{id}/enable", openapi(post(enable), routes!(enable)))
+        .add(
+            "{id}/deliveries",
+            openapi(get(deliveries), routes!(deliveries)),
+        )
+}
@@ -1,2 +1,3 @@
 pub mod axum_session;
 pub mod oauth2;
+pub mod webhooks;
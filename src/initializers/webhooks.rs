@@ -0,0 +1,38 @@
+use loco_rs::prelude::*;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{common::events, workers::webhook_delivery::WebhookDeliveryWorker};
+
+/// Turns every published Task event into webhook deliveries
+pub struct WebhookDispatchInitializer;
+
+#[async_trait]
+impl Initializer for WebhookDispatchInitializer {
+    fn name(&self) -> String {
+        "webhook-dispatch".to_string()
+    }
+
+    async fn before_run(&self, ctx: &AppContext) -> Result<()> {
+        let ctx = ctx.clone();
+        let mut receiver = events::hub().subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "webhook dispatch lagged");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                if let Err(e) = WebhookDeliveryWorker::dispatch(&ctx, &event).await {
+                    tracing::error!(error = ?e, "could not dispatch webhooks");
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+// task mailer
+#![allow(non_upper_case_globals)]
+
+use chrono::{DateTime, Utc};
+use loco_rs::prelude::*;
+use serde_json::json;
+
+use crate::{
+    common::settings::Settings,
+    models::{comments, invitations, tasks, users},
+};
+
+static access_expired: Dir<'_> = include_dir!("src/mailers/task/access_expired");
+static due_reminder: Dir<'_> = include_dir!("src/mailers/task/due_reminder");
+static invitation: Dir<'_> = include_dir!("src/mailers/task/invitation");
+static mention: Dir<'_> = include_dir!("src/mailers/task/mention");
+
+#[allow(clippy::module_name_repetitions)]
+pub struct TaskMailer {}
+impl Mailer for TaskMailer {}
+impl TaskMailer {
+    /// Sending a reminder that the task is due soon
+    ///
+    /// # Errors
+    ///
+    /// When email sending is failed
+    pub async fn send_due_reminder(
+        ctx: &AppContext,
+        user: &users::Model,
+        task: &tasks::Model,
+        due_at: &DateTime<Utc>,
+    ) -> Result<()> {
+        let settings = &Settings::from_opt_json(&ctx.config.settings)?;
+
+        Self::mail_template(
+            ctx,
+            &due_reminder,
+            mailer::Args {
+                to: user.email.to_string(),
+                locals: json!({
+                    "name": user.name,
+                    "taskId": task.id,
+                    "taskName": task.name,
+                    "dueAt": due_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                    "frontend": settings.frontend,
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sending an invitation to the task to an email without an account
+    ///
+    /// # Errors
+    ///
+    /// When email sending is failed
+    pub async fn send_invitation(
+        ctx: &AppContext,
+        inviter: &users::Model,
+        task: &tasks::Model,
+        invite: &invitations::Model,
+    ) -> Result<()> {
+        let settings = &Settings::from_opt_json(&ctx.config.settings)?;
+
+        Self::mail_template(
+            ctx,
+            &invitation,
+            mailer::Args {
+                to: invite.email.to_string(),
+                locals: json!({
+                    "inviterName": inviter.name,
+                    "taskName": task.name,
+                    "token": invite.token,
+                    "frontend": settings.frontend,
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sending a notice to the task owner that the access of a user expired
+    ///
+    /// # Errors
+    ///
+    /// When email sending is failed
+    pub async fn send_access_expired(
+        ctx: &AppContext,
+        owner: &users::Model,
+        user: &users::Model,
+        task: &tasks::Model,
+    ) -> Result<()> {
+        let settings = &Settings::from_opt_json(&ctx.config.settings)?;
+
+        Self::mail_template(
+            ctx,
+            &access_expired,
+            mailer::Args {
+                to: owner.email.to_string(),
+                locals: json!({
+                    "name": owner.name,
+                    "userName": user.name,
+                    "userEmail": user.email,
+                    "taskId": task.id,
+                    "taskName": task.name,
+                    "frontend": settings.frontend,
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sending a notice to a user that they were mentioned in a comment
+    ///
+    /// # Errors
+    ///
+    /// When email sending is failed
+    pub async fn send_mention(
+        ctx: &AppContext,
+        user: &users::Model,
+        author: &users::Model,
+        task: &tasks::Model,
+        comment: &comments::Model,
+    ) -> Result<()> {
+        let settings = &Settings::from_opt_json(&ctx.config.settings)?;
+
+        Self::mail_template(
+            ctx,
+            &mention,
+            mailer::Args {
+                to: user.email.to_string(),
+                locals: json!({
+                    "name": user.name,
+                    "authorName": author.name,
+                    "taskId": task.id,
+                    "taskName": task.name,
+                    "commentId": comment.id,
+                    "body": comment.body,
+                    "frontend": settings.frontend,
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+}
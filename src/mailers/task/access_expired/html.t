@@ -0,0 +1,12 @@
+;<html>
+
+<body>
+  Dear {{name}},
+  The access of <b>{{userName}}</b> ({{userEmail}}) to the task <b>{{taskName}}</b> expired and was removed.
+  <a href="https://{{frontend}}/tasks/{{taskId}}">
+    Open the task
+  </a>
+  <p>Best regards,<br>The TaskHub Team</p>
+</body>
+
+</html>
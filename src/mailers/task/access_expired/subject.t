@@ -0,0 +1 @@
+Access of {{userName}} to {{taskName}} expired
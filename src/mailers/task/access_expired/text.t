@@ -0,0 +1,5 @@
+Dear {{name}},
+  The access of {{userName}} ({{userEmail}}) to the task {{taskName}} expired and was removed.
+  Open the task with the link below:
+
+  https://{{frontend}}/tasks/{{taskId}}
@@ -0,0 +1,12 @@
+;<html>
+
+<body>
+  Dear {{name}},
+  The task <b>{{taskName}}</b> is due on {{dueAt}}.
+  <a href="https://{{frontend}}/tasks/{{taskId}}">
+    Open the task
+  </a>
+  <p>Best regards,<br>The TaskHub Team</p>
+</body>
+
+</html>
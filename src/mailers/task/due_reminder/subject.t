@@ -0,0 +1 @@
+Reminder: {{taskName}} is due soon
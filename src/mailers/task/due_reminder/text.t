@@ -0,0 +1,5 @@
+Dear {{name}},
+  The task {{taskName}} is due on {{dueAt}}.
+  Open the task with the link below:
+
+  https://{{frontend}}/tasks/{{taskId}}
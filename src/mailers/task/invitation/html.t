@@ -0,0 +1,13 @@
+;<html>
+
+<body>
+  Hello,
+  {{inviterName}} invited you to the task <b>{{taskName}}</b> on TaskHub.
+  <a href="https://{{frontend}}/register?invite={{token}}">
+    Create your account
+  </a>
+  <p>The task is shared with you as soon as you sign up with this email and verify it.</p>
+  <p>Best regards,<br>The TaskHub Team</p>
+</body>
+
+</html>
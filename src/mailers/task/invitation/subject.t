@@ -0,0 +1 @@
+Invitation to {{taskName}} on TaskHub
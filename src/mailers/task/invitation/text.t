@@ -0,0 +1,6 @@
+Hello,
+  {{inviterName}} invited you to the task {{taskName}} on TaskHub.
+  Create your account with the link below, the task is shared with you
+  as soon as you sign up with this email and verify it:
+
+  https://{{frontend}}/register?invite={{token}}
@@ -0,0 +1,13 @@
+;<html>
+
+<body>
+  Dear {{name}},
+  <b>{{authorName}}</b> mentioned you in a comment on the task <b>{{taskName}}</b>:
+  <blockquote>{{body}}</blockquote>
+  <a href="https://{{frontend}}/tasks/{{taskId}}#comment-{{commentId}}">
+    Open the comment
+  </a>
+  <p>Best regards,<br>The TaskHub Team</p>
+</body>
+
+</html>
@@ -0,0 +1 @@
+{{authorName}} mentioned you on {{taskName}}
@@ -0,0 +1,8 @@
+Dear {{name}},
+  {{authorName}} mentioned you in a comment on the task {{taskName}}:
+
+  {{body}}
+
+  Open the comment with the link below:
+
+  https://{{frontend}}/tasks/{{taskId}}#comment-{{commentId}}
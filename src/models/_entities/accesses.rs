@@ -12,6 +12,8 @@ pub struct Model {
     pub accesslevel: AccessLevelEnum,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    pub expires_at: Option<DateTimeWithTimeZone>,
     pub user_id: i32,
     pub task_id: i32,
 }
@@ -15,10 +15,13 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub owner_id: i32,
     pub task_id: i32,
+    pub due_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::due_date_reminders::Entity")]
+    DueDateReminders,
     #[sea_orm(
         belongs_to = "super::tasks::Entity",
         from = "Column::TaskId",
@@ -37,6 +40,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::due_date_reminders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DueDateReminders.def()
+    }
+}
+
 impl Related<super::tasks::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Tasks.def()
@@ -4,8 +4,22 @@ pub mod prelude;
 
 pub mod accesses;
 pub mod attachments;
+pub mod audit_events;
+pub mod comments;
+pub mod due_date_reminders;
+pub mod group_accesses;
+pub mod group_members;
+pub mod groups;
+pub mod invitations;
 pub mod o_auth2_sessions;
+pub mod purchases;
 pub mod roles;
 pub mod sea_orm_active_enums;
+pub mod solution_files;
+pub mod solutions;
 pub mod tasks;
+pub mod template_attachments;
+pub mod templates;
 pub mod users;
+pub mod webhook_deliveries;
+pub mod webhooks;
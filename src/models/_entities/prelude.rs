@@ -2,7 +2,21 @@
 
 pub use super::accesses::Entity as Accesses;
 pub use super::attachments::Entity as Attachments;
+pub use super::audit_events::Entity as AuditEvents;
+pub use super::comments::Entity as Comments;
+pub use super::due_date_reminders::Entity as DueDateReminders;
+pub use super::group_accesses::Entity as GroupAccesses;
+pub use super::group_members::Entity as GroupMembers;
+pub use super::groups::Entity as Groups;
+pub use super::invitations::Entity as Invitations;
 pub use super::o_auth2_sessions::Entity as OAuth2Sessions;
+pub use super::purchases::Entity as Purchases;
 pub use super::roles::Entity as Roles;
+pub use super::solution_files::Entity as SolutionFiles;
+pub use super::solutions::Entity as Solutions;
 pub use super::tasks::Entity as Tasks;
+pub use super::template_attachments::Entity as TemplateAttachments;
+pub use super::templates::Entity as Templates;
 pub use super::users::Entity as Users;
+pub use super::webhook_deliveries::Entity as WebhookDeliveries;
+pub use super::webhooks::Entity as Webhooks;
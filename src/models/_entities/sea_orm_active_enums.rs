@@ -8,6 +8,43 @@ use utoipa::ToSchema;
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
 )]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "audit_action_enum")]
+pub enum AuditActionEnum {
+    #[sea_orm(string_value = "Create")]
+    Create,
+    #[sea_orm(string_value = "Update")]
+    Update,
+    #[sea_orm(string_value = "Delete")]
+    Delete,
+}
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "audit_entity_enum")]
+pub enum AuditEntityEnum {
+    #[sea_orm(string_value = "Task")]
+    Task,
+    #[sea_orm(string_value = "Access")]
+    Access,
+    #[sea_orm(string_value = "Attachment")]
+    Attachment,
+    #[sea_orm(string_value = "GroupAccess")]
+    GroupAccess,
+}
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "access_level_enum")]
 pub enum AccessLevelEnum {
     #[sea_orm(string_value = "View")]
@@ -64,6 +101,56 @@ pub enum AttachmentTypeEnum {
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
 )]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "purchase_status_enum"
+)]
+pub enum PurchaseStatusEnum {
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Paid")]
+    Paid,
+    #[sea_orm(string_value = "Failed")]
+    Failed,
+}
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "solution_status_enum"
+)]
+pub enum SolutionStatusEnum {
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Accepted")]
+    Accepted,
+    #[sea_orm(string_value = "Rejected")]
+    Rejected,
+}
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "task_status_enum")]
+pub enum TaskStatusEnum {
+    #[sea_orm(string_value = "Draft")]
+    Draft,
+    #[sea_orm(string_value = "Open")]
+    Open,
+    #[sea_orm(string_value = "InProgress")]
+    InProgress,
+    #[sea_orm(string_value = "Review")]
+    Review,
+    #[sea_orm(string_value = "Done")]
+    Done,
+    #[sea_orm(string_value = "Archived")]
+    Archived,
+}
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
 #[sea_orm(
     rs_type = "String",
     db_type = "Enum",
@@ -1,6 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.11
 
-use super::sea_orm_active_enums::TaskVisibilityEnum;
+use super::sea_orm_active_enums::{TaskStatusEnum, TaskVisibilityEnum};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +13,12 @@ pub struct Model {
     pub visibility: TaskVisibilityEnum,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub parent_id: Option<i32>,
+    pub status: TaskStatusEnum,
+    pub price_cents: Option<i32>,
+    pub owner_id: Option<i32>,
+    pub pending_owner_id: Option<i32>,
+    pub forked_from_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -21,6 +27,50 @@ pub enum Relation {
     Accesses,
     #[sea_orm(has_many = "super::attachments::Entity")]
     Attachments,
+    #[sea_orm(has_many = "super::comments::Entity")]
+    Comments,
+    #[sea_orm(has_many = "super::group_accesses::Entity")]
+    GroupAccesses,
+    #[sea_orm(has_many = "super::invitations::Entity")]
+    Invitations,
+    #[sea_orm(has_many = "super::purchases::Entity")]
+    Purchases,
+    #[sea_orm(has_many = "super::solutions::Entity")]
+    Solutions,
+    #[sea_orm(has_many = "super::webhooks::Entity")]
+    Webhooks,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::OwnerId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Owner,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::PendingOwnerId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    PendingOwner,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    SelfRef,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ForkedFromId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    ForkedFrom,
 }
 
 impl Related<super::accesses::Entity> for Entity {
@@ -34,3 +84,39 @@ impl Related<super::attachments::Entity> for Entity {
         Relation::Attachments.def()
     }
 }
+
+impl Related<super::comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
+
+impl Related<super::group_accesses::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroupAccesses.def()
+    }
+}
+
+impl Related<super::invitations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invitations.def()
+    }
+}
+
+impl Related<super::purchases::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Purchases.def()
+    }
+}
+
+impl Related<super::solutions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Solutions.def()
+    }
+}
+
+impl Related<super::webhooks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhooks.def()
+    }
+}
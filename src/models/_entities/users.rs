@@ -25,6 +25,8 @@ pub struct Model {
     pub magic_link_token: Option<String>,
     pub magic_link_expiration: Option<DateTimeWithTimeZone>,
     pub role_id: i32,
+    #[sea_orm(unique)]
+    pub calendar_token: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -33,8 +35,20 @@ pub enum Relation {
     Accesses,
     #[sea_orm(has_many = "super::attachments::Entity")]
     Attachments,
+    #[sea_orm(has_many = "super::comments::Entity")]
+    Comments,
+    #[sea_orm(has_many = "super::due_date_reminders::Entity")]
+    DueDateReminders,
+    #[sea_orm(has_many = "super::group_members::Entity")]
+    GroupMembers,
+    #[sea_orm(has_many = "super::groups::Entity")]
+    Groups,
+    #[sea_orm(has_many = "super::invitations::Entity")]
+    Invitations,
     #[sea_orm(has_many = "super::o_auth2_sessions::Entity")]
     OAuth2Sessions,
+    #[sea_orm(has_many = "super::purchases::Entity")]
+    Purchases,
     #[sea_orm(
         belongs_to = "super::roles::Entity",
         from = "Column::RoleId",
@@ -43,6 +57,12 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Roles,
+    #[sea_orm(has_many = "super::solutions::Entity")]
+    Solutions,
+    #[sea_orm(has_many = "super::templates::Entity")]
+    Templates,
+    #[sea_orm(has_many = "super::webhooks::Entity")]
+    Webhooks,
 }
 
 impl Related<super::accesses::Entity> for Entity {
@@ -57,14 +77,68 @@ impl Related<super::attachments::Entity> for Entity {
     }
 }
 
+impl Related<super::comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
+
+impl Related<super::due_date_reminders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DueDateReminders.def()
+    }
+}
+
+impl Related<super::group_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroupMembers.def()
+    }
+}
+
+impl Related<super::groups::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Groups.def()
+    }
+}
+
+impl Related<super::invitations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invitations.def()
+    }
+}
+
 impl Related<super::o_auth2_sessions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::OAuth2Sessions.def()
     }
 }
 
+impl Related<super::purchases::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Purchases.def()
+    }
+}
+
 impl Related<super::roles::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Roles.def()
     }
 }
+
+impl Related<super::solutions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Solutions.def()
+    }
+}
+
+impl Related<super::templates::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Templates.def()
+    }
+}
+
+impl Related<super::webhooks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhooks.def()
+    }
+}
@@ -1,12 +1,23 @@
-use crate::models::{
-    _entities::accesses,
-    tasks::{self, AccessLevelEnum},
-    users,
+use crate::{
+    common::{
+        events::{self, TaskEvent},
+        responses,
+    },
+    models::{
+        _entities::{accesses, group_accesses, group_members},
+        audit_events::{self, AuditEntityEnum},
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
 };
 
 pub use super::_entities::accesses::{ActiveModel, Entity, Model};
+use chrono::{DateTime, Utc};
 use loco_rs::model::{ModelError, ModelResult};
-use sea_orm::{entity::prelude::*, ActiveValue::Set, IntoActiveModel};
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, Condition, IntoActiveModel, QuerySelect, QueryTrait,
+    TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 pub type Accesses = Entity;
@@ -31,12 +42,94 @@ impl ActiveModelBehavior for ActiveModel {
 pub struct GrantParams {
     pub email: String,
     pub accesslevel: AccessLevelEnum,
+    /// The Access applies from this moment on, right away when empty
+    #[serde(default)]
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    /// The Access is removed at this moment, never when empty
+    #[serde(default)]
+    pub expires_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateParams {
     pub pid: String,
     pub accesslevel: AccessLevelEnum,
+    /// Replaces the start of the Access, empty opens it
+    #[serde(default)]
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    /// Replaces the expiry of the Access, empty opens it
+    #[serde(default)]
+    pub expires_at: Option<DateTimeWithTimeZone>,
+}
+
+/// What the user managing the Accesses of a Task may do. The owner may do
+/// anything, other users only handle levels strictly below their own and
+/// never the owner's Access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Granter {
+    pub user_id: i32,
+    pub owner_id: i32,
+    pub level: Option<AccessLevelEnum>,
+}
+
+impl Granter {
+    pub async fn load(db: &DatabaseConnection, user_pid: &str, task_id: i32) -> ModelResult<Self> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let owner = Model::find_task_owner(db, task_id).await?;
+
+        Ok(Self {
+            user_id: user.id,
+            owner_id: owner.id,
+            level: tasks::Model::effective_access(db, user.id, task_id).await?,
+        })
+    }
+
+    #[must_use]
+    pub const fn is_owner(&self) -> bool {
+        self.user_id == self.owner_id
+    }
+
+    /// Whether the level can be granted, changed or revoked
+    #[must_use]
+    pub fn can_handle(&self, level: AccessLevelEnum) -> bool {
+        self.is_owner() || self.level.is_some_and(|own| level < own)
+    }
+
+    /// Fails with 403 when the level is not below the granter's own
+    pub fn check_level(&self, level: AccessLevelEnum) -> loco_rs::Result<()> {
+        if self.can_handle(level) {
+            return Ok(());
+        }
+
+        match self.level {
+            Some(own) => responses::forbidden(format!(
+                "Only access levels below {own:?} can be managed, {level:?} is not"
+            )),
+            None => responses::forbidden("Access levels can not be managed without access"),
+        }
+    }
+
+    /// Fails with 403 when the Access belongs to the owner or is not below the granter's level
+    pub fn check_access(&self, access: &accesses::Model) -> loco_rs::Result<()> {
+        if !self.is_owner() && access.user_id == self.owner_id {
+            return responses::forbidden("Access of the owner can not be managed");
+        }
+
+        self.check_level(access.accesslevel)
+    }
+}
+
+/// The window of an Access must not end before it starts
+pub fn check_window(
+    starts_at: Option<DateTimeWithTimeZone>,
+    expires_at: Option<DateTimeWithTimeZone>,
+) -> ModelResult<()> {
+    match (starts_at, expires_at) {
+        (Some(starts_at), Some(expires_at)) if expires_at <= starts_at => {
+            Err(ModelError::msg("Access can not expire before it starts"))
+        }
+        _ => Ok(()),
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -44,6 +137,60 @@ pub struct DenyParams {
     pub pid: String,
 }
 
+/// Whether the Access grants FullAccess from now on for good. One that starts later
+/// does not apply yet, one that expires is removed by the expiry job
+fn keeps_full_access(access: &accesses::Model, now: DateTime<Utc>) -> bool {
+    access.accesslevel == AccessLevelEnum::FullAccess
+        && access.expires_at.is_none()
+        && access.starts_at.is_none_or(|starts_at| starts_at <= now)
+}
+
+/// Refuses changes that take FullAccess away from the owner of the Task or that
+/// leave a root Task without anybody holding FullAccess on it. `after` is the
+/// Access once changed, empty when it is removed
+async fn check_full_access_kept(
+    db: &DatabaseConnection,
+    access: &accesses::Model,
+    after: Option<&accesses::Model>,
+) -> ModelResult<()> {
+    let now = Utc::now();
+
+    if access.accesslevel != AccessLevelEnum::FullAccess
+        || after.is_some_and(|after| keeps_full_access(after, now))
+    {
+        return Ok(());
+    }
+
+    let task = tasks::Model::load(db, access.task_id).await?;
+
+    if task.owner_id == Some(access.user_id) {
+        return Err(ModelError::msg(
+            "Owner keeps FullAccess, transfer the ownership first",
+        ));
+    }
+
+    // subtasks inherit FullAccess from their ancestors
+    if task.parent_id.is_some() {
+        return Ok(());
+    }
+
+    let others = accesses::Entity::find()
+        .filter(accesses::Column::TaskId.eq(task.id))
+        .filter(accesses::Column::Id.ne(access.id))
+        .filter(accesses::Column::Accesslevel.eq(AccessLevelEnum::FullAccess))
+        .filter(accesses::Entity::active_at(now))
+        .count(db)
+        .await?;
+
+    if others == 0 {
+        return Err(ModelError::msg(
+            "Task must keep at least one user with FullAccess",
+        ));
+    }
+
+    Ok(())
+}
+
 // implement your read-oriented logic here
 impl Model {
     pub async fn find_by_email(
@@ -80,25 +227,77 @@ impl Model {
         access.ok_or_else(|| ModelError::EntityNotFound)
     }
 
+    /// Owner of the Task, subtasks without one are owned by the owner of the closest
+    /// owned ancestor
     pub async fn find_task_owner(
         db: &DatabaseConnection,
         task_id: i32,
     ) -> ModelResult<users::Model> {
-        let owner = users::Entity::find()
-            .inner_join(accesses::Entity)
-            .filter(accesses::Column::TaskId.eq(task_id))
-            .filter(accesses::Column::Accesslevel.eq(AccessLevelEnum::FullAccess))
+        let owner_id = tasks::Model::lineage(db, task_id)
+            .await?
+            .iter()
+            .find_map(|task| task.owner_id)
+            .ok_or(ModelError::EntityNotFound)?;
+
+        users::Entity::find_by_id(owner_id)
             .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Users with access to the Task, directly or through a Group, including
+    /// the ones inherited from its ancestors
+    pub async fn list_users_for_task(
+        db: &DatabaseConnection,
+        task_id: i32,
+    ) -> ModelResult<Vec<users::Model>> {
+        let lineage = tasks::Model::lineage(db, task_id).await?;
+        let task_ids: Vec<i32> = lineage.iter().map(|task| task.id).collect();
+
+        let users = users::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        users::Column::Id.in_subquery(
+                            accesses::Entity::find()
+                                .select_only()
+                                .column(accesses::Column::UserId)
+                                .filter(accesses::Column::TaskId.is_in(task_ids.clone()))
+                                .filter(accesses::Entity::active_at(Utc::now()))
+                                .into_query(),
+                        ),
+                    )
+                    .add(
+                        users::Column::Id.in_subquery(
+                            group_members::Entity::find()
+                                .select_only()
+                                .column(group_members::Column::UserId)
+                                .filter(
+                                    group_members::Column::GroupId.in_subquery(
+                                        group_accesses::Entity::find()
+                                            .select_only()
+                                            .column(group_accesses::Column::GroupId)
+                                            .filter(group_accesses::Column::TaskId.is_in(task_ids))
+                                            .into_query(),
+                                    ),
+                                )
+                                .into_query(),
+                        ),
+                    ),
+            )
+            .all(db)
             .await?;
 
-        owner.ok_or_else(|| ModelError::EntityNotFound)
+        Ok(users)
     }
 
+    /// Accesses of the Task that did not expire yet, including the ones that start later
     pub async fn list_for_task(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
         let task = tasks::Model::load(db, task_id).await?;
 
         let accesses = accesses::Entity::find()
             .filter(accesses::Column::TaskId.eq(task.id))
+            .filter(accesses::Entity::not_expired_at(Utc::now()))
             .all(db)
             .await?;
 
@@ -107,21 +306,56 @@ impl Model {
 
     pub async fn grant_access(
         db: &DatabaseConnection,
+        actor_pid: &str,
         task_id: i32,
         params: GrantParams,
     ) -> ModelResult<accesses::Model> {
+        check_window(params.starts_at, params.expires_at)?;
+
         let user = users::Model::find_by_email(db, &params.email).await?;
         let task = tasks::Model::load(db, task_id).await?;
 
+        // a User holds one Access per Task, it is changed through an update
+        let existing = accesses::Entity::find()
+            .filter(accesses::Column::UserId.eq(user.id))
+            .filter(accesses::Column::TaskId.eq(task.id))
+            .one(db)
+            .await?;
+        if existing.is_some() {
+            return Err(ModelError::EntityAlreadyExists);
+        }
+
+        let txn = db.begin().await?;
+
         let access = accesses::ActiveModel {
             user_id: Set(user.id),
             task_id: Set(task.id),
             accesslevel: Set(params.accesslevel),
+            starts_at: Set(params.starts_at),
+            expires_at: Set(params.expires_at),
             ..Default::default()
         }
-        .insert(db)
+        .insert(&txn)
         .await?;
 
+        audit_events::ActiveModel::record(
+            &txn,
+            actor_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            None,
+            Some(&access),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: Some(access.user_id),
+        });
+
         Ok(access)
     }
 }
@@ -130,34 +364,170 @@ impl Model {
 impl ActiveModel {
     pub async fn update_access(
         db: &DatabaseConnection,
+        actor_pid: &str,
         task_id: i32,
         params: UpdateParams,
     ) -> ModelResult<accesses::Model> {
-        let mut active_model = accesses::Model::find_by_pid(db, task_id, &params.pid)
-            .await?
-            .into_active_model();
+        check_window(params.starts_at, params.expires_at)?;
+
+        let before = accesses::Model::find_by_pid(db, task_id, &params.pid).await?;
+        let after = accesses::Model {
+            accesslevel: params.accesslevel,
+            starts_at: params.starts_at,
+            expires_at: params.expires_at,
+            ..before.clone()
+        };
+        check_full_access_kept(db, &before, Some(&after)).await?;
+
+        let mut active_model = before.clone().into_active_model();
+        active_model.accesslevel = Set(after.accesslevel);
+        active_model.starts_at = Set(after.starts_at);
+        active_model.expires_at = Set(after.expires_at);
+
+        let txn = db.begin().await?;
+
+        let access = active_model.update(&txn).await?;
 
-        active_model.accesslevel = Set(params.accesslevel);
+        audit_events::ActiveModel::record(
+            &txn,
+            actor_pid,
+            task_id,
+            AuditEntityEnum::Access,
+            access.id,
+            Some(&before),
+            Some(&access),
+        )
+        .await?;
+
+        txn.commit().await?;
 
-        let access = active_model.update(db).await?;
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: Some(access.user_id),
+        });
 
         Ok(access)
     }
 
     pub async fn deny_access(
         db: &DatabaseConnection,
+        actor_pid: &str,
         task_id: i32,
         params: DenyParams,
     ) -> ModelResult<()> {
-        accesses::Model::find_by_pid(db, task_id, &params.pid)
-            .await?
-            .into_active_model()
-            .delete(db)
-            .await?;
+        let access = accesses::Model::find_by_pid(db, task_id, &params.pid).await?;
+        check_full_access_kept(db, &access, None).await?;
+
+        let txn = db.begin().await?;
+
+        access.clone().into_active_model().delete(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            actor_pid,
+            task_id,
+            AuditEntityEnum::Access,
+            access.id,
+            Some(&access),
+            None,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: Some(access.user_id),
+        });
 
         Ok(())
     }
+
+    /// Removes the expired Access on behalf of the expiry job. The FullAccess of the
+    /// owner, or the last one of a root Task, is kept instead by clearing its expiry.
+    /// Returns whether the Access was removed
+    pub async fn expire(db: &DatabaseConnection, access: &accesses::Model) -> ModelResult<bool> {
+        let kept = match check_full_access_kept(db, access, None).await {
+            Ok(()) => None,
+            Err(ModelError::Message(reason)) => {
+                tracing::warn!(
+                    access_id = access.id,
+                    task_id = access.task_id,
+                    reason,
+                    "kept expired access, cleared its expiry"
+                );
+
+                let mut active_model = access.clone().into_active_model();
+                active_model.expires_at = Set(None);
+                Some(active_model)
+            }
+            Err(err) => return Err(err),
+        };
+
+        let txn = db.begin().await?;
+
+        let after = match kept {
+            Some(active_model) => Some(active_model.update(&txn).await?),
+            None => {
+                access.clone().into_active_model().delete(&txn).await?;
+                None
+            }
+        };
+
+        audit_events::ActiveModel::record(
+            &txn,
+            audit_events::SYSTEM_PID,
+            access.task_id,
+            AuditEntityEnum::Access,
+            access.id,
+            Some(access),
+            after.as_ref(),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: Some(access.user_id),
+        });
+
+        Ok(after.is_none())
+    }
 }
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// Accesses that apply at the given moment
+    #[must_use]
+    pub fn active_at(now: DateTime<Utc>) -> Condition {
+        Condition::all()
+            .add(
+                Condition::any()
+                    .add(accesses::Column::StartsAt.is_null())
+                    .add(accesses::Column::StartsAt.lte(now)),
+            )
+            .add(Self::not_expired_at(now))
+    }
+
+    /// Accesses that are not expired at the given moment
+    #[must_use]
+    pub fn not_expired_at(now: DateTime<Utc>) -> Condition {
+        Condition::any()
+            .add(accesses::Column::ExpiresAt.is_null())
+            .add(accesses::Column::ExpiresAt.gt(now))
+    }
+
+    /// Accesses expired at the given moment, the expiry job removes them
+    pub async fn find_expired(
+        db: &DatabaseConnection,
+        now: DateTime<Utc>,
+    ) -> ModelResult<Vec<Model>> {
+        let expired = accesses::Entity::find()
+            .filter(accesses::Column::ExpiresAt.lte(now))
+            .all(db)
+            .await?;
+
+        Ok(expired)
+    }
+}
@@ -1,35 +1,268 @@
+use std::collections::BTreeMap;
+
 pub use super::_entities::{
     attachments::{ActiveModel, Entity, Model},
     sea_orm_active_enums::AttachmentTypeEnum,
 };
-use crate::models::{
-    self,
-    _entities::accesses,
-    attachments,
-    tasks::{self, AccessLevelEnum},
-    users,
+use crate::{
+    common::{
+        events::{self, TaskEvent},
+        pagination::{ListParams, SortBy},
+        policy::Action,
+    },
+    models::{
+        self, attachments,
+        audit_events::{self, AuditEntityEnum},
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
 };
 use axum::body::Bytes;
 use axum_typed_multipart::{FieldData, TryFromMultipart};
-use loco_rs::prelude::*;
-use sea_orm::entity::prelude::*;
+use chrono::{DateTime, Utc};
+use loco_rs::{model::query::PageResponse, prelude::*};
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder, TransactionTrait};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use validator::{ValidateUrl, ValidationError, ValidationErrors};
 pub type Attachments = Entity;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ImportanceLevel {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Typed payload of an Attachment, its shape depends on the `AttachmentTypeEnum`.
+/// Stored as a string in the `data` column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum AttachmentData {
+    /// RFC 3339 datetime of a DueDate, normalized to UTC
+    DueDate(DateTime<Utc>),
+    /// Integer percentage of a Progress, from 0 to 100
+    Progress(u8),
+    /// Level of an Importance
+    Importance(ImportanceLevel),
+    /// Free-form text, URL or file name
+    Text(String),
+}
+
+impl AttachmentData {
+    /// Parses and validates the raw `data` of the given Attachment type
+    ///
+    /// # Errors
+    ///
+    /// When the data does not match the schema of the Attachment type
+    pub fn parse(
+        attachment_type: AttachmentTypeEnum,
+        data: &str,
+    ) -> std::result::Result<Self, ValidationErrors> {
+        let parsed = match attachment_type {
+            AttachmentTypeEnum::DueDate => DateTime::parse_from_rfc3339(data.trim())
+                .map(|date| Self::DueDate(date.with_timezone(&Utc)))
+                .map_err(|_| {
+                    ValidationError::new("datetime")
+                        .with_message("DueDate must be an RFC 3339 datetime.".into())
+                }),
+            AttachmentTypeEnum::Progress => match data.trim().parse::<u8>() {
+                Ok(progress) if progress <= 100 => Ok(Self::Progress(progress)),
+                _ => Err(ValidationError::new("range")
+                    .with_message("Progress must be an integer between 0 and 100.".into())),
+            },
+            AttachmentTypeEnum::Importance => {
+                serde_json::from_value(serde_json::Value::String(data.trim().to_string()))
+                    .map(Self::Importance)
+                    .map_err(|_| {
+                        ValidationError::new("importance").with_message(
+                            "Importance must be one of Low, Medium, High, Critical.".into(),
+                        )
+                    })
+            }
+            AttachmentTypeEnum::Url => {
+                if data.trim().validate_url() {
+                    Ok(Self::Text(data.trim().to_string()))
+                } else {
+                    Err(ValidationError::new("url").with_message("Url must be a valid URL.".into()))
+                }
+            }
+            AttachmentTypeEnum::File => tasks::check_file_name(data)
+                .map(|()| Self::Text(data.to_string()))
+                .map_err(|_| {
+                    ValidationError::new("file_name").with_message(
+                        "File name must not be empty or contain path separators.".into(),
+                    )
+                }),
+            AttachmentTypeEnum::Description
+            | AttachmentTypeEnum::Text
+            | AttachmentTypeEnum::Tip
+            | AttachmentTypeEnum::Hint
+            | AttachmentTypeEnum::Warning => {
+                if data.trim().is_empty() {
+                    Err(ValidationError::new("length")
+                        .with_message("Attachment data must not be empty.".into()))
+                } else {
+                    Ok(Self::Text(data.to_string()))
+                }
+            }
+        };
+
+        parsed.map_err(|error| {
+            let mut errors = ValidationErrors::new();
+            errors.add("data", error);
+            errors
+        })
+    }
+
+    /// Typed payload of a stored Attachment.
+    /// Rows written before the payloads were validated fall back to `Text`
+    #[must_use]
+    pub fn from_model(attachment: &Model) -> Self {
+        Self::parse(attachment.attachment_type, &attachment.data)
+            .unwrap_or_else(|_| Self::Text(attachment.data.clone()))
+    }
+
+    /// Normalized string form stored in the `data` column
+    #[must_use]
+    pub fn to_data(&self) -> String {
+        match self {
+            Self::DueDate(date) => date.to_rfc3339(),
+            Self::Progress(progress) => progress.to_string(),
+            Self::Importance(level) => format!("{level:?}"),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Validates `data` against the type. Both are `None` when they are not set in the
+/// saved `ActiveModel`, which is only fine if neither changes
+pub struct Validator {
+    pub attachment_type: Option<AttachmentTypeEnum>,
+    pub data: Option<String>,
+}
+
+impl Validate for Validator {
+    fn validate(&self) -> std::result::Result<(), ValidationErrors> {
+        match (self.attachment_type, &self.data) {
+            (Some(attachment_type), Some(data)) => {
+                AttachmentData::parse(attachment_type, data).map(|_| ())
+            }
+            (None, None) => Ok(()),
+            (None, Some(_)) => Err(missing("attachment_type")),
+            (Some(_), None) => Err(missing("data")),
+        }
+    }
+}
+
+fn missing(field: &'static str) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        field,
+        ValidationError::new("required").with_message("Field must be set.".into()),
+    );
+    errors
+}
+
+fn value<V>(value: &ActiveValue<V>) -> Option<V>
+where
+    V: Clone + Into<sea_orm::Value>,
+{
+    match value {
+        ActiveValue::Set(value) | ActiveValue::Unchanged(value) => Some(value.clone()),
+        ActiveValue::NotSet => None,
+    }
+}
+
+/// Failure to write an Attachment. Invalid data keeps the errors of each field, so
+/// that clients can show them next to the fields
+#[derive(Debug)]
+pub enum AttachmentError {
+    Invalid(ValidationErrors),
+    Model(ModelError),
+}
+
+pub type AttachmentResult<T> = std::result::Result<T, AttachmentError>;
+
+impl std::fmt::Display for AttachmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(errors) => std::fmt::Display::fmt(errors, f),
+            Self::Model(err) => std::fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl std::error::Error for AttachmentError {}
+
+impl From<ModelError> for AttachmentError {
+    fn from(err: ModelError) -> Self {
+        Self::Model(err)
+    }
+}
+
+impl From<DbErr> for AttachmentError {
+    fn from(err: DbErr) -> Self {
+        Self::Model(err.into())
+    }
+}
+
+/// Validates and normalizes the raw `data` of the Attachment type
+fn checked_data(attachment_type: AttachmentTypeEnum, data: &str) -> AttachmentResult<String> {
+    AttachmentData::parse(attachment_type, data)
+        .map(|data| data.to_data())
+        .map_err(AttachmentError::Invalid)
+}
+
+/// Validates and normalizes the raw `data` of the Attachment type, with the errors
+/// flattened into one message
+pub(crate) fn validated_data(
+    attachment_type: AttachmentTypeEnum,
+    data: &str,
+) -> ModelResult<String> {
+    AttachmentData::parse(attachment_type, data)
+        .map(|data| data.to_data())
+        .map_err(|errors| ModelError::Message(errors.to_string()))
+}
+
+impl Validatable for ActiveModel {
+    fn validator(&self) -> Box<dyn Validate> {
+        Box::new(Validator {
+            attachment_type: value(&self.attachment_type),
+            data: value(&self.data),
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
     async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
     where
         C: ConnectionTrait,
     {
-        if !insert && self.updated_at.is_unchanged() {
-            let mut this = self;
+        self.validate()?;
+
+        let mut this = self;
+
+        // `due_at` follows `data`, it is only set for DueDates
+        if let (Some(attachment_type), Some(data)) =
+            (value(&this.attachment_type), value(&this.data))
+        {
+            let due_at = match AttachmentData::parse(attachment_type, &data) {
+                Ok(AttachmentData::DueDate(due_at)) => Some(due_at.into()),
+                _ => None,
+            };
+            if value(&this.due_at) != Some(due_at) {
+                this.due_at = Set(due_at);
+            }
+        }
+
+        if !insert && this.updated_at.is_unchanged() {
             this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
-            Ok(this)
-        } else {
-            Ok(self)
         }
+
+        Ok(this)
     }
 }
 
@@ -63,33 +296,35 @@ pub struct AttachmentUpdateForm {
     pub file: Option<FieldData<Bytes>>,
 }
 
+/// DueDate of a Task the User can access, for the calendar feed
+#[derive(Debug)]
+pub struct CalendarEntry {
+    pub attachment: Model,
+    pub due_at: DateTime<Utc>,
+    pub task: tasks::Model,
+    /// First Description of the Task
+    pub description: Option<String>,
+}
+
+/// Filters of the Attachment list endpoint
+#[derive(Debug, Deserialize, Serialize, Default, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct AttachmentFilter {
+    pub attachment_type: Option<AttachmentTypeEnum>,
+}
+
 // implement your read-oriented logic here
 impl Model {
-    pub async fn has_access(
+    /// Fails unless the User is allowed the action on the Task of the Attachment
+    pub async fn authorize(
         db: &DatabaseConnection,
         user_pid: &str,
         attachment_id: i32,
-        levels: Vec<AccessLevelEnum>,
-    ) -> Result<()> {
-        let user = users::Model::find_by_pid(db, user_pid).await?;
+        action: Action,
+    ) -> Result<AccessLevelEnum> {
         let attachment = Model::load(db, attachment_id).await?;
 
-        let user_access = accesses::Entity::find()
-            .filter(accesses::Column::UserId.eq(user.id))
-            .filter(accesses::Column::TaskId.eq(attachment.task_id))
-            .one(db)
-            .await?;
-
-        let has_access = match user_access {
-            Some(access_level) => levels.contains(&access_level.accesslevel),
-            None => false,
-        };
-
-        if has_access {
-            Ok(())
-        } else {
-            unauthorized("unauthorized")
-        }
+        tasks::Model::authorize(db, user_pid, attachment.task_id, action).await
     }
 
     pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
@@ -110,25 +345,164 @@ impl Model {
         Ok(attachments)
     }
 
+    /// Lists a page of the Task's Attachments.
+    /// Sorting by name orders them by their `data`, e.g. the file name or the text
+    pub async fn page_attachments(
+        db: &DatabaseConnection,
+        task_id: i32,
+        params: &ListParams,
+        filter: &AttachmentFilter,
+    ) -> ModelResult<PageResponse<Self>> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let mut query = attachments::Entity::find()
+            .filter(models::_entities::attachments::Column::TaskId.eq(task.id));
+
+        if let Some(attachment_type) = filter.attachment_type {
+            query = query
+                .filter(models::_entities::attachments::Column::AttachmentType.eq(attachment_type));
+        }
+
+        let column = match params.sort_by {
+            SortBy::CreatedAt => models::_entities::attachments::Column::CreatedAt,
+            SortBy::UpdatedAt => models::_entities::attachments::Column::UpdatedAt,
+            SortBy::Name => models::_entities::attachments::Column::Data,
+        };
+
+        let query = query
+            .order_by(column, params.order())
+            .order_by(models::_entities::attachments::Column::Id, params.order());
+
+        model::query::paginate(db, query, None, &params.pagination()).await
+    }
+
+    /// Lists DueDate Attachments that fall into the `(from, until]` window,
+    /// together with their due date
+    pub async fn list_due_between(
+        db: &DatabaseConnection,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> ModelResult<Vec<(Self, DateTime<Utc>)>> {
+        let attachments = attachments::Entity::find()
+            .filter(
+                models::_entities::attachments::Column::AttachmentType
+                    .eq(AttachmentTypeEnum::DueDate),
+            )
+            .filter(models::_entities::attachments::Column::DueAt.gt(from))
+            .filter(models::_entities::attachments::Column::DueAt.lte(until))
+            .all(db)
+            .await?;
+
+        let due = attachments
+            .into_iter()
+            .filter_map(|attachment| {
+                let due_at = attachment.due_at?.with_timezone(&Utc);
+                Some((attachment, due_at))
+            })
+            .collect();
+
+        Ok(due)
+    }
+
+    /// Lists the DueDate Attachments of the Tasks the User can access, soonest first
+    pub async fn list_due_for_user(
+        db: &DatabaseConnection,
+        user_id: i32,
+    ) -> ModelResult<Vec<CalendarEntry>> {
+        let task_ids = tasks::Model::accessible_ids(db, user_id).await?;
+
+        let due = attachments::Entity::find()
+            .filter(
+                models::_entities::attachments::Column::AttachmentType
+                    .eq(AttachmentTypeEnum::DueDate),
+            )
+            .filter(models::_entities::attachments::Column::TaskId.is_in(task_ids))
+            .find_also_related(tasks::Entity)
+            .all(db)
+            .await?;
+
+        let mut descriptions = BTreeMap::new();
+        for description in attachments::Entity::find()
+            .filter(
+                models::_entities::attachments::Column::AttachmentType
+                    .eq(AttachmentTypeEnum::Description),
+            )
+            .filter(
+                models::_entities::attachments::Column::TaskId
+                    .is_in(due.iter().map(|(attachment, _)| attachment.task_id)),
+            )
+            .order_by_asc(models::_entities::attachments::Column::Id)
+            .all(db)
+            .await?
+        {
+            descriptions
+                .entry(description.task_id)
+                .or_insert(description.data);
+        }
+
+        let mut entries: Vec<CalendarEntry> = due
+            .into_iter()
+            .filter_map(|(attachment, task)| {
+                let task = task?;
+                let AttachmentData::DueDate(due_at) = AttachmentData::from_model(&attachment)
+                else {
+                    return None;
+                };
+
+                Some(CalendarEntry {
+                    description: descriptions.get(&task.id).cloned(),
+                    attachment,
+                    due_at,
+                    task,
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| (entry.due_at, entry.attachment.id));
+
+        Ok(entries)
+    }
+
     pub async fn add_attachment(
         db: &DatabaseConnection,
         user_pid: &str,
         task_id: i32,
         params: AttachmentAddParams,
-    ) -> ModelResult<Self> {
+    ) -> AttachmentResult<Self> {
         let user = users::Model::find_by_pid(db, user_pid).await?;
         let task = tasks::Model::load(db, task_id).await?;
 
+        let data = checked_data(params.attachment_type, &params.data)?;
+
+        let txn = db.begin().await?;
+
         let attachment = ActiveModel {
             task_id: Set(task.id),
             owner_id: Set(user.id),
             attachment_type: Set(params.attachment_type),
-            data: Set(params.data.clone()),
+            data: Set(data),
             ..Default::default()
         }
-        .insert(db)
+        .insert(&txn)
         .await?;
 
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Attachment,
+            attachment.id,
+            None,
+            Some(&attachment),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AttachmentAdded {
+            task_id: attachment.task_id,
+            attachment_id: attachment.id,
+        });
+
         Ok(attachment)
     }
 }
@@ -140,16 +514,74 @@ impl ActiveModel {
         db: &DatabaseConnection,
         user_pid: &str,
         params: AttachmentUpdateParams,
-    ) -> ModelResult<Model> {
+    ) -> AttachmentResult<Model> {
+        let Some(attachment_type) = value(&self.attachment_type) else {
+            return Err(ModelError::msg("Attachment type is not loaded").into());
+        };
+        let data = checked_data(attachment_type, &params.data)?;
+
         let user = users::Model::find_by_pid(db, user_pid).await?;
+        let before = self.clone().try_into_model()?;
 
         self.owner_id = Set(user.id);
-        self.data = Set(params.data);
+        self.data = Set(data);
+
+        let txn = db.begin().await?;
 
-        let attachment = self.update(db).await?;
+        let attachment = self.update(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            attachment.task_id,
+            AuditEntityEnum::Attachment,
+            attachment.id,
+            Some(&before),
+            Some(&attachment),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AttachmentUpdated {
+            task_id: attachment.task_id,
+            attachment_id: attachment.id,
+        });
 
         Ok(attachment)
     }
+
+    pub async fn remove_attachment(
+        self,
+        db: &DatabaseConnection,
+        user_pid: &str,
+    ) -> ModelResult<()> {
+        let attachment = self.clone().try_into_model()?;
+
+        let txn = db.begin().await?;
+
+        self.delete(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            attachment.task_id,
+            AuditEntityEnum::Attachment,
+            attachment.id,
+            Some(&attachment),
+            None,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AttachmentRemoved {
+            task_id: attachment.task_id,
+            attachment_id: attachment.id,
+        });
+
+        Ok(())
+    }
 }
 
 // implement your custom finders, selectors oriented logic here
@@ -0,0 +1,122 @@
+pub use super::_entities::{
+    audit_events::{ActiveModel, Entity, Model},
+    sea_orm_active_enums::{AuditActionEnum, AuditEntityEnum},
+};
+use crate::{common::pagination::ListParams, models::_entities::audit_events};
+use loco_rs::{model::query::PageResponse, prelude::*};
+use sea_orm::{entity::prelude::*, QueryOrder};
+use serde::Serialize;
+use serde_json::Value;
+pub type AuditEvents = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Actor of the changes made by background jobs
+pub const SYSTEM_PID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Fields that change on every save and would only add noise to the diff
+const IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+fn snapshot<T: Serialize>(value: Option<&T>) -> ModelResult<Option<Value>> {
+    value
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| ModelError::Any(e.into()))
+}
+
+/// Keeps only the fields that differ between the two snapshots
+fn diff(before: Option<Value>, after: Option<Value>) -> (Option<Value>, Option<Value>) {
+    match (before, after) {
+        (Some(Value::Object(mut before)), Some(Value::Object(mut after))) => {
+            let changed: Vec<String> = after
+                .iter()
+                .filter(|(key, value)| {
+                    !IGNORED_FIELDS.contains(&key.as_str()) && before.get(*key) != Some(*value)
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            before.retain(|key, _| changed.contains(key));
+            after.retain(|key, _| changed.contains(key));
+
+            (Some(Value::Object(before)), Some(Value::Object(after)))
+        }
+        snapshots => snapshots,
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn list_for_task(
+        db: &DatabaseConnection,
+        task_id: i32,
+        params: &ListParams,
+    ) -> ModelResult<PageResponse<Self>> {
+        let query = audit_events::Entity::find()
+            .filter(audit_events::Column::TaskId.eq(task_id))
+            .order_by(audit_events::Column::Id, params.order());
+
+        model::query::paginate(db, query, None, &params.pagination()).await
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Records a change made to the Task or to something that belongs to it.
+    /// `before` is empty for created entities and `after` for removed ones,
+    /// updates only keep the changed fields
+    pub async fn record<C, T>(
+        db: &C,
+        actor_pid: &str,
+        task_id: i32,
+        entity: AuditEntityEnum,
+        entity_id: i32,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> ModelResult<Model>
+    where
+        C: ConnectionTrait,
+        T: Serialize + Sync,
+    {
+        let action = match (before, after) {
+            (None, _) => AuditActionEnum::Create,
+            (_, None) => AuditActionEnum::Delete,
+            _ => AuditActionEnum::Update,
+        };
+
+        let actor_pid = Uuid::parse_str(actor_pid).map_err(|e| ModelError::Any(e.into()))?;
+        let (before, after) = diff(snapshot(before)?, snapshot(after)?);
+
+        let event = Self {
+            actor_pid: Set(actor_pid),
+            task_id: Set(task_id),
+            entity: Set(entity),
+            entity_id: Set(entity_id),
+            action: Set(action),
+            before: Set(before),
+            after: Set(after),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok(event)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
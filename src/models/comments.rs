@@ -0,0 +1,287 @@
+use std::{collections::BTreeSet, sync::OnceLock};
+
+use crate::{
+    common::{
+        events::{self, TaskEvent},
+        policy::Action,
+    },
+    models::{accesses, tasks, users},
+};
+
+pub use super::_entities::comments::{self, ActiveModel, Entity, Model};
+use chrono::Utc;
+use loco_rs::prelude::*;
+use regex::Regex;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, IntoActiveModel, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+pub type Comments = Entity;
+
+/// Longest comment body in characters
+pub const MAX_BODY_LEN: usize = 10_000;
+
+static MENTION_RE: OnceLock<Regex> = OnceLock::new();
+
+fn mention_re() -> &'static Regex {
+    MENTION_RE.get_or_init(|| Regex::new(r"(?:^|\s)@([\w.\-]+)").expect("valid mention regex"))
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Comment on the Task, or a reply to one of its comments when `parent_id` is given
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateParams {
+    pub body: String,
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateParams {
+    pub body: String,
+}
+
+fn check_body(body: &str) -> ModelResult<String> {
+    let body = body.trim();
+
+    if body.is_empty() {
+        return Err(ModelError::msg("Comment can not be empty"));
+    }
+    if body.chars().count() > MAX_BODY_LEN {
+        return Err(ModelError::msg(&format!(
+            "Comment can not be longer than {MAX_BODY_LEN} characters"
+        )));
+    }
+
+    Ok(body.to_string())
+}
+
+/// Names mentioned in the body as `@name`, lowercased
+#[must_use]
+pub fn mentioned_names(body: &str) -> BTreeSet<String> {
+    mention_re()
+        .captures_iter(body)
+        .map(|captures| captures[1].trim_end_matches('.').to_lowercase())
+        .collect()
+}
+
+/// Whether `@name` refers to the User: their name without spaces, or their
+/// email up to the `@`
+fn is_mentioned(user: &users::Model, names: &BTreeSet<String>) -> bool {
+    let name: String = user
+        .name
+        .split_whitespace()
+        .collect::<String>()
+        .to_lowercase();
+    let local_part = user
+        .email
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    names.contains(&name) || names.contains(&local_part)
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Loads the comment if it belongs to the Task
+    pub async fn load_for_task(
+        db: &DatabaseConnection,
+        task_id: i32,
+        id: i32,
+    ) -> ModelResult<Self> {
+        let comment = Self::load(db, id).await?;
+
+        if comment.task_id == task_id {
+            Ok(comment)
+        } else {
+            Err(ModelError::EntityNotFound)
+        }
+    }
+
+    /// All comments of the Task, oldest first. Replies point to their parent
+    /// through `parent_id`, so clients build the threads themselves
+    pub async fn list_for_task(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let comments = comments::Entity::find()
+            .filter(comments::Column::TaskId.eq(task.id))
+            .order_by_asc(comments::Column::CreatedAt)
+            .order_by_asc(comments::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(comments)
+    }
+
+    #[must_use]
+    pub const fn is_removed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Fails unless the User may edit the comment: its author while they can
+    /// still comment on the Task
+    pub async fn authorize_edit(&self, db: &DatabaseConnection, user_pid: &str) -> Result<()> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        if user.id != self.author_id {
+            return unauthorized("unauthorized");
+        }
+
+        tasks::Model::authorize(db, user_pid, self.task_id, Action::AddComment).await?;
+
+        Ok(())
+    }
+
+    /// Fails unless the User may remove the comment: its author while they can
+    /// still comment on the Task, or a moderator of the Task
+    pub async fn authorize_remove(&self, db: &DatabaseConnection, user_pid: &str) -> Result<()> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let action = if user.id == self.author_id {
+            Action::AddComment
+        } else {
+            Action::ModerateComments
+        };
+
+        tasks::Model::authorize(db, user_pid, self.task_id, action).await?;
+
+        Ok(())
+    }
+
+    /// Users with access to the Task who are mentioned in the comment, without its author
+    pub async fn mentioned_users(&self, db: &DatabaseConnection) -> ModelResult<Vec<users::Model>> {
+        let names = mentioned_names(&self.body);
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let users = accesses::Model::list_users_for_task(db, self.task_id)
+            .await?
+            .into_iter()
+            .filter(|user| user.id != self.author_id && is_mentioned(user, &names))
+            .collect();
+
+        Ok(users)
+    }
+
+    pub async fn add(
+        db: &DatabaseConnection,
+        author_pid: &str,
+        task_id: i32,
+        params: &CreateParams,
+    ) -> ModelResult<Self> {
+        let body = check_body(&params.body)?;
+
+        let author = users::Model::find_by_pid(db, author_pid).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+
+        if let Some(parent_id) = params.parent_id {
+            let parent = match Self::load_for_task(db, task.id, parent_id).await {
+                Ok(parent) => parent,
+                Err(ModelError::EntityNotFound) => {
+                    return Err(ModelError::msg("Replied comment is not on this Task"))
+                }
+                Err(err) => return Err(err),
+            };
+
+            if parent.is_removed() {
+                return Err(ModelError::msg("Can not reply to a removed comment"));
+            }
+        }
+
+        let comment = comments::ActiveModel {
+            body: Set(body),
+            task_id: Set(task.id),
+            author_id: Set(author.id),
+            parent_id: Set(params.parent_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        events::publish(TaskEvent::CommentAdded {
+            task_id: comment.task_id,
+            comment_id: comment.id,
+        });
+
+        Ok(comment)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn edit(
+        db: &DatabaseConnection,
+        comment: Model,
+        params: &UpdateParams,
+    ) -> ModelResult<Model> {
+        if comment.is_removed() {
+            return Err(ModelError::msg("Removed comments can not be edited"));
+        }
+
+        let body = check_body(&params.body)?;
+
+        let mut active_model = comment.into_active_model();
+        active_model.body = Set(body);
+        active_model.edited_at = Set(Some(Utc::now().into()));
+
+        let comment = active_model.update(db).await?;
+
+        events::publish(TaskEvent::CommentUpdated {
+            task_id: comment.task_id,
+            comment_id: comment.id,
+        });
+
+        Ok(comment)
+    }
+
+    /// Removes the comment. Comments with replies stay in the thread without
+    /// their body, so that the replies keep their context
+    pub async fn remove(db: &DatabaseConnection, comment: Model) -> ModelResult<()> {
+        let (task_id, comment_id) = (comment.task_id, comment.id);
+
+        let replies = comments::Entity::find()
+            .filter(comments::Column::ParentId.eq(comment.id))
+            .count(db)
+            .await?;
+
+        if replies == 0 {
+            comment.delete(db).await?;
+        } else {
+            let mut active_model = comment.into_active_model();
+            active_model.body = Set(String::new());
+            active_model.deleted_at = Set(Some(Utc::now().into()));
+            active_model.update(db).await?;
+        }
+
+        events::publish(TaskEvent::CommentRemoved {
+            task_id,
+            comment_id,
+        });
+
+        Ok(())
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
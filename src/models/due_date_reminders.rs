@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set};
+
+pub use super::_entities::due_date_reminders::{self, ActiveModel, Entity, Model};
+use loco_rs::model::ModelResult;
+pub type DueDateReminders = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Records that the reminder for the given due date is about to be sent to the user.
+    /// Returns `false` when the reminder was already sent before
+    ///
+    /// # Errors
+    ///
+    /// When has DB query error
+    pub async fn claim(
+        db: &DatabaseConnection,
+        attachment_id: i32,
+        user_id: i32,
+        due_at: DateTime<Utc>,
+    ) -> ModelResult<bool> {
+        let inserted = Entity::insert(ActiveModel {
+            attachment_id: Set(attachment_id),
+            user_id: Set(user_id),
+            due_at: Set(due_at.into()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                due_date_reminders::Column::AttachmentId,
+                due_date_reminders::Column::UserId,
+                due_date_reminders::Column::DueAt,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec(db)
+        .await;
+
+        match inserted {
+            Ok(_) => Ok(true),
+            Err(DbErr::RecordNotInserted) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Forgets a claimed reminder so it is sent again on the next run
+    ///
+    /// # Errors
+    ///
+    /// When has DB query error
+    pub async fn release(
+        db: &DatabaseConnection,
+        attachment_id: i32,
+        user_id: i32,
+        due_at: DateTime<Utc>,
+    ) -> ModelResult<()> {
+        Entity::delete_many()
+            .filter(due_date_reminders::Column::AttachmentId.eq(attachment_id))
+            .filter(due_date_reminders::Column::UserId.eq(user_id))
+            .filter(due_date_reminders::Column::DueAt.eq(due_at))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
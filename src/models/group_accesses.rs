@@ -0,0 +1,201 @@
+use crate::{
+    common::events::{self, TaskEvent},
+    models::{
+        _entities::{group_accesses, group_members},
+        audit_events::{self, AuditEntityEnum},
+        groups,
+        tasks::{self, AccessLevelEnum},
+    },
+};
+
+pub use super::_entities::group_accesses::{ActiveModel, Entity, Model};
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, IntoActiveModel, QuerySelect, QueryTrait,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+pub type GroupAccesses = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct GrantParams {
+    pub group_id: i32,
+    pub accesslevel: AccessLevelEnum,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct DenyParams {
+    pub group_id: i32,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn find_by_group(
+        db: &DatabaseConnection,
+        task_id: i32,
+        group_id: i32,
+    ) -> ModelResult<Self> {
+        group_accesses::Entity::find()
+            .filter(group_accesses::Column::TaskId.eq(task_id))
+            .filter(group_accesses::Column::GroupId.eq(group_id))
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    pub async fn list_for_group(db: &DatabaseConnection, group_id: i32) -> ModelResult<Vec<Self>> {
+        let accesses = group_accesses::Entity::find()
+            .filter(group_accesses::Column::GroupId.eq(group_id))
+            .all(db)
+            .await?;
+
+        Ok(accesses)
+    }
+
+    pub async fn list_for_task(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let accesses = group_accesses::Entity::find()
+            .filter(group_accesses::Column::TaskId.eq(task.id))
+            .all(db)
+            .await?;
+
+        Ok(accesses)
+    }
+
+    /// Grants on the Tasks given to any of the Groups the user is a member of
+    pub async fn list_for_member(
+        db: &DatabaseConnection,
+        user_id: i32,
+        task_ids: impl IntoIterator<Item = i32>,
+    ) -> ModelResult<Vec<Self>> {
+        let accesses = group_accesses::Entity::find()
+            .filter(group_accesses::Column::TaskId.is_in(task_ids))
+            .filter(
+                group_accesses::Column::GroupId.in_subquery(
+                    group_members::Entity::find()
+                        .select_only()
+                        .column(group_members::Column::GroupId)
+                        .filter(group_members::Column::UserId.eq(user_id))
+                        .into_query(),
+                ),
+            )
+            .all(db)
+            .await?;
+
+        Ok(accesses)
+    }
+
+    /// Grants the access level to the Group, or changes the one it already has
+    pub async fn grant_access(
+        db: &DatabaseConnection,
+        actor_pid: &str,
+        task_id: i32,
+        params: &GrantParams,
+    ) -> ModelResult<Self> {
+        let group = groups::Model::load(db, params.group_id).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let before = group_accesses::Entity::find()
+            .filter(group_accesses::Column::TaskId.eq(task.id))
+            .filter(group_accesses::Column::GroupId.eq(group.id))
+            .one(db)
+            .await?;
+
+        let txn = db.begin().await?;
+
+        let access = match &before {
+            Some(before) => {
+                let mut active_model = before.clone().into_active_model();
+                active_model.accesslevel = Set(params.accesslevel);
+                active_model.update(&txn).await?
+            }
+            None => {
+                group_accesses::ActiveModel {
+                    group_id: Set(group.id),
+                    task_id: Set(task.id),
+                    accesslevel: Set(params.accesslevel),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?
+            }
+        };
+
+        audit_events::ActiveModel::record(
+            &txn,
+            actor_pid,
+            task.id,
+            AuditEntityEnum::GroupAccess,
+            access.id,
+            before.as_ref(),
+            Some(&access),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: None,
+        });
+
+        Ok(access)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn deny_access(
+        db: &DatabaseConnection,
+        actor_pid: &str,
+        task_id: i32,
+        params: &DenyParams,
+    ) -> ModelResult<()> {
+        let access = group_accesses::Model::find_by_group(db, task_id, params.group_id).await?;
+
+        let txn = db.begin().await?;
+
+        access.clone().into_active_model().delete(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            actor_pid,
+            task_id,
+            AuditEntityEnum::GroupAccess,
+            access.id,
+            Some(&access),
+            None,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::AccessChanged {
+            task_id: access.task_id,
+            user_id: None,
+        });
+
+        Ok(())
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
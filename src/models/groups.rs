@@ -0,0 +1,225 @@
+use crate::{
+    common::events::{self, TaskEvent},
+    models::{_entities::group_members, group_accesses, users},
+};
+
+pub use super::_entities::groups::{self, ActiveModel, Entity, Model};
+use loco_rs::prelude::*;
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, Condition, QuerySelect, QueryTrait, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+pub type Groups = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateParams {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AddMembersParams {
+    pub emails: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RemoveMemberParams {
+    pub pid: String,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Loads the Group if the user owns it
+    pub async fn load_owned(db: &DatabaseConnection, user_pid: &str, id: i32) -> Result<Self> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let group = Self::load(db, id).await?;
+
+        if group.owner_id == user.id {
+            Ok(group)
+        } else {
+            unauthorized("unauthorized")
+        }
+    }
+
+    /// Loads the Group if the user owns it or is one of its members
+    pub async fn load_visible(db: &DatabaseConnection, user_pid: &str, id: i32) -> Result<Self> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let group = Self::load(db, id).await?;
+
+        if group.owner_id == user.id || group.has_member(db, user.id).await? {
+            Ok(group)
+        } else {
+            unauthorized("unauthorized")
+        }
+    }
+
+    pub async fn has_member(&self, db: &DatabaseConnection, user_id: i32) -> ModelResult<bool> {
+        let member = group_members::Entity::find()
+            .filter(group_members::Column::GroupId.eq(self.id))
+            .filter(group_members::Column::UserId.eq(user_id))
+            .one(db)
+            .await?;
+
+        Ok(member.is_some())
+    }
+
+    pub async fn members(&self, db: &DatabaseConnection) -> ModelResult<Vec<users::Model>> {
+        let members = users::Entity::find()
+            .inner_join(group_members::Entity)
+            .filter(group_members::Column::GroupId.eq(self.id))
+            .all(db)
+            .await?;
+
+        Ok(members)
+    }
+
+    /// Groups the user owns or belongs to
+    pub async fn list_for_user(db: &DatabaseConnection, user_pid: &str) -> ModelResult<Vec<Self>> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        let groups = groups::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(groups::Column::OwnerId.eq(user.id))
+                    .add(
+                        groups::Column::Id.in_subquery(
+                            group_members::Entity::find()
+                                .select_only()
+                                .column(group_members::Column::GroupId)
+                                .filter(group_members::Column::UserId.eq(user.id))
+                                .into_query(),
+                        ),
+                    ),
+            )
+            .all(db)
+            .await?;
+
+        Ok(groups)
+    }
+
+    pub async fn add(
+        db: &DatabaseConnection,
+        owner_pid: &str,
+        params: &CreateParams,
+    ) -> ModelResult<Self> {
+        let owner = users::Model::find_by_pid(db, owner_pid).await?;
+
+        let group = groups::ActiveModel {
+            name: Set(params.name.trim().to_string()),
+            owner_id: Set(owner.id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok(group)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Adds every user behind the emails to the Group, users already in it are skipped
+    pub async fn add_members(
+        db: &DatabaseConnection,
+        group: &Model,
+        params: &AddMembersParams,
+    ) -> ModelResult<Vec<users::Model>> {
+        let mut users = Vec::with_capacity(params.emails.len());
+        for email in &params.emails {
+            users.push(users::Model::find_by_email(db, email.trim()).await?);
+        }
+
+        let txn = db.begin().await?;
+
+        for user in &users {
+            let member = group_members::Entity::find()
+                .filter(group_members::Column::GroupId.eq(group.id))
+                .filter(group_members::Column::UserId.eq(user.id))
+                .one(&txn)
+                .await?;
+
+            if member.is_none() {
+                group_members::ActiveModel {
+                    group_id: Set(group.id),
+                    user_id: Set(user.id),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok(users)
+    }
+
+    pub async fn remove_member(
+        db: &DatabaseConnection,
+        group: &Model,
+        params: &RemoveMemberParams,
+    ) -> ModelResult<()> {
+        let user = users::Model::find_by_pid(db, &params.pid).await?;
+
+        let result = group_members::Entity::delete_many()
+            .filter(group_members::Column::GroupId.eq(group.id))
+            .filter(group_members::Column::UserId.eq(user.id))
+            .exec(db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(ModelError::EntityNotFound);
+        }
+
+        for access in group_accesses::Model::list_for_group(db, group.id).await? {
+            events::publish(TaskEvent::AccessChanged {
+                task_id: access.task_id,
+                user_id: Some(user.id),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes the Group, its members lose the Accesses granted to it
+    pub async fn remove(db: &DatabaseConnection, group: Model) -> ModelResult<()> {
+        let accesses = group_accesses::Model::list_for_group(db, group.id).await?;
+
+        group.delete(db).await?;
+
+        for access in accesses {
+            events::publish(TaskEvent::AccessChanged {
+                task_id: access.task_id,
+                user_id: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,201 @@
+use crate::models::{
+    _entities::{accesses, invitations},
+    accesses::{check_window, GrantParams},
+    audit_events::{self, AuditEntityEnum},
+    tasks,
+};
+
+pub use super::_entities::invitations::{ActiveModel, Entity, Model};
+use super::_entities::users;
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::{entity::prelude::*, ActiveValue::Set, IntoActiveModel};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+pub type Invitations = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RevokeParams {
+    pub email: String,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn find_by_email(
+        db: &DatabaseConnection,
+        task_id: i32,
+        email: &str,
+    ) -> ModelResult<Self> {
+        invitations::Entity::find()
+            .filter(invitations::Column::TaskId.eq(task_id))
+            .filter(invitations::Column::Email.eq(email.trim()))
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    pub async fn list_for_task(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let invitations = invitations::Entity::find()
+            .filter(invitations::Column::TaskId.eq(task.id))
+            .all(db)
+            .await?;
+
+        Ok(invitations)
+    }
+
+    /// Invites the email to the Task. Inviting it again replaces the access level,
+    /// the window and the token of the pending invitation
+    pub async fn invite(
+        db: &DatabaseConnection,
+        inviter_pid: &str,
+        task_id: i32,
+        params: &GrantParams,
+    ) -> ModelResult<Self> {
+        check_window(params.starts_at, params.expires_at)?;
+
+        let inviter = users::Model::find_by_pid(db, inviter_pid).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+        let email = params.email.trim();
+
+        let pending = invitations::Entity::find()
+            .filter(invitations::Column::TaskId.eq(task.id))
+            .filter(invitations::Column::Email.eq(email))
+            .one(db)
+            .await?;
+
+        let invitation = match pending {
+            Some(pending) => {
+                let mut active_model = pending.into_active_model();
+                active_model.accesslevel = Set(params.accesslevel);
+                active_model.starts_at = Set(params.starts_at);
+                active_model.expires_at = Set(params.expires_at);
+                active_model.token = Set(Uuid::new_v4().to_string());
+                active_model.invited_by_id = Set(inviter.id);
+                active_model.update(db).await?
+            }
+            None => {
+                invitations::ActiveModel {
+                    email: Set(email.to_string()),
+                    token: Set(Uuid::new_v4().to_string()),
+                    accesslevel: Set(params.accesslevel),
+                    starts_at: Set(params.starts_at),
+                    expires_at: Set(params.expires_at),
+                    task_id: Set(task.id),
+                    invited_by_id: Set(inviter.id),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?
+            }
+        };
+
+        Ok(invitation)
+    }
+
+    /// Turns the pending invitations of the user's email into `accesses` rows.
+    /// Runs inside the transaction that verifies the email, or creates a user
+    /// whose email the OAuth provider verified
+    pub async fn accept_for<C>(db: &C, user: &users::Model) -> ModelResult<Vec<accesses::Model>>
+    where
+        C: ConnectionTrait,
+    {
+        let pending = invitations::Entity::find()
+            .filter(invitations::Column::Email.eq(&user.email))
+            .find_also_related(users::Entity)
+            .all(db)
+            .await?;
+
+        let now = chrono::Utc::now();
+
+        let mut granted = Vec::with_capacity(pending.len());
+        for (invitation, inviter) in pending {
+            if invitation
+                .expires_at
+                .is_some_and(|expires_at| expires_at <= now)
+            {
+                invitation.into_active_model().delete(db).await?;
+                continue;
+            }
+
+            let existing = accesses::Entity::find()
+                .filter(accesses::Column::UserId.eq(user.id))
+                .filter(accesses::Column::TaskId.eq(invitation.task_id))
+                .one(db)
+                .await?;
+
+            if existing.is_none() {
+                let access = accesses::ActiveModel {
+                    user_id: Set(user.id),
+                    task_id: Set(invitation.task_id),
+                    accesslevel: Set(invitation.accesslevel),
+                    starts_at: Set(invitation.starts_at),
+                    expires_at: Set(invitation.expires_at),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?;
+
+                let actor_pid = inviter.map_or_else(|| user.pid, |inviter| inviter.pid);
+
+                audit_events::ActiveModel::record(
+                    db,
+                    &actor_pid.to_string(),
+                    access.task_id,
+                    AuditEntityEnum::Access,
+                    access.id,
+                    None,
+                    Some(&access),
+                )
+                .await?;
+
+                granted.push(access);
+            }
+
+            invitation.into_active_model().delete(db).await?;
+        }
+
+        Ok(granted)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn revoke(
+        db: &DatabaseConnection,
+        task_id: i32,
+        params: &RevokeParams,
+    ) -> ModelResult<()> {
+        let result = invitations::Entity::delete_many()
+            .filter(invitations::Column::TaskId.eq(task_id))
+            .filter(invitations::Column::Email.eq(params.email.trim()))
+            .exec(db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(ModelError::EntityNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
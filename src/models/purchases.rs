@@ -0,0 +1,177 @@
+pub use super::_entities::{
+    purchases::{ActiveModel, Entity, Model},
+    sea_orm_active_enums::PurchaseStatusEnum,
+};
+use crate::models::{
+    _entities::{accesses, purchases},
+    audit_events::{self, AuditEntityEnum},
+    tasks::{self, AccessLevelEnum, TaskVisibilityEnum},
+    users,
+};
+use loco_rs::prelude::*;
+use sea_orm::{entity::prelude::*, IntoActiveModel, TransactionTrait};
+pub type Purchases = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn find_by_reference(db: &DatabaseConnection, reference: &str) -> ModelResult<Self> {
+        purchases::Entity::find()
+            .filter(purchases::Column::ProviderRef.eq(reference))
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Starts a purchase of the Paid Task at its current price
+    pub async fn start(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task_id: i32,
+        currency: &str,
+    ) -> ModelResult<(Self, tasks::Model)> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+
+        if task.visibility != TaskVisibilityEnum::Paid {
+            return Err(ModelError::msg("Only Paid Tasks can be purchased"));
+        }
+
+        let Some(price_cents) = task.price_cents.filter(|price| *price > 0) else {
+            return Err(ModelError::msg("Task has no price"));
+        };
+
+        if tasks::Model::effective_access(db, user.id, task.id)
+            .await?
+            .is_some()
+        {
+            return Err(ModelError::EntityAlreadyExists);
+        }
+
+        let purchase = ActiveModel {
+            task_id: Set(task.id),
+            user_id: Set(user.id),
+            amount_cents: Set(price_cents),
+            currency: Set(currency.to_string()),
+            status: Set(PurchaseStatusEnum::Pending),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok((purchase, task))
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn set_reference(
+        mut self,
+        db: &DatabaseConnection,
+        reference: String,
+    ) -> ModelResult<Model> {
+        self.provider_ref = Set(Some(reference));
+
+        let purchase = self.update(db).await?;
+
+        Ok(purchase)
+    }
+
+    /// Marks the purchase as paid and gives the buyer `View` access to the Task.
+    /// Repeated webhooks for the same purchase change nothing
+    pub async fn complete(mut self, db: &DatabaseConnection) -> ModelResult<Model> {
+        if *self.status.as_ref() == PurchaseStatusEnum::Paid {
+            return Ok(self.try_into_model()?);
+        }
+
+        let user = users::Entity::find_by_id(*self.user_id.as_ref())
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)?;
+        let task_id = *self.task_id.as_ref();
+
+        let txn = db.begin().await?;
+
+        self.status = Set(PurchaseStatusEnum::Paid);
+        let purchase = self.update(&txn).await?;
+
+        let own_access = accesses::Entity::find()
+            .filter(accesses::Column::UserId.eq(user.id))
+            .filter(accesses::Column::TaskId.eq(task_id))
+            .one(&txn)
+            .await?;
+        let active = own_access.as_ref().is_some_and(|access| {
+            let now = chrono::Utc::now();
+            access.starts_at.is_none_or(|starts_at| starts_at <= now)
+                && access.expires_at.is_none_or(|expires_at| expires_at > now)
+        });
+
+        if !active {
+            // a User has one Access per Task, one outside its window is opened up
+            let access = match &own_access {
+                Some(before) => {
+                    let mut active_model = before.clone().into_active_model();
+                    active_model.accesslevel = Set(AccessLevelEnum::View);
+                    active_model.starts_at = Set(None);
+                    active_model.expires_at = Set(None);
+                    active_model.update(&txn).await?
+                }
+                None => {
+                    accesses::ActiveModel {
+                        user_id: Set(user.id),
+                        task_id: Set(task_id),
+                        accesslevel: Set(AccessLevelEnum::View),
+                        ..Default::default()
+                    }
+                    .insert(&txn)
+                    .await?
+                }
+            };
+
+            audit_events::ActiveModel::record(
+                &txn,
+                &user.pid.to_string(),
+                task_id,
+                AuditEntityEnum::Access,
+                access.id,
+                own_access.as_ref(),
+                Some(&access),
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(purchase)
+    }
+
+    pub async fn fail(mut self, db: &DatabaseConnection) -> ModelResult<Model> {
+        if *self.status.as_ref() != PurchaseStatusEnum::Pending {
+            return Ok(self.try_into_model()?);
+        }
+
+        self.status = Set(PurchaseStatusEnum::Failed);
+
+        let purchase = self.update(db).await?;
+
+        Ok(purchase)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+pub use super::_entities::solution_files::{ActiveModel, Entity, Model};
+use sea_orm::entity::prelude::*;
+pub type SolutionFiles = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Where the content of the file is kept in the storage
+    #[must_use]
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from("solutions")
+            .join(self.solution_id.to_string())
+            .join(&self.file_name)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
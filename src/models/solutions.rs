@@ -0,0 +1,164 @@
+pub use super::_entities::{
+    sea_orm_active_enums::SolutionStatusEnum,
+    solutions::{ActiveModel, Entity, Model},
+};
+use std::collections::HashSet;
+
+use crate::models::{_entities::solutions, solution_files, tasks, users};
+use axum::body::Bytes;
+use axum_typed_multipart::{FieldData, TryFromMultipart};
+use loco_rs::prelude::*;
+use sea_orm::{entity::prelude::*, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+pub type Solutions = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[derive(Debug, ToSchema)]
+pub struct SolutionAddParams {
+    pub body: String,
+    pub file_names: Vec<String>,
+}
+
+#[derive(TryFromMultipart, ToSchema)]
+pub struct SolutionAddForm {
+    pub body: String,
+
+    #[form_data(limit = "10MiB")]
+    #[schema(value_type = Vec<Vec<u8>>)]
+    pub files: Vec<FieldData<Bytes>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ReviewParams {
+    pub status: SolutionStatusEnum,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    pub async fn files(&self, db: &DatabaseConnection) -> ModelResult<Vec<solution_files::Model>> {
+        let files = self.find_related(solution_files::Entity).all(db).await?;
+
+        Ok(files)
+    }
+
+    pub async fn find_file(
+        &self,
+        db: &DatabaseConnection,
+        file_name: &str,
+    ) -> ModelResult<solution_files::Model> {
+        self.find_related(solution_files::Entity)
+            .filter(solution_files::Column::FileName.eq(file_name))
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    pub async fn list_for_task(
+        db: &DatabaseConnection,
+        task_id: i32,
+    ) -> ModelResult<Vec<(Self, Vec<solution_files::Model>)>> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let solutions = solutions::Entity::find()
+            .filter(solutions::Column::TaskId.eq(task.id))
+            .find_with_related(solution_files::Entity)
+            .all(db)
+            .await?;
+
+        Ok(solutions)
+    }
+
+    pub async fn add_solution(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task_id: i32,
+        params: SolutionAddParams,
+    ) -> ModelResult<(Self, Vec<solution_files::Model>)> {
+        let mut seen = HashSet::with_capacity(params.file_names.len());
+        for file_name in &params.file_names {
+            tasks::check_file_name(file_name)?;
+            if !seen.insert(file_name.as_str()) {
+                return Err(ModelError::msg(&format!(
+                    "File `{file_name}` is submitted more than once"
+                )));
+            }
+        }
+
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let txn = db.begin().await?;
+
+        let solution = ActiveModel {
+            task_id: Set(task.id),
+            author_id: Set(user.id),
+            body: Set(params.body),
+            status: Set(SolutionStatusEnum::Pending),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut files = Vec::with_capacity(params.file_names.len());
+        for file_name in params.file_names {
+            let file = solution_files::ActiveModel {
+                solution_id: Set(solution.id),
+                file_name: Set(file_name),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            files.push(file);
+        }
+
+        txn.commit().await?;
+
+        Ok((solution, files))
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn review(
+        mut self,
+        db: &DatabaseConnection,
+        params: ReviewParams,
+    ) -> ModelResult<Model> {
+        if params.status == SolutionStatusEnum::Pending {
+            return Err(ModelError::msg("Solution can only be accepted or rejected"));
+        }
+
+        self.status = Set(params.status);
+
+        let solution = self.update(db).await?;
+
+        Ok(solution)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
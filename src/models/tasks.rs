@@ -1,16 +1,36 @@
-use crate::models::_entities::accesses;
+use std::collections::BTreeSet;
+
+use crate::{
+    common::{
+        archive::{self, AccessEntry, AttachmentEntry, Manifest, TaskEntry},
+        events::{self, TaskEvent},
+        pagination::{ListParams, SortBy},
+        policy::Action,
+    },
+    models::{
+        _entities::{accesses, attachments, group_accesses, group_members},
+        attachments::AttachmentData,
+        audit_events::{self, AuditEntityEnum},
+    },
+};
 
 pub use super::_entities::{
-    sea_orm_active_enums::{AccessLevelEnum, TaskVisibilityEnum},
+    sea_orm_active_enums::{
+        AccessLevelEnum, AttachmentTypeEnum, TaskStatusEnum, TaskVisibilityEnum,
+    },
     tasks::{self, ActiveModel, Entity, Model},
     users,
 };
 
-use loco_rs::prelude::*;
-use migration::extension::postgres::PgExpr;
-use sea_orm::{entity::prelude::*, Condition, TransactionTrait};
+use axum::body::Bytes;
+use axum_typed_multipart::{FieldData, TryFromMultipart};
+use loco_rs::{model::query::PageResponse, prelude::*};
+use sea_orm::{
+    entity::prelude::*, Condition, DbBackend, FromQueryResult, Order, QueryOrder, QueryResult,
+    QuerySelect, QueryTrait, Select, TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 pub type Tasks = Entity;
 
 #[derive(Debug, Validate, Deserialize)]
@@ -45,17 +65,427 @@ impl ActiveModelBehavior for ActiveModel {
 pub struct CreateParams {
     pub name: String,
     pub visibility: Option<TaskVisibilityEnum>,
+    /// Price of a Paid Task, in the smallest currency unit
+    #[serde(default)]
+    pub price_cents: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateParams {
     pub name: Option<String>,
     pub visibility: Option<TaskVisibilityEnum>,
+    /// Price of a Paid Task, in the smallest currency unit
+    #[serde(default)]
+    pub price_cents: Option<i32>,
+}
+
+fn check_price(price_cents: Option<i32>) -> ModelResult<()> {
+    if price_cents.is_some_and(|price| price < 0) {
+        return Err(ModelError::msg("Price can not be negative"));
+    }
+
+    Ok(())
+}
+
+/// File names end up in storage paths, so they must not leave their directory
+pub(crate) fn check_file_name(name: &str) -> ModelResult<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(ModelError::msg(&format!("Invalid file name `{name}`")));
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct SearchParams {
-    pub name: String,
+    /// Words to look for in the Task name and its text attachments
+    #[serde(alias = "name")]
+    pub query: String,
+}
+
+/// Task found by a search, with its relevance and the matching text
+#[derive(Debug)]
+pub struct SearchHit {
+    pub task: Model,
+    pub rank: f32,
+    /// HTML-escaped matching text with the found words wrapped in `<mark>` tags
+    pub snippet: String,
+}
+
+impl FromQueryResult for SearchHit {
+    fn from_query_result(res: &QueryResult, pre: &str) -> std::result::Result<Self, DbErr> {
+        Ok(Self {
+            task: Model::from_query_result(res, pre)?,
+            rank: res.try_get(pre, "rank")?,
+            snippet: res.try_get(pre, "snippet")?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TransitionParams {
+    pub status: TaskStatusEnum,
+}
+
+/// Action a User needs to be allowed to move a Task from one status to another,
+/// `None` when the transition is not allowed at all
+#[must_use]
+pub const fn allowed_transition(from: TaskStatusEnum, to: TaskStatusEnum) -> Option<Action> {
+    use TaskStatusEnum::{Archived, Done, Draft, InProgress, Open, Review};
+
+    match (from, to) {
+        (Open, InProgress) | (InProgress, Review) => Some(Action::StartWork),
+        (Draft | InProgress | Review | Done, Open) | (Review, InProgress | Done) => {
+            Some(Action::ReviewWork)
+        }
+        (Draft | Open | Done, Archived) | (Archived, Open) => Some(Action::ArchiveTask),
+        _ => None,
+    }
+}
+
+/// Moves the Task under another one, or makes it a root when `parent_id` is empty
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct MoveParams {
+    pub parent_id: Option<i32>,
+}
+
+/// Hands the Task over to the User with the email
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TransferParams {
+    pub email: String,
+    /// The recipient has to accept the Task before it changes hands
+    #[serde(default)]
+    pub require_confirmation: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct TreeParams {
+    /// Levels of subtasks to include, at most 10
+    #[serde(default = "default_tree_depth")]
+    pub depth: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    /// Also export who has Access to the Task, by their email
+    #[serde(default)]
+    pub include_accesses: bool,
+}
+
+#[derive(TryFromMultipart, ToSchema)]
+pub struct ImportForm {
+    /// ZIP archive written by the export
+    #[form_data(limit = "10MiB")]
+    #[schema(value_type = Vec<u8>)]
+    pub archive: FieldData<Bytes>,
+}
+
+const fn default_tree_depth() -> u32 {
+    3
+}
+
+/// Task with its subtasks
+#[derive(Debug)]
+pub struct TaskTree {
+    pub task: Model,
+    pub children: Vec<TaskTree>,
+}
+
+/// Copy of a Task with the File Attachments whose content still has to be copied,
+/// each one paired with the Attachment it was copied from
+#[derive(Debug)]
+pub struct Fork {
+    pub task: Model,
+    pub files: Vec<(attachments::Model, attachments::Model)>,
+}
+
+/// Manifest of an exported Task with its File Attachments, each one paired with the
+/// archive entry its content goes to
+#[derive(Debug)]
+pub struct Export {
+    pub manifest: Manifest,
+    pub files: Vec<(String, attachments::Model)>,
+}
+
+/// Task recreated from an archive with its File Attachments, each one paired with the
+/// archive entry holding its content, and the emails of the archived Accesses, which
+/// are not granted
+#[derive(Debug)]
+pub struct Import {
+    pub task: Model,
+    pub files: Vec<(String, attachments::Model)>,
+    pub skipped_emails: Vec<String>,
+}
+
+pub const MAX_TREE_DEPTH: u32 = 10;
+/// Deepest allowed nesting of subtasks, also guards ancestor walks
+pub const MAX_NESTING: usize = 32;
+
+/// Attachment types whose text is searched along with the Task name by the `LIKE`
+/// fallback. Postgres reads the same types in the `task_attachment_text` function
+const SEARCHABLE_ATTACHMENTS: [AttachmentTypeEnum; 4] = [
+    AttachmentTypeEnum::Description,
+    AttachmentTypeEnum::Text,
+    AttachmentTypeEnum::Tip,
+    AttachmentTypeEnum::Hint,
+];
+const SNIPPET_RADIUS: usize = 80;
+/// Private use characters that `ts_headline` puts around found words, they are
+/// turned into `<mark>` tags once the snippet is escaped
+const HEADLINE_START: char = '\u{E000}';
+const HEADLINE_STOP: char = '\u{E001}';
+
+/// Filters of the Task list endpoints
+#[derive(Debug, Deserialize, Serialize, Default, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct TaskFilter {
+    pub visibility: Option<TaskVisibilityEnum>,
+    /// Only applies to lists of a particular user's Tasks
+    pub accesslevel: Option<AccessLevelEnum>,
+}
+
+impl TaskFilter {
+    fn apply(&self, mut query: Select<Entity>, with_access: bool) -> Select<Entity> {
+        if let Some(visibility) = self.visibility {
+            query = query.filter(tasks::Column::Visibility.eq(visibility));
+        }
+
+        if let (Some(accesslevel), true) = (self.accesslevel, with_access) {
+            query = query.filter(accesses::Column::Accesslevel.eq(accesslevel));
+        }
+
+        query
+    }
+}
+
+fn sort(query: Select<Entity>, params: &ListParams) -> Select<Entity> {
+    let column = match params.sort_by {
+        SortBy::CreatedAt => tasks::Column::CreatedAt,
+        SortBy::UpdatedAt => tasks::Column::UpdatedAt,
+        SortBy::Name => tasks::Column::Name,
+    };
+
+    query
+        .order_by(column, params.order())
+        .order_by(tasks::Column::Id, params.order())
+}
+
+/// Tasks the User has an active Access to, directly or through one of their Groups
+fn granted_to(user_id: i32) -> Condition {
+    Condition::any()
+        .add(
+            tasks::Column::Id.in_subquery(
+                accesses::Entity::find()
+                    .select_only()
+                    .column(accesses::Column::TaskId)
+                    .filter(accesses::Column::UserId.eq(user_id))
+                    .filter(accesses::Entity::active_at(chrono::Utc::now()))
+                    .into_query(),
+            ),
+        )
+        .add(
+            tasks::Column::Id.in_subquery(
+                group_accesses::Entity::find()
+                    .select_only()
+                    .column(group_accesses::Column::TaskId)
+                    .filter(
+                        group_accesses::Column::GroupId.in_subquery(
+                            group_members::Entity::find()
+                                .select_only()
+                                .column(group_members::Column::GroupId)
+                                .filter(group_members::Column::UserId.eq(user_id))
+                                .into_query(),
+                        ),
+                    )
+                    .into_query(),
+            ),
+        )
+}
+
+async fn paginate(
+    db: &DatabaseConnection,
+    query: Select<Entity>,
+    params: &ListParams,
+) -> ModelResult<PageResponse<Model>> {
+    model::query::paginate(db, sort(query, params), None, &params.pagination()).await
+}
+
+/// Ranked full-text search, backed by `tasks.search_vector` on Postgres
+/// and by plain `LIKE` matching elsewhere
+async fn search(
+    db: &DatabaseConnection,
+    params: &SearchParams,
+    visible: Condition,
+    list: &ListParams,
+    filter: &TaskFilter,
+) -> ModelResult<PageResponse<SearchHit>> {
+    let backend = db.get_database_backend();
+    let query = filter.apply(tasks::Entity::find().filter(visible), false);
+
+    let query = if backend == DbBackend::Postgres {
+        let ts_query = "websearch_to_tsquery('english', $1)";
+
+        query
+            .filter(Expr::cust_with_values(
+                format!("tasks.search_vector @@ {ts_query}"),
+                [params.query.clone()],
+            ))
+            .expr_as(
+                Expr::cust_with_values(
+                    format!("ts_rank(tasks.search_vector, {ts_query})"),
+                    [params.query.clone()],
+                ),
+                "rank",
+            )
+            .expr_as(
+                Expr::cust_with_values(
+                    format!(
+                        "ts_headline('english', \
+                        concat_ws(' ', tasks.name, task_attachment_text(tasks.id)), {ts_query}, \
+                        'StartSel={HEADLINE_START}, StopSel={HEADLINE_STOP}, MaxFragments=2')"
+                    ),
+                    [params.query.clone()],
+                ),
+                "snippet",
+            )
+    } else {
+        let pattern = like_pattern(&params.query);
+        // $1 is the pattern, the searchable types follow
+        let types = (2..=SEARCHABLE_ATTACHMENTS.len() + 1)
+            .map(|index| format!("${index}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let values = || {
+            std::iter::once(pattern.clone())
+                .chain(SEARCHABLE_ATTACHMENTS.iter().map(ActiveEnum::to_value))
+                .collect::<Vec<_>>()
+        };
+        let attachment_text = format!(
+            "SELECT a.data FROM attachments a WHERE a.task_id = tasks.id \
+            AND a.attachment_type IN ({types}) AND a.data LIKE $1 ESCAPE '\\'"
+        );
+
+        query
+            .filter(Expr::cust_with_values(
+                format!("(tasks.name LIKE $1 ESCAPE '\\' OR EXISTS ({attachment_text}))"),
+                values(),
+            ))
+            .expr_as(
+                Expr::cust_with_values(
+                    "CASE WHEN tasks.name LIKE $1 ESCAPE '\\' THEN 1.0 ELSE 0.5 END",
+                    [pattern.clone()],
+                ),
+                "rank",
+            )
+            .expr_as(
+                Expr::cust_with_values(
+                    format!("COALESCE(({attachment_text} LIMIT 1), tasks.name)"),
+                    values(),
+                ),
+                "snippet",
+            )
+    };
+
+    let pagination = list.pagination();
+    let paginator = sort(query.order_by(Expr::cust("rank"), Order::Desc), list)
+        .into_model::<SearchHit>()
+        .paginate(db, pagination.page_size);
+
+    let totals = paginator.num_items_and_pages().await?;
+    let mut page = paginator.fetch_page(pagination.page - 1).await?;
+
+    for hit in &mut page {
+        hit.snippet = if backend == DbBackend::Postgres {
+            mark_headline(&hit.snippet)
+        } else {
+            highlight(&hit.snippet, &params.query)
+        };
+    }
+
+    Ok(PageResponse {
+        page,
+        total_items: totals.number_of_items,
+        total_pages: totals.number_of_pages,
+    })
+}
+
+/// `LIKE` pattern that finds the query anywhere in a text, the wildcards `%` and `_`
+/// of the query are matched literally, to be used with `ESCAPE '\'`
+#[must_use]
+pub fn like_pattern(query: &str) -> String {
+    let mut pattern = String::with_capacity(query.len() + 2);
+    pattern.push('%');
+
+    for c in query.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+
+    pattern.push('%');
+    pattern
+}
+
+/// Escapes text for use in HTML, snippets are shown as HTML because of their `<mark>` tags
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escapes a `ts_headline` of Postgres, then marks the found words
+#[must_use]
+pub fn mark_headline(headline: &str) -> String {
+    escape_html(headline)
+        .replace(HEADLINE_START, "<mark>")
+        .replace(HEADLINE_STOP, "</mark>")
+}
+
+/// Cuts `text` around the first occurrence of `term`, escapes it and marks the term,
+/// mimicking what `ts_headline` returns on Postgres
+#[must_use]
+pub fn highlight(text: &str, term: &str) -> String {
+    let lowered = text.to_lowercase();
+
+    // lowercasing may change byte offsets of non-ASCII text
+    let found = (lowered.len() == text.len())
+        .then(|| lowered.find(&term.to_lowercase()))
+        .flatten();
+
+    let Some(start) = found.filter(|_| !term.is_empty()) else {
+        return escape_html(&text.chars().take(SNIPPET_RADIUS * 2).collect::<String>());
+    };
+    let end = start + term.len();
+
+    let mut from = start.saturating_sub(SNIPPET_RADIUS);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (end + SNIPPET_RADIUS).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+
+    format!(
+        "{}<mark>{}</mark>{}",
+        escape_html(&text[from..start]),
+        escape_html(&text[start..end]),
+        escape_html(&text[end..to])
+    )
 }
 
 // implement your read-oriented logic here
@@ -67,6 +497,57 @@ impl Model {
             .ok_or_else(|| ModelError::EntityNotFound)
     }
 
+    /// The Task followed by its ancestors up to the root Task
+    pub async fn lineage(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
+        let mut lineage = vec![Self::load(db, task_id).await?];
+
+        while let Some(parent_id) = lineage.last().and_then(|task| task.parent_id) {
+            if lineage.len() >= MAX_NESTING {
+                return Err(ModelError::msg("Task hierarchy is too deep"));
+            }
+
+            lineage.push(Self::load(db, parent_id).await?);
+        }
+
+        Ok(lineage)
+    }
+
+    /// Access level of the user on the Task, the highest of the direct grant and the
+    /// grants of the user's Groups. Subtasks without any grant for the user inherit
+    /// the level from the closest ancestor that has one
+    pub async fn effective_access(
+        db: &DatabaseConnection,
+        user_id: i32,
+        task_id: i32,
+    ) -> ModelResult<Option<AccessLevelEnum>> {
+        let lineage = Self::lineage(db, task_id).await?;
+
+        let user_accesses = accesses::Entity::find()
+            .filter(accesses::Column::UserId.eq(user_id))
+            .filter(accesses::Column::TaskId.is_in(lineage.iter().map(|task| task.id)))
+            .filter(accesses::Entity::active_at(chrono::Utc::now()))
+            .all(db)
+            .await?;
+
+        let group_accesses =
+            group_accesses::Model::list_for_member(db, user_id, lineage.iter().map(|task| task.id))
+                .await?;
+
+        Ok(lineage.iter().find_map(|task| {
+            let direct = user_accesses
+                .iter()
+                .filter(|access| access.task_id == task.id)
+                .map(|access| access.accesslevel);
+
+            let grouped = group_accesses
+                .iter()
+                .filter(|access| access.task_id == task.id)
+                .map(|access| access.accesslevel);
+
+            direct.chain(grouped).max()
+        }))
+    }
+
     pub async fn has_access(
         db: &DatabaseConnection,
         user_pid: &str,
@@ -74,83 +555,158 @@ impl Model {
         levels: Vec<AccessLevelEnum>,
     ) -> Result<()> {
         let user = users::Model::find_by_pid(db, user_pid).await?;
-        let task = tasks::Model::load(db, task_id).await?;
 
-        let user_access = accesses::Entity::find()
-            .filter(accesses::Column::UserId.eq(user.id))
-            .filter(accesses::Column::TaskId.eq(task.id))
-            .one(db)
-            .await?;
+        let has_access = Self::effective_access(db, user.id, task_id)
+            .await?
+            .is_some_and(|level| levels.contains(&level));
+
+        if has_access {
+            Ok(())
+        } else {
+            unauthorized("unauthorized")
+        }
+    }
+
+    /// Loads the Task if the viewer may see it. Public and Paid Tasks are seen by
+    /// anyone, Private ones only by Users with an Access to them. Hidden Tasks are
+    /// not found, so that their ids do not leak
+    pub async fn load_visible(
+        db: &DatabaseConnection,
+        viewer_pid: Option<&str>,
+        task_id: i32,
+    ) -> ModelResult<Self> {
+        let task = Self::load(db, task_id).await?;
+
+        if task.visibility != TaskVisibilityEnum::Private {
+            return Ok(task);
+        }
 
-        let has_access = match user_access {
-            Some(access_level) => levels.contains(&access_level.accesslevel),
-            None => false,
+        let Some(viewer_pid) = viewer_pid else {
+            return Err(ModelError::EntityNotFound);
         };
 
-        if has_access {
+        let viewer = users::Model::find_by_pid(db, viewer_pid).await?;
+
+        match Self::effective_access(db, viewer.id, task.id).await? {
+            Some(level) if Action::ReadTask.allows(level) => Ok(task),
+            _ => Err(ModelError::EntityNotFound),
+        }
+    }
+
+    /// Fails unless the User's effective Access to the Task allows the action,
+    /// returns the level of that Access
+    pub async fn authorize(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task_id: i32,
+        action: Action,
+    ) -> Result<AccessLevelEnum> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        match Self::effective_access(db, user.id, task_id).await? {
+            Some(level) if action.allows(level) => Ok(level),
+            _ => unauthorized("unauthorized"),
+        }
+    }
+
+    /// Fails unless the user owns the Task
+    pub async fn check_owner(db: &DatabaseConnection, user_pid: &str, task_id: i32) -> Result<()> {
+        let owner = accesses::Model::find_task_owner(db, task_id).await?;
+
+        if owner.pid.to_string() == user_pid {
             Ok(())
         } else {
             unauthorized("unauthorized")
         }
     }
 
-    pub async fn list_public(db: &DatabaseConnection) -> ModelResult<Vec<Self>> {
-        let tasks = tasks::Entity::find()
-            .filter(
-                model::query::condition()
-                    .eq(tasks::Column::Visibility, TaskVisibilityEnum::Public)
-                    .build(),
-            )
-            .all(db)
-            .await?;
+    pub async fn list_public(
+        db: &DatabaseConnection,
+        params: &ListParams,
+        filter: &TaskFilter,
+    ) -> ModelResult<PageResponse<Self>> {
+        let query = tasks::Entity::find().filter(
+            model::query::condition()
+                .eq(tasks::Column::Visibility, TaskVisibilityEnum::Public)
+                .build(),
+        );
 
-        Ok(tasks)
+        paginate(db, filter.apply(query, false), params).await
     }
 
     pub async fn search_for_user(
         db: &DatabaseConnection,
         asked_by: &str,
         params: &SearchParams,
-    ) -> ModelResult<Vec<Self>> {
+        list: &ListParams,
+        filter: &TaskFilter,
+    ) -> ModelResult<PageResponse<SearchHit>> {
         let user = users::Model::find_by_pid(db, asked_by).await?;
 
-        let query = tasks::Entity::find()
-            .inner_join(accesses::Entity)
-            .filter(Expr::col(tasks::Column::Name).ilike(format!("%{}%", params.name)))
-            .filter(
-                Condition::any()
-                    .add(accesses::Column::UserId.eq(user.id))
-                    .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Public))
-                    .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Paid)),
-            );
+        let visible = granted_to(user.id)
+            .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Public))
+            .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Paid));
+
+        search(db, params, visible, list, filter).await
+    }
+
+    /// Ids of the Tasks the User has an Access to, together with the subtasks that
+    /// inherit it
+    pub async fn accessible_ids(
+        db: &DatabaseConnection,
+        user_id: i32,
+    ) -> ModelResult<BTreeSet<i32>> {
+        let mut ids: BTreeSet<i32> = tasks::Entity::find()
+            .select_only()
+            .column(tasks::Column::Id)
+            .filter(granted_to(user_id))
+            .into_tuple::<i32>()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect();
 
-        let tasks = query.all(db).await?;
+        let mut level: Vec<i32> = ids.iter().copied().collect();
+        for _ in 0..MAX_NESTING {
+            if level.is_empty() {
+                break;
+            }
 
-        Ok(tasks)
+            level = tasks::Entity::find()
+                .select_only()
+                .column(tasks::Column::Id)
+                .filter(tasks::Column::ParentId.is_in(level))
+                .into_tuple::<i32>()
+                .all(db)
+                .await?
+                .into_iter()
+                .filter(|id| ids.insert(*id))
+                .collect();
+        }
+
+        Ok(ids)
     }
 
     pub async fn search_for_anon(
         db: &DatabaseConnection,
         params: &SearchParams,
-    ) -> ModelResult<Vec<Self>> {
-        let query = tasks::Entity::find()
-            .filter(Expr::col(tasks::Column::Name).ilike(format!("%{}%", params.name)))
-            .filter(
-                Condition::any()
-                    .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Public))
-                    .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Paid)),
-            );
-
-        let tasks = query.all(db).await?;
+        list: &ListParams,
+        filter: &TaskFilter,
+    ) -> ModelResult<PageResponse<SearchHit>> {
+        let visible = Condition::any()
+            .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Public))
+            .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Paid));
 
-        Ok(tasks)
+        search(db, params, visible, list, filter).await
     }
 
     pub async fn list_for_user(
         db: &DatabaseConnection,
         user_pid: &str,
         asked_by: &str,
-    ) -> ModelResult<Vec<Self>> {
+        params: &ListParams,
+        filter: &TaskFilter,
+    ) -> ModelResult<PageResponse<Self>> {
         let user = users::Model::find_by_pid(db, user_pid).await?;
 
         let mut visibility = Condition::any()
@@ -164,28 +720,118 @@ impl Model {
         let query = tasks::Entity::find()
             .inner_join(accesses::Entity)
             .filter(accesses::Column::UserId.eq(user.id))
+            .filter(accesses::Entity::active_at(chrono::Utc::now()))
             .filter(visibility);
 
-        let tasks = query.all(db).await?;
-
-        Ok(tasks)
+        paginate(db, filter.apply(query, true), params).await
     }
 
-    pub async fn list_for_anon(db: &DatabaseConnection, user_pid: &str) -> ModelResult<Vec<Self>> {
+    pub async fn list_for_anon(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        params: &ListParams,
+        filter: &TaskFilter,
+    ) -> ModelResult<PageResponse<Self>> {
         let user = users::Model::find_by_pid(db, user_pid).await?;
 
         let query = tasks::Entity::find()
             .inner_join(accesses::Entity)
             .filter(accesses::Column::UserId.eq(user.id))
+            .filter(accesses::Entity::active_at(chrono::Utc::now()))
             .filter(
                 Condition::any()
                     .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Public))
                     .add(tasks::Column::Visibility.eq(TaskVisibilityEnum::Paid)),
             );
 
-        let tasks = query.all(db).await?;
+        paginate(db, filter.apply(query, true), params).await
+    }
+
+    /// Loads the Task with its subtasks, at most `depth` levels down
+    pub async fn tree(db: &DatabaseConnection, task_id: i32, depth: u32) -> ModelResult<TaskTree> {
+        let mut levels = vec![vec![Self::load(db, task_id).await?]];
+
+        for _ in 0..depth.min(MAX_TREE_DEPTH) {
+            let parent_ids: Vec<i32> = levels
+                .last()
+                .map(|level| level.iter().map(|task| task.id).collect())
+                .unwrap_or_default();
+
+            let children = tasks::Entity::find()
+                .filter(tasks::Column::ParentId.is_in(parent_ids))
+                .order_by_asc(tasks::Column::Id)
+                .all(db)
+                .await?;
+
+            if children.is_empty() {
+                break;
+            }
+
+            levels.push(children);
+        }
+
+        // assemble the tree bottom-up, one level at a time
+        let mut below: Vec<TaskTree> = Vec::new();
+        for level in levels.into_iter().rev() {
+            let mut nodes = Vec::with_capacity(level.len());
+
+            for task in level {
+                let (children, rest) = below
+                    .into_iter()
+                    .partition(|child| child.task.parent_id == Some(task.id));
+                below = rest;
+
+                nodes.push(TaskTree { task, children });
+            }
+
+            below = nodes;
+        }
+
+        below.pop().ok_or(ModelError::EntityNotFound)
+    }
+
+    /// Creates a subtask, it inherits accesses of the parent Task
+    pub async fn add_child(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        parent_id: i32,
+        params: CreateParams,
+    ) -> ModelResult<Self> {
+        let lineage = Self::lineage(db, parent_id).await?;
+        if lineage.len() >= MAX_NESTING {
+            return Err(ModelError::msg("Task hierarchy is too deep"));
+        }
+
+        let parent = &lineage[0];
+        check_price(params.price_cents)?;
 
-        Ok(tasks)
+        let txn = db.begin().await?;
+
+        let task = tasks::ActiveModel {
+            name: ActiveValue::set(params.name),
+            visibility: ActiveValue::set(params.visibility.unwrap_or(parent.visibility)),
+            price_cents: ActiveValue::set(params.price_cents),
+            parent_id: ActiveValue::set(Some(parent.id)),
+            status: ActiveValue::set(TaskStatusEnum::Draft),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            None,
+            Some(&task),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(task)
     }
 
     pub async fn add(
@@ -194,18 +840,22 @@ impl Model {
         params: CreateParams,
     ) -> ModelResult<Self> {
         let user = users::Model::find_by_pid(db, user_pid).await?;
+        check_price(params.price_cents)?;
 
         let txn = db.begin().await?;
 
         let task = tasks::ActiveModel {
             name: ActiveValue::set(params.name.clone()),
             visibility: ActiveValue::set(params.visibility.unwrap_or(TaskVisibilityEnum::Private)),
+            price_cents: ActiveValue::set(params.price_cents),
+            status: ActiveValue::set(TaskStatusEnum::Draft),
+            owner_id: ActiveValue::set(Some(user.id)),
             ..Default::default()
         }
         .insert(&txn)
         .await?;
 
-        accesses::ActiveModel {
+        let access = accesses::ActiveModel {
             user_id: ActiveValue::set(user.id),
             task_id: ActiveValue::set(task.id),
             accesslevel: ActiveValue::set(AccessLevelEnum::FullAccess),
@@ -214,20 +864,340 @@ impl Model {
         .insert(&txn)
         .await?;
 
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            None,
+            Some(&task),
+        )
+        .await?;
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            None,
+            Some(&access),
+        )
+        .await?;
+
         txn.commit().await?;
 
         Ok(task)
     }
+
+    /// Copies the Task and its Attachments into a new Private Task owned by the User.
+    /// Public Tasks can be forked by anyone, others only with an Access to them.
+    /// Subtasks are not copied
+    pub async fn fork(db: &DatabaseConnection, user_pid: &str, source_id: i32) -> Result<Fork> {
+        let source = Self::load_visible(db, Some(user_pid), source_id).await?;
+        if source.visibility != TaskVisibilityEnum::Public {
+            Self::authorize(db, user_pid, source.id, Action::ReadTask).await?;
+        }
+
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let source_attachments = attachments::Entity::find()
+            .filter(attachments::Column::TaskId.eq(source.id))
+            .order_by_asc(attachments::Column::Id)
+            .all(db)
+            .await?;
+
+        // rows older than the payload validation may not pass it, check them all before
+        // writing so that no half-copied Task is left
+        let mut copied_data = Vec::with_capacity(source_attachments.len());
+        let mut invalid_ids = Vec::new();
+        for original in &source_attachments {
+            match AttachmentData::parse(original.attachment_type, &original.data) {
+                Ok(data) => copied_data.push(data.to_data()),
+                Err(_) => invalid_ids.push(original.id.to_string()),
+            }
+        }
+        if !invalid_ids.is_empty() {
+            return Err(Error::BadRequest(format!(
+                "Attachments {} have invalid data and can not be forked",
+                invalid_ids.join(", ")
+            )));
+        }
+
+        let txn = db.begin().await?;
+
+        let task = tasks::ActiveModel {
+            name: ActiveValue::set(source.name.clone()),
+            visibility: ActiveValue::set(TaskVisibilityEnum::Private),
+            status: ActiveValue::set(TaskStatusEnum::Draft),
+            owner_id: ActiveValue::set(Some(user.id)),
+            forked_from_id: ActiveValue::set(Some(source.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let access = accesses::ActiveModel {
+            user_id: ActiveValue::set(user.id),
+            task_id: ActiveValue::set(task.id),
+            accesslevel: ActiveValue::set(AccessLevelEnum::FullAccess),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            None,
+            Some(&task),
+        )
+        .await?;
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            None,
+            Some(&access),
+        )
+        .await?;
+
+        let mut files = Vec::new();
+        for (original, data) in source_attachments.into_iter().zip(copied_data) {
+            let copy = attachments::ActiveModel {
+                task_id: ActiveValue::set(task.id),
+                owner_id: ActiveValue::set(user.id),
+                attachment_type: ActiveValue::set(original.attachment_type),
+                data: ActiveValue::set(data),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            audit_events::ActiveModel::record(
+                &txn,
+                user_pid,
+                task.id,
+                AuditEntityEnum::Attachment,
+                copy.id,
+                None,
+                Some(&copy),
+            )
+            .await?;
+
+            if copy.attachment_type == AttachmentTypeEnum::File {
+                files.push((original, copy));
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok(Fork { task, files })
+    }
+
+    /// Describes the Task with its Attachments, and its Accesses when `include_accesses`
+    /// is set, for an archive. Subtasks are not exported
+    pub async fn export(
+        db: &DatabaseConnection,
+        task_id: i32,
+        include_accesses: bool,
+    ) -> ModelResult<Export> {
+        let task = Self::load(db, task_id).await?;
+        let task_attachments = attachments::Entity::find()
+            .filter(attachments::Column::TaskId.eq(task.id))
+            .order_by_asc(attachments::Column::Id)
+            .all(db)
+            .await?;
+
+        let mut entries = Vec::with_capacity(task_attachments.len());
+        let mut files = Vec::new();
+        for (index, attachment) in task_attachments.into_iter().enumerate() {
+            let file = (attachment.attachment_type == AttachmentTypeEnum::File)
+                .then(|| archive::file_entry(index));
+
+            entries.push(AttachmentEntry {
+                attachment_type: attachment.attachment_type,
+                data: attachment.data.clone(),
+                file: file.clone(),
+            });
+            if let Some(file) = file {
+                files.push((file, attachment));
+            }
+        }
+
+        let accesses = if include_accesses {
+            let accesses = accesses::Entity::find()
+                .filter(accesses::Column::TaskId.eq(task.id))
+                .order_by_asc(accesses::Column::Id)
+                .find_also_related(users::Entity)
+                .all(db)
+                .await?;
+
+            Some(
+                accesses
+                    .into_iter()
+                    .filter_map(|(access, user)| {
+                        user.map(|user| AccessEntry {
+                            email: user.email,
+                            accesslevel: access.accesslevel,
+                            starts_at: access.starts_at,
+                            expires_at: access.expires_at,
+                        })
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        Ok(Export {
+            manifest: Manifest {
+                version: archive::FORMAT_VERSION,
+                task: TaskEntry {
+                    name: task.name,
+                    visibility: task.visibility,
+                    status: task.status,
+                    price_cents: task.price_cents,
+                },
+                attachments: entries,
+                accesses,
+            },
+            files,
+        })
+    }
+
+    /// Recreates the archived Task as a root Task owned by the User, with its Attachments.
+    /// Archived Accesses are not granted, their emails are returned instead
+    pub async fn import(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        manifest: &Manifest,
+    ) -> ModelResult<Import> {
+        let name = manifest.task.name.trim();
+        if name.chars().count() < 2 {
+            return Err(ModelError::msg("Name must be at least 2 characters long."));
+        }
+        check_price(manifest.task.price_cents)?;
+
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        // check everything before writing, so that nothing is left of a rejected archive
+        let mut attachment_data = Vec::with_capacity(manifest.attachments.len());
+        for entry in &manifest.attachments {
+            let data = AttachmentData::parse(entry.attachment_type, &entry.data).map_err(|_| {
+                ModelError::msg(&format!(
+                    "Invalid {:?} attachment `{}`",
+                    entry.attachment_type, entry.data
+                ))
+            })?;
+            attachment_data.push(data.to_data());
+        }
+
+        // the Users of an archive never agreed to get the Task and the importer may not
+        // be allowed to grant their levels, so the importer shares it with them instead
+        let skipped_emails = manifest
+            .accesses
+            .iter()
+            .flatten()
+            .map(|entry| entry.email.clone())
+            .collect();
+
+        let txn = db.begin().await?;
+
+        let task = tasks::ActiveModel {
+            name: ActiveValue::set(name.to_string()),
+            visibility: ActiveValue::set(manifest.task.visibility),
+            status: ActiveValue::set(manifest.task.status),
+            price_cents: ActiveValue::set(manifest.task.price_cents),
+            owner_id: ActiveValue::set(Some(user.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            None,
+            Some(&task),
+        )
+        .await?;
+
+        let access = accesses::ActiveModel {
+            user_id: ActiveValue::set(user.id),
+            task_id: ActiveValue::set(task.id),
+            accesslevel: ActiveValue::set(AccessLevelEnum::FullAccess),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            None,
+            Some(&access),
+        )
+        .await?;
+
+        let mut files = Vec::new();
+        for (entry, data) in manifest.attachments.iter().zip(attachment_data) {
+            let attachment = attachments::ActiveModel {
+                task_id: ActiveValue::set(task.id),
+                owner_id: ActiveValue::set(user.id),
+                attachment_type: ActiveValue::set(entry.attachment_type),
+                data: ActiveValue::set(data),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            audit_events::ActiveModel::record(
+                &txn,
+                user_pid,
+                task.id,
+                AuditEntityEnum::Attachment,
+                attachment.id,
+                None,
+                Some(&attachment),
+            )
+            .await?;
+
+            if let Some(file) = &entry.file {
+                files.push((file.clone(), attachment));
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok(Import {
+            task,
+            files,
+            skipped_emails,
+        })
+    }
 }
 
 // implement your write-oriented logic here
 impl ActiveModel {
     pub async fn update(
         db: &DatabaseConnection,
+        user_pid: &str,
         params: UpdateParams,
         task_id: i32,
     ) -> Result<Model> {
-        let mut active_model = tasks::Model::load(db, task_id).await?.into_active_model();
+        let before = tasks::Model::load(db, task_id).await?;
+        let mut active_model = before.clone().into_active_model();
 
         if let Some(name) = params.name {
             active_model.name = Set(name);
@@ -237,18 +1207,382 @@ impl ActiveModel {
             active_model.visibility = Set(visibility);
         }
 
-        let task = active_model.update(db).await?;
+        if let Some(price_cents) = params.price_cents {
+            check_price(Some(price_cents))?;
+            active_model.price_cents = Set(Some(price_cents));
+        }
+
+        let txn = db.begin().await?;
+
+        let task = active_model.update(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&before),
+            Some(&task),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::TaskUpdated { task_id: task.id });
+        if task.visibility != before.visibility {
+            events::publish(TaskEvent::AccessChanged {
+                task_id: task.id,
+                user_id: None,
+            });
+        }
 
         Ok(task)
     }
 
-    pub async fn remove(db: &DatabaseConnection, task_id: i32) -> Result<()> {
-        tasks::Model::load(db, task_id)
+    /// Sets the status of the Task, unless someone else changed it since `task` was loaded
+    pub async fn transition(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task: Model,
+        params: TransitionParams,
+    ) -> ModelResult<Model> {
+        let txn = db.begin().await?;
+
+        let res = tasks::Entity::update_many()
+            .set(ActiveModel {
+                status: Set(params.status),
+                updated_at: Set(chrono::Utc::now().into()),
+                ..Default::default()
+            })
+            .filter(tasks::Column::Id.eq(task.id))
+            .filter(tasks::Column::Status.eq(task.status))
+            .exec(&txn)
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(ModelError::msg("Task status was changed by someone else"));
+        }
+
+        let updated = tasks::Entity::find_by_id(task.id)
+            .one(&txn)
             .await?
-            .into_active_model()
-            .delete(db)
+            .ok_or_else(|| ModelError::EntityNotFound)?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            Some(&updated),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::TaskUpdated { task_id: task.id });
+
+        Ok(updated)
+    }
+
+    /// Moves the Task with its subtasks under another parent
+    pub async fn move_to(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task_id: i32,
+        params: MoveParams,
+    ) -> ModelResult<Model> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        if let Some(parent_id) = params.parent_id {
+            let lineage = tasks::Model::lineage(db, parent_id).await?;
+
+            if lineage.iter().any(|ancestor| ancestor.id == task.id) {
+                return Err(ModelError::msg(
+                    "Task can not be moved under itself or its subtasks",
+                ));
+            }
+            if lineage.len() >= MAX_NESTING {
+                return Err(ModelError::msg("Task hierarchy is too deep"));
+            }
+        }
+
+        let txn = db.begin().await?;
+
+        let mut active_model = task.clone().into_active_model();
+        active_model.parent_id = Set(params.parent_id);
+
+        // a root Task inherits nothing: it keeps the owner it had through its ancestors,
+        // ownership only changes hands through a transfer. The user moving it out keeps
+        // the FullAccess they moved it with
+        if params.parent_id.is_none() && task.parent_id.is_some() {
+            let owner_id = match task.owner_id {
+                Some(owner_id) => Some(owner_id),
+                None => tasks::Model::lineage(db, task.id)
+                    .await?
+                    .iter()
+                    .find_map(|ancestor| ancestor.owner_id),
+            };
+            active_model.owner_id = Set(owner_id);
+
+            let mover = users::Model::find_by_pid(db, user_pid).await?;
+            let keepers: BTreeSet<i32> = owner_id.into_iter().chain([mover.id]).collect();
+
+            for user_id in keepers {
+                let own_access = accesses::Entity::find()
+                    .filter(accesses::Column::UserId.eq(user_id))
+                    .filter(accesses::Column::TaskId.eq(task.id))
+                    .one(&txn)
+                    .await?;
+                if own_access.is_some() {
+                    continue;
+                }
+
+                let access = accesses::ActiveModel {
+                    user_id: ActiveValue::set(user_id),
+                    task_id: ActiveValue::set(task.id),
+                    accesslevel: ActiveValue::set(AccessLevelEnum::FullAccess),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+
+                audit_events::ActiveModel::record(
+                    &txn,
+                    user_pid,
+                    task.id,
+                    AuditEntityEnum::Access,
+                    access.id,
+                    None,
+                    Some(&access),
+                )
+                .await?;
+            }
+        }
+
+        let moved = active_model.update(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            Some(&moved),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        // the subtree now inherits from other ancestors
+        events::publish(TaskEvent::TaskUpdated { task_id: moved.id });
+        events::publish(TaskEvent::AccessChanged {
+            task_id: moved.id,
+            user_id: None,
+        });
+
+        Ok(moved)
+    }
+
+    /// Hands the Task over to the recipient, or waits for the recipient to accept it
+    /// when a confirmation is required
+    pub async fn transfer(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task: Model,
+        params: &TransferParams,
+    ) -> ModelResult<Model> {
+        let recipient = users::Model::find_by_email(db, params.email.trim()).await?;
+
+        if task.owner_id == Some(recipient.id) {
+            return Err(ModelError::msg("User already owns the Task"));
+        }
+
+        let txn = db.begin().await?;
+
+        let transferred = if params.require_confirmation {
+            let mut active_model = task.clone().into_active_model();
+            active_model.pending_owner_id = Set(Some(recipient.id));
+            active_model.update(&txn).await?
+        } else {
+            Self::hand_over(&txn, user_pid, &task, &recipient).await?
+        };
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            Some(&transferred),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        if transferred.owner_id != task.owner_id {
+            Self::publish_handed_over(&transferred);
+        }
+
+        Ok(transferred)
+    }
+
+    /// Completes the transfer waiting for the user's confirmation
+    pub async fn accept_transfer(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task_id: i32,
+    ) -> ModelResult<Model> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let task = tasks::Model::load(db, task_id).await?;
+
+        if task.pending_owner_id != Some(user.id) {
+            return Err(ModelError::msg("Task is not being transferred to the user"));
+        }
+
+        let txn = db.begin().await?;
+
+        let transferred = Self::hand_over(&txn, user_pid, &task, &user).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            Some(&transferred),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        Self::publish_handed_over(&transferred);
+
+        Ok(transferred)
+    }
+
+    /// Withdraws the transfer waiting for confirmation
+    pub async fn cancel_transfer(
+        db: &DatabaseConnection,
+        user_pid: &str,
+        task: Model,
+    ) -> ModelResult<Model> {
+        if task.pending_owner_id.is_none() {
+            return Err(ModelError::msg("Task is not being transferred"));
+        }
+
+        let txn = db.begin().await?;
+
+        let mut active_model = task.clone().into_active_model();
+        active_model.pending_owner_id = Set(None);
+        let cancelled = active_model.update(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            Some(&cancelled),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(cancelled)
+    }
+
+    /// Makes the recipient the owner with FullAccess, the previous owner keeps their access
+    async fn hand_over<C>(
+        db: &C,
+        user_pid: &str,
+        task: &Model,
+        recipient: &users::Model,
+    ) -> ModelResult<Model>
+    where
+        C: ConnectionTrait,
+    {
+        let before = accesses::Entity::find()
+            .filter(accesses::Column::UserId.eq(recipient.id))
+            .filter(accesses::Column::TaskId.eq(task.id))
+            .one(db)
             .await?;
 
+        let access = match &before {
+            Some(before) => {
+                let mut active_model = before.clone().into_active_model();
+                active_model.accesslevel = Set(AccessLevelEnum::FullAccess);
+                active_model.starts_at = Set(None);
+                active_model.expires_at = Set(None);
+                active_model.update(db).await?
+            }
+            None => {
+                accesses::ActiveModel {
+                    user_id: Set(recipient.id),
+                    task_id: Set(task.id),
+                    accesslevel: Set(AccessLevelEnum::FullAccess),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?
+            }
+        };
+
+        audit_events::ActiveModel::record(
+            db,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            before.as_ref(),
+            Some(&access),
+        )
+        .await?;
+
+        let mut active_model = task.clone().into_active_model();
+        active_model.owner_id = Set(Some(recipient.id));
+        active_model.pending_owner_id = Set(None);
+
+        Ok(active_model.update(db).await?)
+    }
+
+    /// Tells followers about the new owner, whose Access changed along
+    fn publish_handed_over(task: &Model) {
+        events::publish(TaskEvent::TaskUpdated { task_id: task.id });
+        events::publish(TaskEvent::AccessChanged {
+            task_id: task.id,
+            user_id: None,
+        });
+    }
+
+    pub async fn remove(db: &DatabaseConnection, user_pid: &str, task_id: i32) -> Result<()> {
+        let task = tasks::Model::load(db, task_id).await?;
+
+        let txn = db.begin().await?;
+
+        task.clone().into_active_model().delete(&txn).await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            Some(&task),
+            None,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        events::publish(TaskEvent::TaskRemoved { task_id: task.id });
+
         Ok(())
     }
 }
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use super::_entities::sea_orm_active_enums::AttachmentTypeEnum;
+pub use super::_entities::template_attachments::{ActiveModel, Entity, Model};
+use sea_orm::entity::prelude::*;
+pub type TemplateAttachments = Entity;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// Storage key of the content of a File attachment
+    #[must_use]
+    pub fn file_path(&self) -> Option<PathBuf> {
+        (self.attachment_type == AttachmentTypeEnum::File).then(|| {
+            PathBuf::from("templates")
+                .join(self.id.to_string())
+                .join(&self.data)
+        })
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,356 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::OnceLock,
+};
+
+use crate::models::{
+    _entities::{accesses, template_attachments},
+    attachments::{self, validated_data, AttachmentData, AttachmentTypeEnum},
+    audit_events::{self, AuditEntityEnum},
+    tasks::{self, AccessLevelEnum, TaskStatusEnum, TaskVisibilityEnum},
+    users,
+};
+
+pub use super::_entities::templates::{self, ActiveModel, Entity, Model};
+use chrono::{Duration, Utc};
+use loco_rs::prelude::*;
+use regex::{Captures, Regex};
+use sea_orm::{entity::prelude::*, ActiveValue::Set, QueryOrder, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+pub type Templates = Entity;
+
+static VARIABLE_RE: OnceLock<Regex> = OnceLock::new();
+
+fn variable_re() -> &'static Regex {
+    VARIABLE_RE.get_or_init(|| {
+        Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").expect("valid variable regex")
+    })
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Saves the Task with its Attachments as a template, named after the Task unless
+/// `name` is given
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateParams {
+    pub task_id: i32,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct InstantiateParams {
+    /// Values of the `{{variables}}` used by the template
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Saved template with the File attachments whose content still has to be copied,
+/// each one paired with the Attachment it was saved from
+#[derive(Debug)]
+pub struct SavedTemplate {
+    pub template: Model,
+    pub attachments: Vec<template_attachments::Model>,
+    pub files: Vec<(attachments::Model, template_attachments::Model)>,
+}
+
+/// Task created from a template with the File Attachments whose content still has
+/// to be copied, each one paired with the template attachment it was created from
+#[derive(Debug)]
+pub struct Instance {
+    pub task: tasks::Model,
+    pub files: Vec<(template_attachments::Model, attachments::Model)>,
+}
+
+/// Whether `{{variables}}` in data of the attachment type are filled in
+const fn has_variables(attachment_type: AttachmentTypeEnum) -> bool {
+    matches!(
+        attachment_type,
+        AttachmentTypeEnum::Description | AttachmentTypeEnum::Text
+    )
+}
+
+/// Names of the `{{variables}}` used in the text
+#[must_use]
+pub fn variables(text: &str) -> BTreeSet<String> {
+    variable_re()
+        .captures_iter(text)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// Replaces the `{{variables}}` of the text with their values, unknown ones are kept
+#[must_use]
+pub fn render(text: &str, values: &BTreeMap<String, String>) -> String {
+    variable_re()
+        .replace_all(text, |captures: &Captures| {
+            values
+                .get(&captures[1])
+                .cloned()
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Loads the template if the User saved it
+    pub async fn load_owned(db: &DatabaseConnection, user_pid: &str, id: i32) -> Result<Self> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let template = Self::load(db, id).await?;
+
+        if template.owner_id == user.id {
+            Ok(template)
+        } else {
+            unauthorized("unauthorized")
+        }
+    }
+
+    pub async fn list_for_user(
+        db: &DatabaseConnection,
+        user_pid: &str,
+    ) -> ModelResult<Vec<(Self, Vec<template_attachments::Model>)>> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        let templates = templates::Entity::find()
+            .filter(templates::Column::OwnerId.eq(user.id))
+            .order_by_asc(templates::Column::Id)
+            .find_with_related(template_attachments::Entity)
+            .all(db)
+            .await?;
+
+        Ok(templates)
+    }
+
+    pub async fn attachments(
+        &self,
+        db: &DatabaseConnection,
+    ) -> ModelResult<Vec<template_attachments::Model>> {
+        let attachments = template_attachments::Entity::find()
+            .filter(template_attachments::Column::TemplateId.eq(self.id))
+            .order_by_asc(template_attachments::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    /// Names of the `{{variables}}` used in the name and the attachments
+    #[must_use]
+    pub fn variables(&self, attachments: &[template_attachments::Model]) -> BTreeSet<String> {
+        let mut names = variables(&self.name);
+
+        for attachment in attachments {
+            if has_variables(attachment.attachment_type) {
+                names.extend(variables(&attachment.data));
+            }
+        }
+
+        names
+    }
+
+    pub async fn create_from_task(
+        db: &DatabaseConnection,
+        owner_pid: &str,
+        params: &CreateParams,
+    ) -> ModelResult<SavedTemplate> {
+        let owner = users::Model::find_by_pid(db, owner_pid).await?;
+        let task = tasks::Model::load(db, params.task_id).await?;
+        let task_attachments = attachments::Model::list_attachments(db, task.id).await?;
+
+        let name = params
+            .name
+            .as_deref()
+            .map_or(task.name.as_str(), str::trim)
+            .to_string();
+        if name.is_empty() {
+            return Err(ModelError::msg("Template name can not be empty"));
+        }
+
+        let created_at = task.created_at.with_timezone(&Utc);
+
+        let txn = db.begin().await?;
+
+        let template = templates::ActiveModel {
+            name: Set(name),
+            owner_id: Set(owner.id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut saved = Vec::with_capacity(task_attachments.len());
+        let mut files = Vec::new();
+        for attachment in task_attachments {
+            let due_offset_secs = match AttachmentData::from_model(&attachment) {
+                AttachmentData::DueDate(due_at) => Some((due_at - created_at).num_seconds()),
+                _ => None,
+            };
+
+            let copy = template_attachments::ActiveModel {
+                template_id: Set(template.id),
+                attachment_type: Set(attachment.attachment_type),
+                data: Set(attachment.data.clone()),
+                due_offset_secs: Set(due_offset_secs),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            if copy.attachment_type == AttachmentTypeEnum::File {
+                files.push((attachment, copy.clone()));
+            }
+            saved.push(copy);
+        }
+
+        txn.commit().await?;
+
+        Ok(SavedTemplate {
+            template,
+            attachments: saved,
+            files,
+        })
+    }
+
+    /// Creates a Task owned by the User from the template. Variables are filled in
+    /// the name and in Description and Text attachments, DueDates are set relative
+    /// to now
+    pub async fn instantiate(
+        &self,
+        db: &DatabaseConnection,
+        user_pid: &str,
+        params: &InstantiateParams,
+    ) -> ModelResult<Instance> {
+        let template_attachments = self.attachments(db).await?;
+
+        let missing: Vec<String> = self
+            .variables(&template_attachments)
+            .into_iter()
+            .filter(|name| !params.variables.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(ModelError::msg(&format!(
+                "Missing values for variables: {}",
+                missing.join(", ")
+            )));
+        }
+
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        // fill in and check every attachment before writing, so that a rejected one
+        // leaves no half-built Task behind
+        let now = Utc::now();
+        let mut attachment_data = Vec::with_capacity(template_attachments.len());
+        for template_attachment in &template_attachments {
+            let data = match template_attachment.due_offset_secs {
+                Some(offset) => AttachmentData::DueDate(now + Duration::seconds(offset)).to_data(),
+                None if has_variables(template_attachment.attachment_type) => {
+                    render(&template_attachment.data, &params.variables)
+                }
+                None => template_attachment.data.clone(),
+            };
+            attachment_data.push(validated_data(template_attachment.attachment_type, &data)?);
+        }
+
+        let txn = db.begin().await?;
+
+        let task = tasks::ActiveModel {
+            name: Set(render(&self.name, &params.variables)),
+            visibility: Set(TaskVisibilityEnum::Private),
+            status: Set(TaskStatusEnum::Draft),
+            owner_id: Set(Some(user.id)),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let access = accesses::ActiveModel {
+            user_id: Set(user.id),
+            task_id: Set(task.id),
+            accesslevel: Set(AccessLevelEnum::FullAccess),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Task,
+            task.id,
+            None,
+            Some(&task),
+        )
+        .await?;
+        audit_events::ActiveModel::record(
+            &txn,
+            user_pid,
+            task.id,
+            AuditEntityEnum::Access,
+            access.id,
+            None,
+            Some(&access),
+        )
+        .await?;
+
+        let mut files = Vec::new();
+        for (template_attachment, data) in template_attachments.into_iter().zip(attachment_data) {
+            let attachment = attachments::ActiveModel {
+                task_id: Set(task.id),
+                owner_id: Set(user.id),
+                attachment_type: Set(template_attachment.attachment_type),
+                data: Set(data),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            audit_events::ActiveModel::record(
+                &txn,
+                user_pid,
+                task.id,
+                AuditEntityEnum::Attachment,
+                attachment.id,
+                None,
+                Some(&attachment),
+            )
+            .await?;
+
+            if attachment.attachment_type == AttachmentTypeEnum::File {
+                files.push((template_attachment, attachment));
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok(Instance { task, files })
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
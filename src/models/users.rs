@@ -11,11 +11,12 @@ pub use super::_entities::{
     roles::{self},
     users::{self, ActiveModel, Entity, Model},
 };
-use super::o_auth2_sessions;
+use super::{invitations, o_auth2_sessions};
 use loco_oauth2::models::users::OAuth2UserTrait;
 
 pub const MAGIC_LINK_LENGTH: i8 = 32;
 pub const MAGIC_LINK_EXPIRATION_MIN: i8 = 5;
+pub const CALENDAR_TOKEN_LENGTH: usize = 40;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct LoginParams {
@@ -78,6 +79,23 @@ impl Authenticable for users::Model {
         user.ok_or_else(|| ModelError::EntityNotFound)
     }
 
+    /// finds a user by the secret token of their calendar feed
+    ///
+    /// # Errors
+    ///
+    /// When could not find user by the given token or DB query error
+    pub async fn find_by_calendar_token(db: &DatabaseConnection, token: &str) -> ModelResult<Self> {
+        let user = users::Entity::find()
+            .filter(
+                model::query::condition()
+                    .eq(users::Column::CalendarToken, token)
+                    .build(),
+            )
+            .one(db)
+            .await?;
+        user.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
     async fn find_by_claims_key(db: &DatabaseConnection, claims_key: &str) -> ModelResult<Self> {
         Self::find_by_pid(db, claims_key).await
     }
@@ -302,7 +320,7 @@ impl users::Model {
     }
 
     /// Asynchronously creates a user with a password and saves it to the
-    /// database.
+    /// database. Pending invitations of the email become accesses.
     ///
     /// # Errors
     ///
@@ -415,14 +433,23 @@ impl ActiveModel {
     /// email and updates it in the database.
     ///
     /// This method sets the timestamp when the user successfully verifies their
-    /// email.
+    /// email. Pending invitations of the email turn into accesses only now,
+    /// once the user has proven they own it.
     ///
     /// # Errors
     ///
     /// when has DB query error
     pub async fn verified(mut self, db: &DatabaseConnection) -> ModelResult<users::Model> {
         self.email_verified_at = ActiveValue::set(Some(Local::now().into()));
-        Ok(self.update(db).await?)
+
+        let txn = db.begin().await?;
+
+        let user = self.update(&txn).await?;
+        invitations::Model::accept_for(&txn, &user).await?;
+
+        txn.commit().await?;
+
+        Ok(user)
     }
 
     /// Resets the current user password with a new password and
@@ -474,6 +501,31 @@ impl ActiveModel {
         self.magic_link_expiration = ActiveValue::set(None);
         Ok(self.update(db).await?)
     }
+
+    /// Generates a new secret token for the calendar feed, so that URLs with the
+    /// previous token stop working.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn rotate_calendar_token(
+        mut self,
+        db: &DatabaseConnection,
+    ) -> ModelResult<users::Model> {
+        self.calendar_token = ActiveValue::set(Some(hash::random_string(CALENDAR_TOKEN_LENGTH)));
+        Ok(self.update(db).await?)
+    }
+
+    /// Turns the calendar feed off by removing its token.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn clear_calendar_token(
+        mut self,
+        db: &DatabaseConnection,
+    ) -> ModelResult<users::Model> {
+        self.calendar_token = ActiveValue::set(None);
+        Ok(self.update(db).await?)
+    }
 }
 
 /// `OAuth2UserProfile` user profile information via scopes
@@ -565,10 +617,13 @@ impl OAuth2UserTrait<OAuth2UserProfile> for Model {
                     )));
                 };
 
-                users::ActiveModel {
+                // only an email the provider verified proves the user owns it
+                let email_verified_at = profile.email_verified.then(|| Local::now().into());
+
+                let user = users::ActiveModel {
                     email: ActiveValue::set(profile.email.to_string()),
                     name: ActiveValue::set(profile.name.to_string()),
-                    email_verified_at: ActiveValue::set(Some(Local::now().into())),
+                    email_verified_at: ActiveValue::set(email_verified_at),
                     password: ActiveValue::set(password_hash),
                     role_id: ActiveValue::set(role_id),
                     ..Default::default()
@@ -578,7 +633,13 @@ impl OAuth2UserTrait<OAuth2UserProfile> for Model {
                 .map_err(|e| {
                     tracing::error!("Error while trying to create user: {e}");
                     ModelError::Any(e.into())
-                })?
+                })?;
+
+                if profile.email_verified {
+                    invitations::Model::accept_for(&txn, &user).await?;
+                }
+
+                user
             }
             // Do nothing if user exists
             Some(user) => user,
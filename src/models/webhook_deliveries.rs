@@ -0,0 +1,161 @@
+use crate::{
+    common::{events::TaskEvent, pagination::ListParams},
+    models::_entities::webhooks,
+};
+
+pub use super::_entities::webhook_deliveries::{self, ActiveModel, Entity, Model};
+use chrono::{DateTime, Duration, Utc};
+use loco_rs::{model::query::PageResponse, prelude::*};
+use sea_orm::{entity::prelude::*, ActiveValue::Set, IntoActiveModel, QueryOrder};
+use serde::Serialize;
+pub type WebhookDeliveries = Entity;
+
+/// Attempts after which a delivery is given up
+pub const MAX_ATTEMPTS: i32 = 8;
+/// Wait before the first retry, doubled with every further attempt
+const BASE_BACKOFF_SECS: i64 = 30;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Wait before the next attempt of a delivery that failed `attempts` times
+#[must_use]
+pub fn backoff(attempts: i32) -> Duration {
+    Duration::seconds(BASE_BACKOFF_SECS << attempts.clamp(0, MAX_ATTEMPTS))
+}
+
+/// What the receiver of a delivery answered
+#[derive(Debug)]
+pub enum Outcome {
+    Delivered { status: u16 },
+    Failed { status: Option<u16>, error: String },
+}
+
+/// Body sent to the hook
+#[derive(Debug, Serialize)]
+struct Payload<'a> {
+    event: &'a TaskEvent,
+    occurred_at: DateTime<Utc>,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    pub async fn list_for_webhook(
+        db: &DatabaseConnection,
+        webhook_id: i32,
+        params: &ListParams,
+    ) -> ModelResult<PageResponse<Self>> {
+        let query = webhook_deliveries::Entity::find()
+            .filter(webhook_deliveries::Column::WebhookId.eq(webhook_id))
+            .order_by(webhook_deliveries::Column::Id, params.order());
+
+        model::query::paginate(db, query, None, &params.pagination()).await
+    }
+
+    /// Neither delivered nor given up yet
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.next_attempt_at.is_some()
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Queues the event for every hook. The first attempt is made right away,
+    /// `next_attempt_at` is when it is retried unless it succeeded by then
+    pub async fn create_for_event(
+        db: &DatabaseConnection,
+        hooks: &[webhooks::Model],
+        event: &TaskEvent,
+    ) -> ModelResult<Vec<Model>> {
+        let now = Utc::now();
+        let payload = serde_json::to_string(&Payload {
+            event,
+            occurred_at: now,
+        })
+        .map_err(|err| ModelError::Any(err.into()))?;
+
+        let mut deliveries = Vec::with_capacity(hooks.len());
+        for hook in hooks {
+            let delivery = webhook_deliveries::ActiveModel {
+                webhook_id: Set(hook.id),
+                event: Set(event.kind().to_string()),
+                payload: Set(payload.clone()),
+                next_attempt_at: Set(Some((now + backoff(0)).into())),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+
+            deliveries.push(delivery);
+        }
+
+        Ok(deliveries)
+    }
+
+    /// Logs the attempt and schedules the next one with an exponential backoff,
+    /// unless the delivery succeeded or ran out of attempts
+    pub async fn record_attempt(
+        db: &DatabaseConnection,
+        delivery: Model,
+        outcome: &Outcome,
+    ) -> ModelResult<Model> {
+        let now = Utc::now();
+        let attempts = delivery.attempts + 1;
+
+        let mut active_model = delivery.into_active_model();
+        active_model.attempts = Set(attempts);
+
+        match outcome {
+            Outcome::Delivered { status } => {
+                active_model.status_code = Set(Some(i32::from(*status)));
+                active_model.error = Set(None);
+                active_model.delivered_at = Set(Some(now.into()));
+                active_model.next_attempt_at = Set(None);
+            }
+            Outcome::Failed { status, error } => {
+                active_model.status_code = Set(status.map(i32::from));
+                active_model.error = Set(Some(error.clone()));
+                active_model.next_attempt_at =
+                    Set((attempts < MAX_ATTEMPTS).then(|| (now + backoff(attempts)).into()));
+            }
+        }
+
+        Ok(active_model.update(db).await?)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Pending deliveries of enabled hooks whose retry is due
+    pub async fn find_due(db: &DatabaseConnection, now: DateTime<Utc>) -> ModelResult<Vec<Model>> {
+        let due = webhook_deliveries::Entity::find()
+            .inner_join(webhooks::Entity)
+            .filter(webhooks::Column::Enabled.eq(true))
+            .filter(webhook_deliveries::Column::NextAttemptAt.lte(now))
+            .all(db)
+            .await?;
+
+        Ok(due)
+    }
+}
@@ -0,0 +1,273 @@
+use std::net::{IpAddr, SocketAddr};
+
+use crate::models::{tasks, users};
+
+pub use super::_entities::webhooks::{self, ActiveModel, Entity, Model};
+use hmac::{Hmac, Mac};
+use loco_rs::prelude::*;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, Condition, IntoActiveModel};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::ValidateUrl;
+pub type Webhooks = Entity;
+
+/// Header carrying the hex encoded HMAC-SHA256 of the delivery body
+pub const SIGNATURE_HEADER: &str = "x-taskhub-signature";
+pub const EVENT_HEADER: &str = "x-taskhub-event";
+/// Header carrying the delivery id, the same for every retry of a delivery
+pub const DELIVERY_HEADER: &str = "x-taskhub-delivery";
+
+/// Failed deliveries in a row after which the hook is disabled
+pub const MAX_FAILURES: i32 = 10;
+
+/// Recorded instead of delivering to a host that is not on the public internet
+pub const NOT_PUBLIC_ERROR: &str = "Webhook URL must point to a public host";
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Whether the address is on the public internet. Loopback, private, link-local,
+/// unique-local and shared addresses are not, which also covers the metadata
+/// services of cloud providers
+#[must_use]
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            let shared = first == 100 && (64..128).contains(&second);
+
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || first == 0
+                || shared)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_address(IpAddr::V4(ip));
+            }
+            let first = ip.segments()[0];
+            let unique_local = first & 0xfe00 == 0xfc00;
+            let link_local = first & 0xffc0 == 0xfe80;
+
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || unique_local
+                || link_local)
+        }
+    }
+}
+
+/// Host of the hook URL with the addresses it resolves to, so that a delivery goes
+/// to the addresses that were checked
+///
+/// # Errors
+///
+/// When the URL is not an http(s) URL, its host can not be resolved or one of its
+/// addresses is not public
+pub async fn resolve_destination(url: &str) -> ModelResult<(String, Vec<SocketAddr>)> {
+    let parsed = reqwest::Url::parse(url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"));
+    let Some((host, port)) = parsed.as_ref().and_then(|url| {
+        let host = url
+            .host_str()?
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        Some((host.to_string(), url.port_or_known_default()?))
+    }) else {
+        return Err(ModelError::msg("Webhook URL must be an http(s) URL"));
+    };
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|_| ModelError::msg("Webhook URL host can not be resolved"))?
+        .collect();
+    if addrs.is_empty() || !addrs.iter().all(|addr| is_public_address(addr.ip())) {
+        return Err(ModelError::msg(NOT_PUBLIC_ERROR));
+    }
+
+    Ok((host, addrs))
+}
+
+/// Registers a hook for the events of the Task, or of all Tasks the User owns
+/// when `task_id` is empty
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateParams {
+    pub url: String,
+    pub task_id: Option<i32>,
+}
+
+// implement your read-oriented logic here
+impl Model {
+    pub async fn load(db: &DatabaseConnection, id: i32) -> ModelResult<Self> {
+        Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or_else(|| ModelError::EntityNotFound)
+    }
+
+    /// Loads the hook if the user registered it
+    pub async fn load_owned(db: &DatabaseConnection, user_pid: &str, id: i32) -> Result<Self> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+        let hook = Self::load(db, id).await?;
+
+        if hook.owner_id == user.id {
+            Ok(hook)
+        } else {
+            unauthorized("unauthorized")
+        }
+    }
+
+    pub async fn list_for_user(db: &DatabaseConnection, user_pid: &str) -> ModelResult<Vec<Self>> {
+        let user = users::Model::find_by_pid(db, user_pid).await?;
+
+        let hooks = webhooks::Entity::find()
+            .filter(webhooks::Column::OwnerId.eq(user.id))
+            .all(db)
+            .await?;
+
+        Ok(hooks)
+    }
+
+    /// Enabled hooks interested in the Task: the ones registered for it or for one
+    /// of its ancestors, and the ones of its owner that cover all their Tasks.
+    /// Hooks only fire while the User who registered them owns the Task they are
+    /// registered for, so a transferred Task stops reaching its former owner
+    pub async fn list_for_task(db: &DatabaseConnection, task_id: i32) -> ModelResult<Vec<Self>> {
+        let lineage = tasks::Model::lineage(db, task_id).await?;
+        if lineage.iter().all(|task| task.owner_id.is_none()) {
+            return Ok(Vec::new());
+        }
+
+        let mut interested = Condition::any();
+        for (depth, task) in lineage.iter().enumerate() {
+            // subtasks without an owner are owned by the owner of the closest owned ancestor
+            let Some(owner_id) = lineage[depth..]
+                .iter()
+                .find_map(|ancestor| ancestor.owner_id)
+            else {
+                continue;
+            };
+
+            interested = interested.add(
+                Condition::all()
+                    .add(webhooks::Column::TaskId.eq(task.id))
+                    .add(webhooks::Column::OwnerId.eq(owner_id)),
+            );
+
+            if depth == 0 {
+                interested = interested.add(
+                    Condition::all()
+                        .add(webhooks::Column::TaskId.is_null())
+                        .add(webhooks::Column::OwnerId.eq(owner_id)),
+                );
+            }
+        }
+
+        let hooks = webhooks::Entity::find()
+            .filter(webhooks::Column::Enabled.eq(true))
+            .filter(interested)
+            .all(db)
+            .await?;
+
+        Ok(hooks)
+    }
+
+    pub async fn add(
+        db: &DatabaseConnection,
+        owner_pid: &str,
+        params: &CreateParams,
+    ) -> ModelResult<Self> {
+        let url = params.url.trim();
+        if !url.validate_url() || !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(ModelError::msg("Webhook URL must be an http(s) URL"));
+        }
+        resolve_destination(url).await?;
+
+        let owner = users::Model::find_by_pid(db, owner_pid).await?;
+
+        let hook = webhooks::ActiveModel {
+            url: Set(url.to_string()),
+            secret: Set(Uuid::new_v4().simple().to_string()),
+            owner_id: Set(owner.id),
+            task_id: Set(params.task_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok(hook)
+    }
+
+    /// Signs a delivery body with the secret of the hook
+    ///
+    /// # Errors
+    ///
+    /// When the secret can not be used as a key
+    pub fn sign(&self, payload: &[u8]) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .map_err(|_| Error::Message("invalid webhook secret".to_string()))?;
+        mac.update(payload);
+
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    pub async fn record_success(db: &DatabaseConnection, hook: Model) -> ModelResult<Model> {
+        if hook.failure_count == 0 {
+            return Ok(hook);
+        }
+
+        let mut active_model = hook.into_active_model();
+        active_model.failure_count = Set(0);
+
+        Ok(active_model.update(db).await?)
+    }
+
+    /// Counts the failed delivery, the hook is disabled once it fails too often in a row
+    pub async fn record_failure(db: &DatabaseConnection, hook: Model) -> ModelResult<Model> {
+        let failure_count = hook.failure_count + 1;
+
+        let mut active_model = hook.into_active_model();
+        active_model.failure_count = Set(failure_count);
+        if failure_count >= MAX_FAILURES {
+            active_model.enabled = Set(false);
+        }
+
+        Ok(active_model.update(db).await?)
+    }
+
+    /// Turns the hook back on after it was disabled for failing
+    pub async fn enable(db: &DatabaseConnection, hook: Model) -> ModelResult<Model> {
+        let mut active_model = hook.into_active_model();
+        active_model.enabled = Set(true);
+        active_model.failure_count = Set(0);
+
+        Ok(active_model.update(db).await?)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -0,0 +1,28 @@
+use loco_rs::prelude::*;
+
+use crate::workers::due_reminder::{DueReminderWorker, DueReminderWorkerArgs};
+
+pub struct DueReminders;
+
+#[async_trait]
+impl Task for DueReminders {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "due_reminders".to_string(),
+            detail: "Email users about tasks that are due soon. Optional: window_hours:<hours>"
+                .to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, vars: &task::Vars) -> Result<()> {
+        let window_hours = vars
+            .cli_arg("window_hours")
+            .ok()
+            .and_then(|hours| hours.parse().ok());
+
+        DueReminderWorker::perform_later(app_context, DueReminderWorkerArgs { window_hours })
+            .await?;
+
+        Ok(())
+    }
+}
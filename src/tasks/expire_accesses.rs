@@ -0,0 +1,21 @@
+use loco_rs::prelude::*;
+
+use crate::workers::access_expiry::{AccessExpiryWorker, AccessExpiryWorkerArgs};
+
+pub struct ExpireAccesses;
+
+#[async_trait]
+impl Task for ExpireAccesses {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "expire_accesses".to_string(),
+            detail: "Remove expired accesses and notify the task owners".to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, _vars: &task::Vars) -> Result<()> {
+        AccessExpiryWorker::perform_later(app_context, AccessExpiryWorkerArgs::default()).await?;
+
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
-
+pub mod due_reminders;
+pub mod expire_accesses;
+pub mod retry_webhooks;
@@ -0,0 +1,34 @@
+use loco_rs::prelude::*;
+
+use crate::{
+    models::webhook_deliveries,
+    workers::webhook_delivery::{WebhookDeliveryWorker, WebhookDeliveryWorkerArgs},
+};
+
+pub struct RetryWebhooks;
+
+#[async_trait]
+impl Task for RetryWebhooks {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "retry_webhooks".to_string(),
+            detail: "Retry webhook deliveries that failed and are due again".to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, _vars: &task::Vars) -> Result<()> {
+        let due = webhook_deliveries::Entity::find_due(&app_context.db, chrono::Utc::now()).await?;
+
+        for delivery in due {
+            WebhookDeliveryWorker::perform_later(
+                app_context,
+                WebhookDeliveryWorkerArgs {
+                    delivery_id: delivery.id,
+                },
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,16 @@
 use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
 
-use crate::models::{accesses, tasks::AccessLevelEnum};
+use crate::models::{accesses, group_accesses, tasks::AccessLevelEnum};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct AccessResponse {
     pub accesslevel: AccessLevelEnum,
     pub user_id: i32,
     pub task_id: i32,
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    pub expires_at: Option<DateTimeWithTimeZone>,
 }
 
 impl AccessResponse {
@@ -17,6 +20,8 @@ impl AccessResponse {
             accesslevel: access.accesslevel,
             user_id: access.user_id,
             task_id: access.task_id,
+            starts_at: access.starts_at,
+            expires_at: access.expires_at,
         }
     }
 
@@ -28,7 +33,32 @@ impl AccessResponse {
                 accesslevel: access.accesslevel,
                 user_id: access.user_id,
                 task_id: access.task_id,
+                starts_at: access.starts_at,
+                expires_at: access.expires_at,
             })
             .collect()
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct GroupAccessResponse {
+    pub accesslevel: AccessLevelEnum,
+    pub group_id: i32,
+    pub task_id: i32,
+}
+
+impl GroupAccessResponse {
+    #[must_use]
+    pub fn new(access: &group_accesses::Model) -> Self {
+        Self {
+            accesslevel: access.accesslevel,
+            group_id: access.group_id,
+            task_id: access.task_id,
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(accesses: &[group_accesses::Model]) -> Vec<Self> {
+        accesses.iter().map(Self::new).collect()
+    }
+}
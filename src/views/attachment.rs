@@ -5,18 +5,20 @@ use crate::models::attachments::*;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct AttachmentResponse {
+    pub id: i32,
     pub task_id: i32,
     pub attachment_type: AttachmentTypeEnum,
-    pub data: String,
+    pub data: AttachmentData,
 }
 
 impl AttachmentResponse {
     #[must_use]
     pub fn new(attachment: Model) -> Self {
         Self {
+            id: attachment.id,
             task_id: attachment.task_id,
             attachment_type: attachment.attachment_type,
-            data: attachment.data.clone(),
+            data: AttachmentData::from_model(&attachment),
         }
     }
 
@@ -25,9 +27,10 @@ impl AttachmentResponse {
         attachment
             .iter()
             .map(|attachment| Self {
+                id: attachment.id,
                 task_id: attachment.task_id,
                 attachment_type: attachment.attachment_type,
-                data: attachment.data.clone(),
+                data: AttachmentData::from_model(attachment),
             })
             .collect()
     }
@@ -0,0 +1,38 @@
+use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::audit_events::{self, AuditActionEnum, AuditEntityEnum};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    pub id: i32,
+    /// Pid of the User who made the change
+    #[schema(value_type = String, format = Uuid)]
+    pub actor_pid: Uuid,
+    pub entity: AuditEntityEnum,
+    pub entity_id: i32,
+    pub action: AuditActionEnum,
+    /// Changed fields before the change, absent for created entities
+    pub before: Option<serde_json::Value>,
+    /// Changed fields after the change, absent for removed entities
+    pub after: Option<serde_json::Value>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl AuditEventResponse {
+    #[must_use]
+    pub fn new(event: audit_events::Model) -> Self {
+        Self {
+            id: event.id,
+            actor_pid: event.actor_pid,
+            entity: event.entity,
+            entity_id: event.entity_id,
+            action: event.action,
+            before: event.before,
+            after: event.after,
+            created_at: event.created_at,
+        }
+    }
+}
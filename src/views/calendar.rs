@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use loco_openapi::prelude::ToSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::attachments::CalendarEntry;
+
+/// Characters of the Task description shown in an event
+pub const EXCERPT_LEN: usize = 200;
+/// Longest content line in octets, longer ones are folded
+const MAX_LINE_LEN: usize = 75;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CalendarTokenResponse {
+    /// Secret feed URL to subscribe to, it works without authentication
+    pub url: String,
+}
+
+impl CalendarTokenResponse {
+    #[must_use]
+    pub fn new(backend: &str, token: &str) -> Self {
+        Self {
+            url: format!("https://{backend}/api/calendar/{token}.ics"),
+        }
+    }
+}
+
+/// Escapes TEXT values, see RFC 5545 section 3.3.11
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn timestamp(date: DateTime<Utc>) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn excerpt(text: &str) -> String {
+    let text = text.trim();
+
+    if text.chars().count() > EXCERPT_LEN {
+        let cut: String = text.chars().take(EXCERPT_LEN).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Appends the content line, folded so that no line is longer than 75 octets
+fn push_line(ics: &mut String, line: &str) {
+    let mut len = 0;
+
+    for c in line.chars() {
+        if len + c.len_utf8() > MAX_LINE_LEN {
+            ics.push_str("\r\n ");
+            // the leading space of the continuation counts
+            len = 1;
+        }
+        ics.push(c);
+        len += c.len_utf8();
+    }
+
+    ics.push_str("\r\n");
+}
+
+/// iCalendar feed with an event at every due date, linking to the Task in the frontend
+#[must_use]
+pub fn ics(entries: &[CalendarEntry], frontend: &str) -> String {
+    let mut ics = String::new();
+
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//TaskHub//Due dates//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        "X-WR-CALNAME:TaskHub due dates",
+    ] {
+        push_line(&mut ics, line);
+    }
+
+    for entry in entries {
+        let link = format!("https://{frontend}/tasks/{}", entry.task.id);
+        let description = match entry.description.as_deref().map(excerpt) {
+            Some(excerpt) if !excerpt.is_empty() => format!("{excerpt}\n\n{link}"),
+            _ => link.clone(),
+        };
+
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(
+            &mut ics,
+            &format!("UID:due-date-{}@taskhub", entry.attachment.id),
+        );
+        push_line(
+            &mut ics,
+            &format!(
+                "DTSTAMP:{}",
+                timestamp(entry.attachment.updated_at.with_timezone(&Utc))
+            ),
+        );
+        push_line(&mut ics, &format!("DTSTART:{}", timestamp(entry.due_at)));
+        push_line(&mut ics, &format!("SUMMARY:{}", escape(&entry.task.name)));
+        push_line(&mut ics, &format!("DESCRIPTION:{}", escape(&description)));
+        push_line(&mut ics, &format!("URL:{link}"));
+        push_line(&mut ics, "END:VEVENT");
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+
+    ics
+}
@@ -0,0 +1,41 @@
+use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::models::comments;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: i32,
+    pub task_id: i32,
+    /// Comment this one replies to, absent for top level comments
+    pub parent_id: Option<i32>,
+    pub author_id: i32,
+    /// Empty once the comment is removed
+    pub body: String,
+    pub edited_at: Option<DateTimeWithTimeZone>,
+    /// Removed comments are only kept while they have replies
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl CommentResponse {
+    #[must_use]
+    pub fn new(comment: comments::Model) -> Self {
+        Self {
+            id: comment.id,
+            task_id: comment.task_id,
+            parent_id: comment.parent_id,
+            author_id: comment.author_id,
+            body: comment.body,
+            edited_at: comment.edited_at,
+            deleted_at: comment.deleted_at,
+            created_at: comment.created_at,
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(comments: Vec<comments::Model>) -> Vec<Self> {
+        comments.into_iter().map(Self::new).collect()
+    }
+}
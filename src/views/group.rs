@@ -0,0 +1,65 @@
+use loco_openapi::prelude::ToSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{groups, users};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct GroupResponse {
+    pub id: i32,
+    pub name: String,
+    pub owner_id: i32,
+}
+
+impl GroupResponse {
+    #[must_use]
+    pub fn new(group: &groups::Model) -> Self {
+        Self {
+            id: group.id,
+            name: group.name.clone(),
+            owner_id: group.owner_id,
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(groups: &[groups::Model]) -> Vec<Self> {
+        groups.iter().map(Self::new).collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct MemberResponse {
+    pub pid: String,
+    pub name: String,
+    pub email: String,
+}
+
+impl MemberResponse {
+    #[must_use]
+    pub fn new(user: &users::Model) -> Self {
+        Self {
+            pid: user.pid.to_string(),
+            name: user.name.clone(),
+            email: user.email.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct GroupFullResponse {
+    pub id: i32,
+    pub name: String,
+    pub owner_id: i32,
+    pub members: Vec<MemberResponse>,
+}
+
+impl GroupFullResponse {
+    #[must_use]
+    pub fn new(group: &groups::Model, members: &[users::Model]) -> Self {
+        Self {
+            id: group.id,
+            name: group.name.clone(),
+            owner_id: group.owner_id,
+            members: members.iter().map(MemberResponse::new).collect(),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{invitations, tasks::AccessLevelEnum};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub email: String,
+    pub accesslevel: AccessLevelEnum,
+    pub task_id: i32,
+    pub starts_at: Option<DateTimeWithTimeZone>,
+    pub expires_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl InvitationResponse {
+    #[must_use]
+    pub fn new(invitation: &invitations::Model) -> Self {
+        Self {
+            email: invitation.email.clone(),
+            accesslevel: invitation.accesslevel,
+            task_id: invitation.task_id,
+            starts_at: invitation.starts_at,
+            expires_at: invitation.expires_at,
+            created_at: invitation.created_at,
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(invitations: &[invitations::Model]) -> Vec<Self> {
+        invitations.iter().map(Self::new).collect()
+    }
+}
@@ -1,6 +1,16 @@
 pub mod access;
 pub mod attachment;
+pub mod audit_event;
 pub mod auth;
+pub mod calendar;
+pub mod comment;
+pub mod group;
+pub mod invitation;
+pub mod page;
+pub mod purchase;
 pub mod role;
+pub mod solution;
 pub mod task;
+pub mod template;
 pub mod user;
+pub mod webhook;
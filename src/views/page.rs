@@ -0,0 +1,37 @@
+use loco_openapi::prelude::ToSchema;
+use loco_rs::model::query;
+use serde::{Deserialize, Serialize};
+
+use crate::common::pagination::ListParams;
+
+/// Envelope of paginated list endpoints
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_items: u64,
+    pub total_pages: u64,
+    /// Page to request next, absent on the last page
+    pub next_page: Option<u64>,
+}
+
+impl<T> PageResponse<T> {
+    #[must_use]
+    pub fn new<M>(
+        page: query::PageResponse<M>,
+        params: &ListParams,
+        view: impl Fn(M) -> T,
+    ) -> Self {
+        let pagination = params.pagination();
+
+        Self {
+            items: page.page.into_iter().map(view).collect(),
+            page: pagination.page,
+            page_size: pagination.page_size,
+            total_items: page.total_items,
+            total_pages: page.total_pages,
+            next_page: (pagination.page < page.total_pages).then_some(pagination.page + 1),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use loco_openapi::prelude::ToSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::purchases::{self, PurchaseStatusEnum};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CheckoutResponse {
+    pub purchase_id: i32,
+    pub task_id: i32,
+    pub amount_cents: i32,
+    pub currency: String,
+    pub status: PurchaseStatusEnum,
+    /// Page of the payment provider where the User pays
+    pub url: String,
+}
+
+impl CheckoutResponse {
+    #[must_use]
+    pub fn new(purchase: purchases::Model, url: String) -> Self {
+        Self {
+            purchase_id: purchase.id,
+            task_id: purchase.task_id,
+            amount_cents: purchase.amount_cents,
+            currency: purchase.currency,
+            status: purchase.status,
+            url,
+        }
+    }
+}
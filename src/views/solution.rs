@@ -0,0 +1,39 @@
+use loco_openapi::prelude::ToSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    solution_files,
+    solutions::{Model, SolutionStatusEnum},
+};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SolutionResponse {
+    pub id: i32,
+    pub task_id: i32,
+    pub author_id: i32,
+    pub body: String,
+    pub status: SolutionStatusEnum,
+    pub files: Vec<String>,
+}
+
+impl SolutionResponse {
+    #[must_use]
+    pub fn new(solution: Model, files: Vec<solution_files::Model>) -> Self {
+        Self {
+            id: solution.id,
+            task_id: solution.task_id,
+            author_id: solution.author_id,
+            body: solution.body,
+            status: solution.status,
+            files: files.into_iter().map(|file| file.file_name).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(solutions: Vec<(Model, Vec<solution_files::Model>)>) -> Vec<Self> {
+        solutions
+            .into_iter()
+            .map(|(solution, files)| Self::new(solution, files))
+            .collect()
+    }
+}
@@ -11,8 +11,35 @@ pub struct TaskFullResponse {
     pub id: i32,
     pub name: String,
     pub visibility: tasks::TaskVisibilityEnum,
+    pub status: tasks::TaskStatusEnum,
+    pub price_cents: Option<i32>,
+    pub parent_id: Option<i32>,
     pub owner: views::user::GetResponse,
     pub attachments: Vec<views::attachment::AttachmentResponse>,
+    /// Attachments are hidden until the Paid Task is purchased
+    pub locked: bool,
+    /// Task this one was forked from, absent when the viewer can not see it
+    pub forked_from: Option<ForkSourceResponse>,
+}
+
+/// Attribution of a forked Task to its source
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ForkSourceResponse {
+    pub id: i32,
+    pub name: String,
+    /// Name of the owner of the source Task
+    pub author: Option<String>,
+}
+
+impl ForkSourceResponse {
+    #[must_use]
+    pub fn new(source: tasks::Model, owner: Option<&users::Model>) -> Self {
+        Self {
+            id: source.id,
+            name: source.name,
+            author: owner.map(|owner| owner.name.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -20,6 +47,76 @@ pub struct TaskResponse {
     pub id: i32,
     pub name: String,
     pub visibility: tasks::TaskVisibilityEnum,
+    pub status: tasks::TaskStatusEnum,
+    pub price_cents: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub owner_id: Option<i32>,
+    /// Recipient of a transfer that waits for confirmation
+    pub pending_owner_id: Option<i32>,
+    /// Task this one was forked from
+    pub forked_from_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TaskTreeResponse {
+    pub id: i32,
+    pub name: String,
+    pub visibility: tasks::TaskVisibilityEnum,
+    #[schema(no_recursion)]
+    pub children: Vec<TaskTreeResponse>,
+}
+
+impl TaskTreeResponse {
+    #[must_use]
+    pub fn new(tree: tasks::TaskTree) -> Self {
+        Self {
+            id: tree.task.id,
+            name: tree.task.name,
+            visibility: tree.task.visibility,
+            children: tree.children.into_iter().map(Self::new).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TaskSearchResponse {
+    pub id: i32,
+    pub name: String,
+    pub visibility: tasks::TaskVisibilityEnum,
+    pub rank: f32,
+    /// HTML-escaped matching text with the found words wrapped in `<mark>` tags
+    pub snippet: String,
+}
+
+impl TaskSearchResponse {
+    #[must_use]
+    pub fn new(hit: tasks::SearchHit) -> Self {
+        Self {
+            id: hit.task.id,
+            name: hit.task.name,
+            visibility: hit.task.visibility,
+            rank: hit.rank,
+            snippet: hit.snippet,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TaskImportResponse {
+    pub task: TaskResponse,
+    /// Emails of archived Accesses, which are not granted. The Task can be shared with
+    /// them like any other one
+    pub skipped_emails: Vec<String>,
+}
+
+impl TaskImportResponse {
+    #[must_use]
+    pub fn new(import: tasks::Import) -> Self {
+        Self {
+            task: TaskResponse::new(import.task),
+            skipped_emails: import.skipped_emails,
+        }
+    }
 }
 
 impl TaskResponse {
@@ -29,6 +126,12 @@ impl TaskResponse {
             id: task.id,
             name: task.name.clone(),
             visibility: task.visibility,
+            status: task.status,
+            price_cents: task.price_cents,
+            parent_id: task.parent_id,
+            owner_id: task.owner_id,
+            pending_owner_id: task.pending_owner_id,
+            forked_from_id: task.forked_from_id,
         }
     }
 
@@ -40,6 +143,12 @@ impl TaskResponse {
                 id: task.id,
                 name: task.name.clone(),
                 visibility: task.visibility,
+                status: task.status,
+                price_cents: task.price_cents,
+                parent_id: task.parent_id,
+                owner_id: task.owner_id,
+                pending_owner_id: task.pending_owner_id,
+                forked_from_id: task.forked_from_id,
             })
             .collect()
     }
@@ -52,14 +161,21 @@ impl TaskFullResponse {
         user: users::Model,
         role: roles::Model,
         attachments: Vec<attachments::Model>,
+        locked: bool,
+        forked_from: Option<ForkSourceResponse>,
     ) -> Self {
         Self {
             id: task.id,
             name: task.name.clone(),
             visibility: task.visibility,
+            status: task.status,
+            price_cents: task.price_cents,
+            parent_id: task.parent_id,
 
             owner: views::user::GetResponse::new(&user, &role),
             attachments: views::attachment::AttachmentResponse::from_vec(attachments),
+            locked,
+            forked_from,
         }
     }
 }
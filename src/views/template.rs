@@ -0,0 +1,63 @@
+use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{attachments::AttachmentTypeEnum, template_attachments, templates};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TemplateAttachmentResponse {
+    pub id: i32,
+    pub attachment_type: AttachmentTypeEnum,
+    /// Data with `{{variables}}` for Description and Text attachments
+    pub data: String,
+    /// Seconds from the creation of the Task to a DueDate
+    pub due_offset_secs: Option<i64>,
+}
+
+impl TemplateAttachmentResponse {
+    #[must_use]
+    pub fn new(attachment: template_attachments::Model) -> Self {
+        Self {
+            id: attachment.id,
+            attachment_type: attachment.attachment_type,
+            data: attachment.data,
+            due_offset_secs: attachment.due_offset_secs,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TemplateResponse {
+    pub id: i32,
+    pub name: String,
+    /// Variables that need a value to instantiate the template
+    pub variables: Vec<String>,
+    pub attachments: Vec<TemplateAttachmentResponse>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl TemplateResponse {
+    #[must_use]
+    pub fn new(template: templates::Model, attachments: Vec<template_attachments::Model>) -> Self {
+        Self {
+            variables: template.variables(&attachments).into_iter().collect(),
+            id: template.id,
+            name: template.name,
+            attachments: attachments
+                .into_iter()
+                .map(TemplateAttachmentResponse::new)
+                .collect(),
+            created_at: template.created_at,
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(
+        templates: Vec<(templates::Model, Vec<template_attachments::Model>)>,
+    ) -> Vec<Self> {
+        templates
+            .into_iter()
+            .map(|(template, attachments)| Self::new(template, attachments))
+            .collect()
+    }
+}
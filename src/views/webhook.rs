@@ -0,0 +1,81 @@
+use loco_openapi::prelude::ToSchema;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{webhook_deliveries, webhooks};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: i32,
+    pub url: String,
+    /// Task whose events are sent, absent when all Tasks of the User are covered
+    pub task_id: Option<i32>,
+    pub enabled: bool,
+    /// Failed deliveries in a row
+    pub failure_count: i32,
+    /// Key of the delivery signatures, only returned when the hook is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl WebhookResponse {
+    #[must_use]
+    pub fn new(hook: webhooks::Model) -> Self {
+        Self {
+            id: hook.id,
+            url: hook.url,
+            task_id: hook.task_id,
+            enabled: hook.enabled,
+            failure_count: hook.failure_count,
+            secret: None,
+            created_at: hook.created_at,
+        }
+    }
+
+    #[must_use]
+    pub fn with_secret(hook: webhooks::Model) -> Self {
+        let secret = hook.secret.clone();
+
+        Self {
+            secret: Some(secret),
+            ..Self::new(hook)
+        }
+    }
+
+    #[must_use]
+    pub fn from_vec(hooks: Vec<webhooks::Model>) -> Vec<Self> {
+        hooks.into_iter().map(Self::new).collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct DeliveryResponse {
+    pub id: i32,
+    pub event: String,
+    pub attempts: i32,
+    /// Status the receiver answered the last attempt with
+    pub status_code: Option<i32>,
+    /// Why the last attempt failed, the body of the answer is not kept
+    pub error: Option<String>,
+    pub delivered_at: Option<DateTimeWithTimeZone>,
+    /// When the delivery is retried, absent once delivered or given up
+    pub next_attempt_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl DeliveryResponse {
+    #[must_use]
+    pub fn new(delivery: webhook_deliveries::Model) -> Self {
+        Self {
+            id: delivery.id,
+            event: delivery.event,
+            attempts: delivery.attempts,
+            status_code: delivery.status_code,
+            error: delivery.error,
+            delivered_at: delivery.delivered_at,
+            next_attempt_at: delivery.next_attempt_at,
+            created_at: delivery.created_at,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mailers::task::TaskMailer,
+    models::{accesses, tasks, users},
+};
+
+pub struct AccessExpiryWorker {
+    pub ctx: AppContext,
+}
+
+#[derive(Deserialize, Debug, Serialize, Default)]
+pub struct AccessExpiryWorkerArgs {}
+
+#[async_trait]
+impl BackgroundWorker<AccessExpiryWorkerArgs> for AccessExpiryWorker {
+    fn build(ctx: &AppContext) -> Self {
+        Self { ctx: ctx.clone() }
+    }
+
+    async fn perform(&self, _args: AccessExpiryWorkerArgs) -> Result<()> {
+        let expired = accesses::Entity::find_expired(&self.ctx.db, Utc::now()).await?;
+
+        for access in expired {
+            if !accesses::ActiveModel::expire(&self.ctx.db, &access).await? {
+                continue;
+            }
+
+            let task = tasks::Model::load(&self.ctx.db, access.task_id).await?;
+            let user = users::Entity::find_by_id(access.user_id)
+                .one(&self.ctx.db)
+                .await?
+                .ok_or_else(|| Error::NotFound)?;
+
+            // Tasks of removed users have no owner to notify
+            let Ok(owner) = accesses::Model::find_task_owner(&self.ctx.db, task.id).await else {
+                tracing::warn!(task_id = task.id, "expired access of a task without owner");
+                continue;
+            };
+
+            if let Err(err) = TaskMailer::send_access_expired(&self.ctx, &owner, &user, &task).await
+            {
+                tracing::warn!(
+                    owner_pid = owner.pid.to_string(),
+                    task_id = task.id,
+                    error = err.to_string(),
+                    "could not notify about expired access"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{Duration, Utc};
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::settings::Settings,
+    mailers::task::TaskMailer,
+    models::{accesses, attachments, due_date_reminders, tasks},
+};
+
+pub struct DueReminderWorker {
+    pub ctx: AppContext,
+}
+
+#[derive(Deserialize, Debug, Serialize, Default)]
+pub struct DueReminderWorkerArgs {
+    /// Overrides `settings.reminders.window_hours`
+    pub window_hours: Option<i64>,
+}
+
+#[async_trait]
+impl BackgroundWorker<DueReminderWorkerArgs> for DueReminderWorker {
+    fn build(ctx: &AppContext) -> Self {
+        Self { ctx: ctx.clone() }
+    }
+
+    async fn perform(&self, args: DueReminderWorkerArgs) -> Result<()> {
+        let settings = Settings::from_opt_json(&self.ctx.config.settings)?;
+        let window_hours = args.window_hours.unwrap_or(settings.reminders.window_hours);
+
+        let now = Utc::now();
+        let Some(until) =
+            Duration::try_hours(window_hours).and_then(|window| now.checked_add_signed(window))
+        else {
+            tracing::error!(window_hours, "due reminder window is out of range");
+            return Err(Error::Message(
+                "due reminder window is out of range".to_string(),
+            ));
+        };
+
+        let due = attachments::Model::list_due_between(&self.ctx.db, now, until).await?;
+
+        for (attachment, due_at) in due {
+            let task = tasks::Model::load(&self.ctx.db, attachment.task_id).await?;
+            let users = accesses::Model::list_users_for_task(&self.ctx.db, task.id).await?;
+
+            for user in users {
+                let claimed =
+                    due_date_reminders::Model::claim(&self.ctx.db, attachment.id, user.id, due_at)
+                        .await?;
+
+                if !claimed {
+                    continue;
+                }
+
+                if let Err(err) =
+                    TaskMailer::send_due_reminder(&self.ctx, &user, &task, &due_at).await
+                {
+                    tracing::warn!(
+                        user_pid = user.pid.to_string(),
+                        task_id = task.id,
+                        error = err.to_string(),
+                        "could not send due date reminder"
+                    );
+
+                    due_date_reminders::Model::release(
+                        &self.ctx.db,
+                        attachment.id,
+                        user.id,
+                        due_at,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
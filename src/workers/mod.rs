@@ -1 +1,4 @@
+pub mod access_expiry;
 pub mod downloader;
+pub mod due_reminder;
+pub mod webhook_delivery;
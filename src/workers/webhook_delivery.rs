@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use loco_rs::prelude::*;
+use reqwest::{header::CONTENT_TYPE, redirect};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::events::TaskEvent,
+    models::{
+        webhook_deliveries::{self, Outcome},
+        webhooks,
+    },
+};
+
+/// How long a receiver may take to answer a delivery
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct WebhookDeliveryWorker {
+    pub ctx: AppContext,
+}
+
+#[derive(Deserialize, Debug, Serialize)]
+pub struct WebhookDeliveryWorkerArgs {
+    pub delivery_id: i32,
+}
+
+impl WebhookDeliveryWorker {
+    /// Logs a delivery of the event for every interested hook and queues them
+    ///
+    /// # Errors
+    ///
+    /// When the hooks can not be loaded or the deliveries can not be queued
+    pub async fn dispatch(ctx: &AppContext, event: &TaskEvent) -> Result<()> {
+        // only the stream of the subscriber who lost access cares about it
+        if let TaskEvent::AccessRevoked { .. } = event {
+            return Ok(());
+        }
+
+        // hooks are found through the Task, which is gone along with its own hooks
+        if let TaskEvent::TaskRemoved { .. } = event {
+            return Ok(());
+        }
+
+        let hooks = webhooks::Model::list_for_task(&ctx.db, event.task_id()).await?;
+        if hooks.is_empty() {
+            return Ok(());
+        }
+
+        let deliveries =
+            webhook_deliveries::ActiveModel::create_for_event(&ctx.db, &hooks, event).await?;
+
+        for delivery in deliveries {
+            Self::perform_later(
+                ctx,
+                WebhookDeliveryWorkerArgs {
+                    delivery_id: delivery.id,
+                },
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        hook: &webhooks::Model,
+        delivery: &webhook_deliveries::Model,
+    ) -> Result<Outcome> {
+        // checked again for every delivery, the host may resolve elsewhere by now
+        let (host, addrs) = match webhooks::resolve_destination(&hook.url).await {
+            Ok(destination) => destination,
+            Err(ModelError::Message(error)) => {
+                return Ok(Outcome::Failed {
+                    status: None,
+                    error,
+                })
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // pinned to the checked addresses, redirects could lead anywhere
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(redirect::Policy::none())
+            .resolve_to_addrs(&host, &addrs)
+            .build()
+            .map_err(|err| Error::Message(err.to_string()))?;
+
+        let signature = hook.sign(delivery.payload.as_bytes())?;
+
+        let res = client
+            .post(&hook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(webhooks::SIGNATURE_HEADER, signature)
+            .header(webhooks::EVENT_HEADER, &delivery.event)
+            .header(webhooks::DELIVERY_HEADER, delivery.id)
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+
+        // the receiver's answer is never kept, it is shown to whoever registered the hook
+        let outcome = match res {
+            Ok(res) if res.status().is_success() => Outcome::Delivered {
+                status: res.status().as_u16(),
+            },
+            Ok(res) => Outcome::Failed {
+                status: Some(res.status().as_u16()),
+                error: "Receiver did not accept the delivery".to_string(),
+            },
+            Err(err) => {
+                let error = if err.is_timeout() {
+                    "Receiver did not answer in time"
+                } else if err.is_connect() {
+                    "Receiver could not be reached"
+                } else {
+                    "Delivery failed"
+                };
+
+                Outcome::Failed {
+                    status: None,
+                    error: error.to_string(),
+                }
+            }
+        };
+
+        Ok(outcome)
+    }
+}
+
+#[async_trait]
+impl BackgroundWorker<WebhookDeliveryWorkerArgs> for WebhookDeliveryWorker {
+    fn build(ctx: &AppContext) -> Self {
+        Self { ctx: ctx.clone() }
+    }
+
+    async fn perform(&self, args: WebhookDeliveryWorkerArgs) -> Result<()> {
+        let delivery = webhook_deliveries::Model::load(&self.ctx.db, args.delivery_id).await?;
+        if !delivery.is_pending() {
+            return Ok(());
+        }
+
+        let hook = webhooks::Model::load(&self.ctx.db, delivery.webhook_id).await?;
+        if !hook.enabled {
+            return Ok(());
+        }
+
+        let outcome = self.send(&hook, &delivery).await?;
+
+        let delivery =
+            webhook_deliveries::ActiveModel::record_attempt(&self.ctx.db, delivery, &outcome)
+                .await?;
+
+        match outcome {
+            Outcome::Delivered { .. } => {
+                webhooks::ActiveModel::record_success(&self.ctx.db, hook).await?;
+            }
+            Outcome::Failed { status, error } => {
+                tracing::warn!(
+                    webhook_id = hook.id,
+                    delivery_id = delivery.id,
+                    attempts = delivery.attempts,
+                    status,
+                    error,
+                    "webhook delivery failed"
+                );
+
+                let hook = webhooks::ActiveModel::record_failure(&self.ctx.db, hook).await?;
+                if !hook.enabled {
+                    tracing::warn!(webhook_id = hook.id, "webhook disabled after failures");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
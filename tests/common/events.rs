@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::events::{self, Subscription, TaskEvent},
+    models::{
+        accesses,
+        tasks::{self, AccessLevelEnum, MoveParams, UpdateParams},
+        users,
+    },
+};
+
+use crate::fixtures::{add_task, create_params, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn denied_access_ends_subscription() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Lecture notes").await;
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::GrantParams {
+            email: other.email.clone(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut receiver = events::hub().subscribe();
+    let mut subscription = Subscription {
+        user_id: other.id,
+        task_ids: BTreeSet::from([task.id]),
+    };
+
+    tasks::ActiveModel::update(
+        db,
+        USER_PID,
+        UpdateParams {
+            name: Some("Lecture slides".to_string()),
+            visibility: None,
+            price_cents: None,
+        },
+        task.id,
+    )
+    .await
+    .unwrap();
+
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event, TaskEvent::TaskUpdated { task_id: task.id });
+    assert!(subscription.wants(&event));
+    assert!(!event.may_revoke(other.id));
+
+    accesses::ActiveModel::deny_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::DenyParams {
+            pid: OTHER_PID.to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let event = receiver.recv().await.unwrap();
+    assert!(event.may_revoke(other.id));
+    assert_eq!(subscription.recheck(db).await.unwrap(), vec![task.id]);
+    assert!(subscription.task_ids.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn moved_and_removed_tasks_end_subscription() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let course = add_task(db, USER_PID, "Course").await;
+    let drafts = add_task(db, USER_PID, "Drafts").await;
+    let module = tasks::Model::add_child(db, USER_PID, course.id, create_params("Module"))
+        .await
+        .unwrap();
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        course.id,
+        accesses::GrantParams {
+            email: other.email.clone(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut receiver = events::hub().subscribe();
+    let mut subscription = Subscription {
+        user_id: other.id,
+        task_ids: BTreeSet::from([course.id, module.id]),
+    };
+
+    tasks::ActiveModel::move_to(
+        db,
+        USER_PID,
+        module.id,
+        MoveParams {
+            parent_id: Some(drafts.id),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        TaskEvent::TaskUpdated { task_id: module.id }
+    );
+    let event = receiver.recv().await.unwrap();
+    assert!(event.may_revoke(other.id));
+    assert_eq!(subscription.recheck(db).await.unwrap(), vec![module.id]);
+
+    tasks::ActiveModel::remove(db, USER_PID, course.id)
+        .await
+        .unwrap();
+
+    let event = receiver.recv().await.unwrap();
+    assert_eq!(event, TaskEvent::TaskRemoved { task_id: course.id });
+    assert!(subscription.wants(&event));
+    assert!(event.may_revoke(other.id));
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use loco_rs::environment::Environment;
+use task_hub::common::{settings::StorageSettings, storage};
+
+#[tokio::test]
+async fn each_storage_reads_its_own_files() {
+    let (first, first_files) = storage::build(&StorageSettings::Memory).unwrap();
+    let (_second, second_files) = storage::build(&StorageSettings::Memory).unwrap();
+
+    let path = Path::new("1/digits.txt");
+    first
+        .upload(path, &Bytes::from_static(b"0123456789"))
+        .await
+        .unwrap();
+
+    assert_eq!(first_files.size(path).await.unwrap(), 10);
+    assert_eq!(
+        first_files.read_range(path, 2, 4).await.unwrap(),
+        b"234".to_vec()
+    );
+    assert!(
+        second_files.size(path).await.is_err(),
+        "Files of another Storage are not visible"
+    );
+}
+
+fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("task_hub-storage-{}", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn can_build_memory_storage() {
+    let (storage, _) = storage::build(&StorageSettings::Memory).unwrap();
+
+    storage::check_writable(&storage).await.unwrap();
+}
+
+#[tokio::test]
+async fn can_build_local_storage() {
+    let dir = scratch_dir();
+    let (storage, files) = storage::build(&StorageSettings::Local {
+        path: dir.display().to_string(),
+    })
+    .unwrap();
+    assert!(dir.is_dir(), "The storage directory is created");
+
+    storage::check_writable(&storage).await.unwrap();
+
+    let path = Path::new("1/notes.txt");
+    storage
+        .upload(path, &Bytes::from_static(b"kept"))
+        .await
+        .unwrap();
+    assert_eq!(files.size(path).await.unwrap(), 4);
+    assert_eq!(std::fs::read(dir.join(path)).unwrap(), b"kept");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn can_build_s3_storage() {
+    let res = storage::build(&StorageSettings::S3 {
+        bucket: "task-hub".to_string(),
+        region: "us-east-1".to_string(),
+        endpoint: Some("http://localhost:9000".to_string()),
+        access_key_id: Some("minioadmin".to_string()),
+        secret_access_key: Some("minioadmin".to_string()),
+    });
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn local_storage_that_can_not_be_written_is_rejected() {
+    let dir = scratch_dir();
+    let (storage, _) = storage::build(&StorageSettings::Local {
+        path: dir.display().to_string(),
+    })
+    .unwrap();
+
+    // Permissions do not stop root, so the directory is replaced by a file instead
+    std::fs::remove_dir(&dir).unwrap();
+    std::fs::write(&dir, b"not a directory").unwrap();
+
+    assert!(storage::check_writable(&storage).await.is_err());
+
+    std::fs::remove_file(dir).unwrap();
+}
+
+#[test]
+fn storage_settings_are_required_outside_tests() {
+    assert!(matches!(
+        storage::configured(&Environment::Test, None),
+        Ok(StorageSettings::Memory)
+    ));
+    assert!(storage::configured(&Environment::Production, None).is_err());
+    assert!(storage::configured(&Environment::Development, None).is_err());
+    assert!(matches!(
+        storage::configured(
+            &Environment::Production,
+            Some(StorageSettings::Local {
+                path: "storage".to_string()
+            })
+        ),
+        Ok(StorageSettings::Local { .. })
+    ));
+}
@@ -0,0 +1,52 @@
+//! Seed Users and Task builders shared by the model, worker and request tests
+
+use sea_orm::DatabaseConnection;
+use task_hub::models::{
+    accesses::{self, GrantParams},
+    tasks::{self, AccessLevelEnum, CreateParams},
+};
+
+/// `user1@example.com` of the seed data
+pub const USER_PID: &str = "11111111-1111-1111-1111-111111111111";
+/// `user2@example.com` of the seed data
+pub const OTHER_PID: &str = "22222222-2222-2222-2222-222222222222";
+pub const OTHER_EMAIL: &str = "user2@example.com";
+
+/// Params of a Private Task without a price
+pub fn create_params(name: &str) -> CreateParams {
+    CreateParams {
+        name: name.to_string(),
+        visibility: None,
+        price_cents: None,
+    }
+}
+
+/// Adds a Private Task owned by the User
+pub async fn add_task(db: &DatabaseConnection, user_pid: &str, name: &str) -> tasks::Model {
+    tasks::Model::add(db, user_pid, create_params(name))
+        .await
+        .expect("task is created")
+}
+
+/// Grants the User with the email an Access without time window, as the Task owner
+pub async fn grant(
+    db: &DatabaseConnection,
+    owner_pid: &str,
+    task_id: i32,
+    email: &str,
+    accesslevel: AccessLevelEnum,
+) -> accesses::Model {
+    accesses::Model::grant_access(
+        db,
+        owner_pid,
+        task_id,
+        GrantParams {
+            email: email.to_string(),
+            accesslevel,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .expect("access is granted")
+}
@@ -1,3 +1,5 @@
+mod common;
+mod fixtures;
 mod models;
 mod requests;
 mod tasks;
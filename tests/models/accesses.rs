@@ -1,6 +1,19 @@
-use task_hub::app::App;
-use loco_rs::testing::prelude::*;
+use chrono::{Duration, Utc};
+use loco_rs::{model::ModelError, testing::prelude::*};
+use rstest::rstest;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        _entities::accesses::Column as AccessColumn,
+        accesses::{self, Granter, UpdateParams},
+        tasks::AccessLevelEnum,
+        users,
+    },
+};
+
+use crate::fixtures::{add_task, grant, OTHER_EMAIL, OTHER_PID, USER_PID};
 
 macro_rules! configure_insta {
     ($($expr:expr),*) => {
@@ -29,3 +42,187 @@ async fn test_model() {
     // snapshot the result:
     // assert_debug_snapshot!(item);
 }
+
+const OWNER_ID: i32 = 1;
+const GRANTER_ID: i32 = 2;
+
+#[rstest]
+#[case(
+    OWNER_ID,
+    Some(AccessLevelEnum::FullAccess),
+    AccessLevelEnum::FullAccess,
+    true
+)]
+#[case(
+    GRANTER_ID,
+    Some(AccessLevelEnum::FullAccess),
+    AccessLevelEnum::FullAccess,
+    false
+)]
+#[case(
+    GRANTER_ID,
+    Some(AccessLevelEnum::FullAccess),
+    AccessLevelEnum::AddUser,
+    true
+)]
+#[case(
+    GRANTER_ID,
+    Some(AccessLevelEnum::AddUser),
+    AccessLevelEnum::AddUser,
+    false
+)]
+#[case(
+    GRANTER_ID,
+    Some(AccessLevelEnum::AddUser),
+    AccessLevelEnum::Edit,
+    true
+)]
+#[case(
+    GRANTER_ID,
+    Some(AccessLevelEnum::AddUser),
+    AccessLevelEnum::View,
+    true
+)]
+#[case(GRANTER_ID, Some(AccessLevelEnum::View), AccessLevelEnum::View, false)]
+#[case(GRANTER_ID, None, AccessLevelEnum::View, false)]
+fn can_check_grant_ceiling(
+    #[case] user_id: i32,
+    #[case] level: Option<AccessLevelEnum>,
+    #[case] target: AccessLevelEnum,
+    #[case] allowed: bool,
+) {
+    let granter = Granter {
+        user_id,
+        owner_id: OWNER_ID,
+        level,
+    };
+
+    assert_eq!(
+        granter.can_handle(target),
+        allowed,
+        "{level:?} -> {target:?}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_put_window_on_owner_full_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Exam").await;
+
+    let later = Utc::now() + Duration::hours(1);
+    for (starts_at, expires_at) in [(None, Some(later)), (Some(later), None)] {
+        let res = accesses::ActiveModel::update_access(
+            db,
+            USER_PID,
+            task.id,
+            UpdateParams {
+                pid: USER_PID.to_string(),
+                accesslevel: AccessLevelEnum::FullAccess,
+                starts_at: starts_at.map(Into::into),
+                expires_at: expires_at.map(Into::into),
+            },
+        )
+        .await;
+        assert!(
+            matches!(res, Err(ModelError::Message(_))),
+            "{starts_at:?} - {expires_at:?}"
+        );
+    }
+
+    grant(
+        db,
+        USER_PID,
+        task.id,
+        OTHER_EMAIL,
+        AccessLevelEnum::FullAccess,
+    )
+    .await;
+
+    let res = accesses::ActiveModel::update_access(
+        db,
+        USER_PID,
+        task.id,
+        UpdateParams {
+            pid: OTHER_PID.to_string(),
+            accesslevel: AccessLevelEnum::FullAccess,
+            starts_at: None,
+            expires_at: Some(later.into()),
+        },
+    )
+    .await;
+    assert!(res.is_ok(), "The owner still holds FullAccess");
+}
+
+#[tokio::test]
+#[serial]
+async fn owner_keeps_full_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Thesis").await;
+
+    let res = accesses::ActiveModel::update_access(
+        db,
+        USER_PID,
+        task.id,
+        UpdateParams {
+            pid: USER_PID.to_string(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    let res = accesses::ActiveModel::deny_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::DenyParams {
+            pid: USER_PID.to_string(),
+        },
+    )
+    .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_grant_access_twice() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Thesis").await;
+    grant(db, USER_PID, task.id, OTHER_EMAIL, AccessLevelEnum::View).await;
+
+    let res = accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::GrantParams {
+            email: OTHER_EMAIL.to_string(),
+            accesslevel: AccessLevelEnum::Edit,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(ModelError::EntityAlreadyExists)));
+
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+    let rows = accesses::Entity::find()
+        .filter(AccessColumn::TaskId.eq(task.id))
+        .filter(AccessColumn::UserId.eq(other.id))
+        .all(db)
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].accesslevel, AccessLevelEnum::View);
+}
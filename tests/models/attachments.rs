@@ -1,6 +1,23 @@
-use task_hub::app::App;
-use loco_rs::testing::prelude::*;
+use loco_rs::{model::ModelError, prelude::Validatable, testing::prelude::*};
+use rstest::rstest;
+use sea_orm::{ActiveValue::Set, IntoActiveModel};
 use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::pagination::{ListParams, SortBy, SortOrder},
+    models::{
+        accesses,
+        attachments::{
+            self, AttachmentAddParams, AttachmentData, AttachmentError, AttachmentFilter,
+            AttachmentTypeEnum, AttachmentUpdateParams,
+        },
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
+    views::calendar::ics,
+};
+
+use crate::fixtures::{add_task, create_params, OTHER_EMAIL, OTHER_PID, USER_PID};
 
 macro_rules! configure_insta {
     ($($expr:expr),*) => {
@@ -29,3 +46,267 @@ async fn test_model() {
     // snapshot the result:
     // assert_debug_snapshot!(item);
 }
+
+#[rstest]
+#[case(AttachmentTypeEnum::DueDate, "2025-06-20T12:00:00+02:00", true)]
+#[case(AttachmentTypeEnum::DueDate, "tomorrow", false)]
+#[case(AttachmentTypeEnum::Progress, "100", true)]
+#[case(AttachmentTypeEnum::Progress, "101", false)]
+#[case(AttachmentTypeEnum::Progress, "-1", false)]
+#[case(AttachmentTypeEnum::Importance, "High", true)]
+#[case(AttachmentTypeEnum::Importance, "urgent", false)]
+#[case(AttachmentTypeEnum::Url, "https://loco.rs", true)]
+#[case(AttachmentTypeEnum::Url, "not a url", false)]
+#[case(AttachmentTypeEnum::Text, "", false)]
+#[case(AttachmentTypeEnum::File, "sheet.pdf", true)]
+#[case(AttachmentTypeEnum::File, "../7/sheet.pdf", false)]
+#[case(AttachmentTypeEnum::File, "..", false)]
+#[case(AttachmentTypeEnum::File, "notes\\sheet.pdf", false)]
+fn can_validate_attachment_data(
+    #[case] attachment_type: AttachmentTypeEnum,
+    #[case] data: &str,
+    #[case] valid: bool,
+) {
+    let res = AttachmentData::parse(attachment_type, data);
+
+    assert_eq!(res.is_ok(), valid, "{attachment_type:?} with {data:?}");
+    if let Err(errors) = res {
+        assert!(errors.field_errors().contains_key("data"));
+    }
+}
+
+#[test]
+fn can_normalize_due_date() {
+    let data = AttachmentData::parse(AttachmentTypeEnum::DueDate, "2025-06-20T12:00:00+02:00")
+        .expect("valid due date");
+
+    assert_eq!(data.to_data(), "2025-06-20T10:00:00+00:00");
+}
+
+#[test]
+fn can_validate_partial_active_model() {
+    let partial = attachments::ActiveModel {
+        data: Set("50".to_string()),
+        ..Default::default()
+    };
+    assert!(partial.validate().is_err(), "type is needed to check data");
+
+    let untouched = attachments::ActiveModel {
+        id: Set(1),
+        ..Default::default()
+    };
+    assert!(untouched.validate().is_ok());
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_invalid_attachment_data() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Homework").await;
+
+    let res = attachments::Model::add_attachment(
+        db,
+        USER_PID,
+        task.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::Progress,
+            data: "101".to_string(),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AttachmentError::Invalid(_))));
+
+    let progress = attachments::Model::add_attachment(
+        db,
+        USER_PID,
+        task.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::Progress,
+            data: " 40 ".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(progress.data, "40");
+
+    let res = progress
+        .into_active_model()
+        .update_attachment(
+            db,
+            USER_PID,
+            AttachmentUpdateParams {
+                data: "lots".to_string(),
+            },
+        )
+        .await;
+    assert!(matches!(res, Err(AttachmentError::Invalid(_))));
+
+    let res = attachments::ActiveModel {
+        id: Set(1),
+        ..Default::default()
+    }
+    .update_attachment(
+        db,
+        USER_PID,
+        AttachmentUpdateParams {
+            data: "50".to_string(),
+        },
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(AttachmentError::Model(ModelError::Message(_)))
+    ));
+}
+
+#[tokio::test]
+#[serial]
+async fn can_sort_attachments_by_name() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Reading list").await;
+
+    // in the opposite order of their types
+    for (attachment_type, data) in [
+        (AttachmentTypeEnum::Description, "zebra"),
+        (AttachmentTypeEnum::Text, "apple"),
+        (AttachmentTypeEnum::Hint, "mango"),
+    ] {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            task.id,
+            AttachmentAddParams {
+                attachment_type,
+                data: data.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let page = attachments::Model::page_attachments(
+        db,
+        task.id,
+        &ListParams {
+            sort_by: SortBy::Name,
+            order: SortOrder::Asc,
+            ..ListParams::default()
+        },
+        &AttachmentFilter::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        page.page
+            .iter()
+            .map(|attachment| attachment.data.as_str())
+            .collect::<Vec<_>>(),
+        vec!["apple", "mango", "zebra"]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn can_list_due_dates_for_calendar() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let add = |task_id: i32, attachment_type: AttachmentTypeEnum, data: &str| {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            task_id,
+            AttachmentAddParams {
+                attachment_type,
+                data: data.to_string(),
+            },
+        )
+    };
+
+    let course = add_task(db, USER_PID, "Course work").await;
+    let exercise = tasks::Model::add_child(db, USER_PID, course.id, create_params("Exercise"))
+        .await
+        .unwrap();
+    let hidden = add_task(db, USER_PID, "Hidden").await;
+
+    add(
+        course.id,
+        AttachmentTypeEnum::Description,
+        "Read chapter 1, then answer; all questions",
+    )
+    .await
+    .unwrap();
+    add(
+        course.id,
+        AttachmentTypeEnum::DueDate,
+        "2025-06-20T12:00:00+02:00",
+    )
+    .await
+    .unwrap();
+    add(
+        exercise.id,
+        AttachmentTypeEnum::DueDate,
+        "2025-06-10T08:00:00+00:00",
+    )
+    .await
+    .unwrap();
+    add(
+        hidden.id,
+        AttachmentTypeEnum::DueDate,
+        "2025-06-01T08:00:00+00:00",
+    )
+    .await
+    .unwrap();
+
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        course.id,
+        accesses::GrantParams {
+            email: OTHER_EMAIL.to_string(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+    let entries = attachments::Model::list_due_for_user(db, other.id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| entry.task.id)
+            .collect::<Vec<_>>(),
+        vec![exercise.id, course.id],
+        "Subtasks inherit the Access, soonest first"
+    );
+
+    let ics = ics(&entries, "example.com");
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+    assert!(ics.contains("DTSTART:20250620T100000Z\r\n"));
+    assert!(ics.contains("SUMMARY:Course work\r\n"));
+    assert!(ics
+        .lines()
+        .all(|line| line.trim_end_matches('\r').len() <= 75));
+
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!(
+        "DESCRIPTION:Read chapter 1\\, then answer\\; all questions\\n\\nhttps://example.com/tasks/{}\r\n",
+        course.id
+    )));
+}
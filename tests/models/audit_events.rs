@@ -0,0 +1,54 @@
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::pagination::ListParams,
+    models::{
+        audit_events::{self, AuditActionEnum, AuditEntityEnum},
+        tasks::{self, UpdateParams},
+    },
+};
+
+use crate::fixtures::{add_task, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn records_task_changes() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Draft name").await;
+
+    tasks::ActiveModel::update(
+        db,
+        USER_PID,
+        UpdateParams {
+            name: Some("Final name".to_string()),
+            visibility: None,
+            price_cents: None,
+        },
+        task.id,
+    )
+    .await
+    .unwrap();
+
+    let history = audit_events::Model::list_for_task(db, task.id, &ListParams::default())
+        .await
+        .unwrap();
+
+    // task and owner access created, then the rename
+    assert_eq!(history.total_items, 3);
+
+    let rename = &history.page[0];
+    assert_eq!(rename.entity, AuditEntityEnum::Task);
+    assert_eq!(rename.action, AuditActionEnum::Update);
+    assert_eq!(
+        rename.before,
+        Some(serde_json::json!({ "name": "Draft name" }))
+    );
+    assert_eq!(
+        rename.after,
+        Some(serde_json::json!({ "name": "Final name" }))
+    );
+}
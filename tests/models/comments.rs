@@ -0,0 +1,161 @@
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::policy::Action,
+    models::{
+        accesses,
+        comments::{self, mentioned_names, CreateParams, UpdateParams},
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
+};
+
+use crate::fixtures::{add_task, OTHER_EMAIL, OTHER_PID, USER_PID};
+
+fn comment(body: &str, parent_id: Option<i32>) -> CreateParams {
+    CreateParams {
+        body: body.to_string(),
+        parent_id,
+    }
+}
+
+#[test]
+fn can_parse_mentions() {
+    let names = mentioned_names("@User2 and @bob.smith. please look, mail me at a@b.com");
+
+    assert_eq!(
+        names.into_iter().collect::<Vec<_>>(),
+        vec!["bob.smith".to_string(), "user2".to_string()]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn viewers_can_comment_and_mention() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Essay").await;
+
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::GrantParams {
+            email: OTHER_EMAIL.to_string(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        tasks::Model::authorize(db, OTHER_PID, task.id, Action::AddComment)
+            .await
+            .is_ok()
+    );
+    assert!(
+        tasks::Model::authorize(db, OTHER_PID, task.id, Action::EditTask)
+            .await
+            .is_err()
+    );
+
+    let question = comments::Model::add(db, USER_PID, task.id, &comment("@user2 ready?", None))
+        .await
+        .unwrap();
+
+    let mentioned = question.mentioned_users(db).await.unwrap();
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+    assert_eq!(mentioned.len(), 1);
+    assert_eq!(mentioned[0].id, other.id);
+
+    let answer = comments::Model::add(db, OTHER_PID, task.id, &comment("Yes", Some(question.id)))
+        .await
+        .unwrap();
+    assert_eq!(answer.parent_id, Some(question.id));
+
+    assert!(
+        answer.authorize_edit(db, USER_PID).await.is_err(),
+        "Only the author edits a comment"
+    );
+    assert!(
+        question.authorize_remove(db, OTHER_PID).await.is_err(),
+        "Viewers can not moderate"
+    );
+    assert!(
+        answer.authorize_remove(db, USER_PID).await.is_ok(),
+        "FullAccess moderates"
+    );
+
+    let answer = comments::ActiveModel::edit(
+        db,
+        answer,
+        &UpdateParams {
+            body: "Yes, done".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(answer.edited_at.is_some());
+
+    comments::ActiveModel::remove(db, question).await.unwrap();
+
+    let thread = comments::Model::list_for_task(db, task.id).await.unwrap();
+    assert_eq!(thread.len(), 2, "Removed comments with replies are kept");
+    assert!(thread[0].is_removed());
+    assert!(thread[0].body.is_empty());
+
+    assert!(
+        comments::Model::add(db, OTHER_PID, task.id, &comment("Hm", Some(thread[0].id)))
+            .await
+            .is_err(),
+        "Removed comments take no replies"
+    );
+
+    comments::ActiveModel::remove(db, answer).await.unwrap();
+    let thread = comments::Model::list_for_task(db, task.id).await.unwrap();
+    assert_eq!(thread.len(), 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn moderators_remove_but_do_not_edit_comments_of_others() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Essay").await;
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::GrantParams {
+            email: OTHER_EMAIL.to_string(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let first = comments::Model::add(db, OTHER_PID, task.id, &comment("First!", None))
+        .await
+        .unwrap();
+
+    assert!(
+        tasks::Model::authorize(db, USER_PID, task.id, Action::ModerateComments)
+            .await
+            .is_ok()
+    );
+    assert!(
+        first.authorize_edit(db, USER_PID).await.is_err(),
+        "Moderators can not put words into the mouth of the author"
+    );
+    assert!(first.authorize_remove(db, USER_PID).await.is_ok());
+    assert!(first.authorize_edit(db, OTHER_PID).await.is_ok());
+}
@@ -0,0 +1,91 @@
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        accesses, group_accesses, groups,
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
+};
+
+use crate::fixtures::{add_task, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn group_grants_raise_effective_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Class work").await;
+    let student = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::GrantParams {
+            email: student.email.clone(),
+            accesslevel: AccessLevelEnum::View,
+            starts_at: None,
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let class = groups::Model::add(
+        db,
+        USER_PID,
+        &groups::CreateParams {
+            name: "Class".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    groups::ActiveModel::add_members(
+        db,
+        &class,
+        &groups::AddMembersParams {
+            emails: vec![student.email.clone()],
+        },
+    )
+    .await
+    .unwrap();
+
+    group_accesses::Model::grant_access(
+        db,
+        USER_PID,
+        task.id,
+        &group_accesses::GrantParams {
+            group_id: class.id,
+            accesslevel: AccessLevelEnum::Edit,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        tasks::Model::effective_access(db, student.id, task.id)
+            .await
+            .unwrap(),
+        Some(AccessLevelEnum::Edit)
+    );
+
+    group_accesses::ActiveModel::deny_access(
+        db,
+        USER_PID,
+        task.id,
+        &group_accesses::DenyParams { group_id: class.id },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        tasks::Model::effective_access(db, student.id, task.id)
+            .await
+            .unwrap(),
+        Some(AccessLevelEnum::View)
+    );
+}
@@ -1,8 +1,14 @@
 mod users;
 
 mod accesses;
+mod audit_events;
+mod comments;
+mod groups;
 mod roles;
+mod solutions;
 mod tasks;
+mod templates;
+mod webhooks;
 
 
 mod attachments;
\ No newline at end of file
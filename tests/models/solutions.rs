@@ -0,0 +1,124 @@
+use loco_rs::{model::ModelError, testing::prelude::*};
+use sea_orm::IntoActiveModel;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        solutions::{self, ReviewParams, SolutionAddParams, SolutionStatusEnum},
+        tasks::AccessLevelEnum,
+    },
+};
+
+use crate::fixtures::{add_task, grant, OTHER_EMAIL, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn can_add_and_review_solution() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Homework").await;
+    grant(
+        db,
+        USER_PID,
+        task.id,
+        OTHER_EMAIL,
+        AccessLevelEnum::AddSolution,
+    )
+    .await;
+
+    let (solution, files) = solutions::Model::add_solution(
+        db,
+        OTHER_PID,
+        task.id,
+        SolutionAddParams {
+            body: "My answer".to_string(),
+            file_names: vec!["answer.txt".to_string(), "notes.md".to_string()],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(solution.status, SolutionStatusEnum::Pending);
+    assert_eq!(files.len(), 2);
+    assert_eq!(
+        files[0].path().to_string_lossy(),
+        format!("solutions/{}/answer.txt", solution.id)
+    );
+
+    let listed = solutions::Model::list_for_task(db, task.id).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].1.len(), 2);
+
+    assert_eq!(
+        solution.find_file(db, "notes.md").await.unwrap().id,
+        files[1].id
+    );
+    assert!(matches!(
+        solution.find_file(db, "missing.md").await,
+        Err(ModelError::EntityNotFound)
+    ));
+
+    let res = solution
+        .clone()
+        .into_active_model()
+        .review(
+            db,
+            ReviewParams {
+                status: SolutionStatusEnum::Pending,
+            },
+        )
+        .await;
+    assert!(matches!(res, Err(ModelError::Message(_))));
+
+    let reviewed = solution
+        .into_active_model()
+        .review(
+            db,
+            ReviewParams {
+                status: SolutionStatusEnum::Rejected,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(reviewed.status, SolutionStatusEnum::Rejected);
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_add_solution_with_bad_file_names() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Homework").await;
+    grant(
+        db,
+        USER_PID,
+        task.id,
+        OTHER_EMAIL,
+        AccessLevelEnum::AddSolution,
+    )
+    .await;
+
+    for file_names in [
+        vec!["../7/answer.txt".to_string()],
+        vec!["..".to_string()],
+        vec!["answer.txt".to_string(), "answer.txt".to_string()],
+    ] {
+        let res = solutions::Model::add_solution(
+            db,
+            OTHER_PID,
+            task.id,
+            SolutionAddParams {
+                body: "My answer".to_string(),
+                file_names,
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(ModelError::Message(_))));
+    }
+
+    let listed = solutions::Model::list_for_task(db, task.id).await.unwrap();
+    assert!(listed.is_empty(), "Rejected Solutions are not stored");
+}
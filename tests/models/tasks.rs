@@ -1,6 +1,28 @@
-use task_hub::app::App;
-use loco_rs::testing::prelude::*;
+use std::collections::BTreeMap;
+
+use loco_rs::{model::ModelError, testing::prelude::*, Error};
+use rstest::rstest;
+use sea_orm::{sea_query::Expr, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
 use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::{
+        archive::{AccessEntry, Archive},
+        policy::Action,
+    },
+    models::{
+        _entities::attachments::Column as AttachmentColumn,
+        accesses,
+        attachments::{self, AttachmentAddParams, AttachmentTypeEnum},
+        tasks::{
+            self, highlight, like_pattern, mark_headline, AccessLevelEnum, CreateParams,
+            MoveParams, TaskStatusEnum, TaskVisibilityEnum, TransferParams,
+        },
+        users,
+    },
+};
+
+use crate::fixtures::{add_task, create_params, grant, OTHER_EMAIL, OTHER_PID, USER_PID};
 
 macro_rules! configure_insta {
     ($($expr:expr),*) => {
@@ -29,3 +51,477 @@ async fn test_model() {
     // snapshot the result:
     // assert_debug_snapshot!(item);
 }
+
+#[tokio::test]
+#[serial]
+async fn subtasks_inherit_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let course = add_task(db, USER_PID, "Course").await;
+    let module = tasks::Model::add_child(db, USER_PID, course.id, create_params("Module"))
+        .await
+        .unwrap();
+    let exercise = tasks::Model::add_child(db, USER_PID, module.id, create_params("Exercise"))
+        .await
+        .unwrap();
+
+    assert_eq!(exercise.parent_id, Some(module.id));
+    assert!(
+        tasks::Model::has_access(db, USER_PID, exercise.id, vec![AccessLevelEnum::FullAccess])
+            .await
+            .is_ok()
+    );
+
+    let tree = tasks::Model::tree(db, course.id, 1).await.unwrap();
+    assert_eq!(tree.children.len(), 1);
+    assert!(tree.children[0].children.is_empty());
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_move_task_under_its_subtask() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let course = add_task(db, USER_PID, "Course").await;
+    let module = tasks::Model::add_child(db, USER_PID, course.id, create_params("Module"))
+        .await
+        .unwrap();
+
+    let res = tasks::ActiveModel::move_to(
+        db,
+        USER_PID,
+        course.id,
+        MoveParams {
+            parent_id: Some(module.id),
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    let module =
+        tasks::ActiveModel::move_to(db, USER_PID, module.id, MoveParams { parent_id: None })
+            .await
+            .unwrap();
+    assert_eq!(module.parent_id, None);
+}
+
+#[rstest]
+#[case("Graph traversal", "graph", "<mark>Graph</mark> traversal")]
+#[case(
+    "Use <b>BFS</b> on the graph & stop",
+    "bfs",
+    "Use &lt;b&gt;<mark>BFS</mark>&lt;/b&gt; on the graph &amp; stop"
+)]
+#[case(
+    "<script>alert(1)</script>",
+    "missing",
+    "&lt;script&gt;alert(1)&lt;/script&gt;"
+)]
+#[case(
+    "Übung zur Graphentheorie",
+    "graph",
+    "Übung zur <mark>Graph</mark>entheorie"
+)]
+#[case("İstanbul graph", "graph", "İstanbul graph")]
+fn can_highlight_like_matches(#[case] text: &str, #[case] term: &str, #[case] snippet: &str) {
+    assert_eq!(highlight(text, term), snippet);
+}
+
+#[rstest]
+#[case("graph", "%graph%")]
+#[case("100%", "%100\\%%")]
+#[case("snake_case", "%snake\\_case%")]
+#[case("C:\\temp", "%C:\\\\temp%")]
+fn can_escape_like_wildcards(#[case] query: &str, #[case] pattern: &str) {
+    assert_eq!(like_pattern(query), pattern);
+}
+
+#[test]
+fn can_mark_headline() {
+    assert_eq!(
+        mark_headline("a <i>\u{E000}graph\u{E001}</i> walk"),
+        "a &lt;i&gt;<mark>graph</mark>&lt;/i&gt; walk"
+    );
+}
+
+#[rstest]
+#[case(
+    TaskStatusEnum::Draft,
+    TaskStatusEnum::Open,
+    Some(AccessLevelEnum::Edit)
+)]
+#[case(
+    TaskStatusEnum::Open,
+    TaskStatusEnum::InProgress,
+    Some(AccessLevelEnum::AddSolution)
+)]
+#[case(
+    TaskStatusEnum::InProgress,
+    TaskStatusEnum::Review,
+    Some(AccessLevelEnum::AddSolution)
+)]
+#[case(
+    TaskStatusEnum::Review,
+    TaskStatusEnum::Done,
+    Some(AccessLevelEnum::Edit)
+)]
+#[case(
+    TaskStatusEnum::Done,
+    TaskStatusEnum::Archived,
+    Some(AccessLevelEnum::FullAccess)
+)]
+#[case(TaskStatusEnum::Draft, TaskStatusEnum::Done, None)]
+#[case(TaskStatusEnum::Archived, TaskStatusEnum::InProgress, None)]
+#[case(TaskStatusEnum::Open, TaskStatusEnum::Open, None)]
+fn can_check_status_transitions(
+    #[case] from: TaskStatusEnum,
+    #[case] to: TaskStatusEnum,
+    #[case] lowest: Option<AccessLevelEnum>,
+) {
+    let levels = tasks::allowed_transition(from, to).map(Action::levels);
+
+    assert_eq!(levels.is_some(), lowest.is_some(), "{from:?} -> {to:?}");
+    if let (Some(levels), Some(lowest)) = (levels, lowest) {
+        assert!(levels.contains(&lowest), "{from:?} -> {to:?}");
+        assert!(
+            !levels.contains(&AccessLevelEnum::View),
+            "{from:?} -> {to:?}"
+        );
+    }
+}
+
+const LEVELS: [AccessLevelEnum; 5] = [
+    AccessLevelEnum::View,
+    AccessLevelEnum::AddSolution,
+    AccessLevelEnum::Edit,
+    AccessLevelEnum::AddUser,
+    AccessLevelEnum::FullAccess,
+];
+
+// View, AddSolution, Edit, AddUser, FullAccess
+const POLICY: [(Action, [bool; 5]); 16] = [
+    (Action::ReadTask, [true, true, true, true, true]),
+    (Action::EditTask, [false, false, true, true, true]),
+    (Action::DeleteTask, [false, false, false, false, true]),
+    (Action::AddSubtask, [false, false, true, false, true]),
+    (Action::MoveTask, [false, false, false, false, true]),
+    (Action::ViewHistory, [false, false, false, false, true]),
+    (Action::ManageAccess, [false, false, false, true, true]),
+    (Action::UploadFile, [false, false, true, true, true]),
+    (Action::EditAttachment, [false, false, true, true, true]),
+    (Action::SubmitSolution, [false, true, false, false, false]),
+    (Action::ReviewSolutions, [false, false, true, false, true]),
+    (Action::StartWork, [false, true, true, false, true]),
+    (Action::ReviewWork, [false, false, true, false, true]),
+    (Action::ArchiveTask, [false, false, false, false, true]),
+    (Action::AddComment, [true, true, true, true, true]),
+    (Action::ModerateComments, [false, false, false, false, true]),
+];
+
+#[test]
+fn can_check_policy() {
+    assert_eq!(POLICY.map(|(action, _)| action), Action::ALL);
+
+    for (action, allowed) in POLICY {
+        for (level, allowed) in LEVELS.into_iter().zip(allowed) {
+            assert_eq!(action.allows(level), allowed, "{action:?} with {level:?}");
+        }
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn moving_to_root_keeps_the_owner() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let course = add_task(db, USER_PID, "Course").await;
+    let module = tasks::Model::add_child(db, USER_PID, course.id, create_params("Module"))
+        .await
+        .unwrap();
+    grant(
+        db,
+        USER_PID,
+        course.id,
+        OTHER_EMAIL,
+        AccessLevelEnum::FullAccess,
+    )
+    .await;
+    let owner = users::Model::find_by_pid(db, USER_PID).await.unwrap();
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    let moved =
+        tasks::ActiveModel::move_to(db, OTHER_PID, module.id, MoveParams { parent_id: None })
+            .await
+            .unwrap();
+    assert_eq!(
+        moved.owner_id,
+        Some(owner.id),
+        "Ownership only changes hands through a transfer"
+    );
+
+    for user_id in [owner.id, other.id] {
+        assert_eq!(
+            tasks::Model::effective_access(db, user_id, moved.id)
+                .await
+                .unwrap(),
+            Some(AccessLevelEnum::FullAccess)
+        );
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn transfer_waits_for_confirmation() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Thesis").await;
+    let recipient = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    let pending = tasks::ActiveModel::transfer(
+        db,
+        USER_PID,
+        task,
+        &TransferParams {
+            email: recipient.email.clone(),
+            require_confirmation: true,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(pending.pending_owner_id, Some(recipient.id));
+    assert_ne!(pending.owner_id, Some(recipient.id));
+
+    assert!(
+        tasks::ActiveModel::accept_transfer(db, USER_PID, pending.id)
+            .await
+            .is_err()
+    );
+
+    let transferred = tasks::ActiveModel::accept_transfer(db, OTHER_PID, pending.id)
+        .await
+        .unwrap();
+    assert_eq!(transferred.owner_id, Some(recipient.id));
+    assert_eq!(transferred.pending_owner_id, None);
+    assert_eq!(
+        accesses::Model::find_task_owner(db, transferred.id)
+            .await
+            .unwrap()
+            .id,
+        recipient.id
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn fork_copies_public_task() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let exercise = tasks::Model::add(
+        db,
+        USER_PID,
+        CreateParams {
+            visibility: Some(TaskVisibilityEnum::Public),
+            ..create_params("Exercise")
+        },
+    )
+    .await
+    .unwrap();
+
+    for (attachment_type, data) in [
+        (AttachmentTypeEnum::Text, "Solve it"),
+        (AttachmentTypeEnum::File, "sheet.pdf"),
+    ] {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            exercise.id,
+            AttachmentAddParams {
+                attachment_type,
+                data: data.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let fork = tasks::Model::fork(db, OTHER_PID, exercise.id)
+        .await
+        .unwrap();
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    assert_eq!(fork.task.name, exercise.name);
+    assert_eq!(fork.task.visibility, TaskVisibilityEnum::Private);
+    assert_eq!(fork.task.forked_from_id, Some(exercise.id));
+    assert_eq!(fork.task.owner_id, Some(other.id));
+
+    let copies = attachments::Model::list_attachments(db, fork.task.id)
+        .await
+        .unwrap();
+    assert_eq!(copies.len(), 2);
+
+    assert_eq!(fork.files.len(), 1, "Only File contents need copying");
+    let (original, copy) = &fork.files[0];
+    assert_eq!(original.task_id, exercise.id);
+    assert_eq!(copy.task_id, fork.task.id);
+    assert_eq!(copy.data, "sheet.pdf");
+    assert_ne!(copy.id, original.id);
+
+    assert!(
+        tasks::Model::fork(db, USER_PID, fork.task.id)
+            .await
+            .is_err(),
+        "Private Tasks can not be forked without access"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn fork_rejects_invalid_attachment_rows() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let exercise = add_task(db, USER_PID, "Exercise").await;
+    let progress = attachments::Model::add_attachment(
+        db,
+        USER_PID,
+        exercise.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::Progress,
+            data: "50".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    // rows written before the payloads were validated skip the model hooks
+    attachments::Entity::update_many()
+        .col_expr(AttachmentColumn::Data, Expr::value("halfway"))
+        .filter(AttachmentColumn::Id.eq(progress.id))
+        .exec(db)
+        .await
+        .unwrap();
+
+    let tasks_before = tasks::Entity::find().count(db).await.unwrap();
+
+    let res = tasks::Model::fork(db, USER_PID, exercise.id).await;
+    assert!(matches!(res, Err(Error::BadRequest(_))));
+    assert_eq!(
+        tasks::Entity::find().count(db).await.unwrap(),
+        tasks_before,
+        "No half-copied fork is left"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn export_round_trips_through_archive() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let exercise = add_task(db, USER_PID, "Exercise").await;
+
+    for (attachment_type, data) in [
+        (AttachmentTypeEnum::Text, "Solve it"),
+        (AttachmentTypeEnum::File, "sheet.pdf"),
+    ] {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            exercise.id,
+            AttachmentAddParams {
+                attachment_type,
+                data: data.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let without_accesses = tasks::Model::export(db, exercise.id, false).await.unwrap();
+    assert!(without_accesses.manifest.accesses.is_none());
+
+    let export = tasks::Model::export(db, exercise.id, true).await.unwrap();
+    assert_eq!(export.manifest.task.name, "Exercise");
+    assert_eq!(export.manifest.attachments.len(), 2);
+    assert_eq!(export.files.len(), 1, "Only File contents are archived");
+
+    let mut manifest = export.manifest;
+    let owner = users::Model::find_by_pid(db, USER_PID).await.unwrap();
+    manifest.accesses = Some(vec![AccessEntry {
+        email: owner.email.clone(),
+        accesslevel: AccessLevelEnum::FullAccess,
+        starts_at: None,
+        expires_at: None,
+    }]);
+    let files = export
+        .files
+        .into_iter()
+        .map(|(entry, _)| (entry, b"%PDF".to_vec()))
+        .collect::<BTreeMap<_, _>>();
+
+    let bytes = Archive {
+        manifest,
+        files: files.clone(),
+    }
+    .write()
+    .unwrap();
+    let archive = Archive::read(&bytes).unwrap();
+    assert_eq!(archive.files, files);
+
+    let import = tasks::Model::import(db, OTHER_PID, &archive.manifest)
+        .await
+        .unwrap();
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    assert_ne!(import.task.id, exercise.id);
+    assert_eq!(import.task.name, "Exercise");
+    assert_eq!(import.task.owner_id, Some(other.id));
+    assert_eq!(import.skipped_emails, vec![owner.email]);
+    assert_eq!(
+        tasks::Model::effective_access(db, owner.id, import.task.id)
+            .await
+            .unwrap(),
+        None,
+        "Archived Accesses are not granted"
+    );
+    assert_eq!(
+        attachments::Model::list_attachments(db, import.task.id)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(import.files.len(), 1);
+    assert_eq!(import.files[0].1.data, "sheet.pdf");
+    assert!(archive.files.contains_key(&import.files[0].0));
+
+    let mut missing_file = Archive::read(&bytes).unwrap();
+    missing_file.files.clear();
+    assert!(matches!(
+        Archive::read(&missing_file.write().unwrap()),
+        Err(ModelError::Message(_))
+    ));
+
+    let mut escaping = archive.manifest;
+    for attachment in &mut escaping.attachments {
+        if attachment.attachment_type == AttachmentTypeEnum::File {
+            attachment.data = "../sheet.pdf".to_string();
+        }
+    }
+    assert!(matches!(
+        tasks::Model::import(db, OTHER_PID, &escaping).await,
+        Err(ModelError::Message(_))
+    ));
+}
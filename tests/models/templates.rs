@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use loco_rs::{model::ModelError, testing::prelude::*};
+use sea_orm::{EntityTrait, PaginatorTrait};
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        attachments::{self, AttachmentAddParams, AttachmentData, AttachmentTypeEnum},
+        tasks,
+        templates::{self, render, variables, CreateParams, InstantiateParams},
+    },
+};
+
+use crate::fixtures::{add_task, OTHER_PID, USER_PID};
+
+#[test]
+fn can_render_variables() {
+    let text = "Week {{ week }} of {{course}}, {{week}} again and {{missing}}";
+
+    assert_eq!(
+        variables(text).into_iter().collect::<Vec<_>>(),
+        vec![
+            "course".to_string(),
+            "missing".to_string(),
+            "week".to_string()
+        ]
+    );
+
+    let values = BTreeMap::from([
+        ("week".to_string(), "3".to_string()),
+        ("course".to_string(), "Algebra".to_string()),
+    ]);
+    assert_eq!(
+        render(text, &values),
+        "Week 3 of Algebra, 3 again and {{missing}}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn can_instantiate_template() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Homework").await;
+
+    for (attachment_type, data) in [
+        (AttachmentTypeEnum::Description, "Week {{week}}".to_string()),
+        (
+            AttachmentTypeEnum::DueDate,
+            (task.created_at.with_timezone(&Utc) + Duration::days(2)).to_rfc3339(),
+        ),
+    ] {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            task.id,
+            AttachmentAddParams {
+                attachment_type,
+                data,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let saved = templates::Model::create_from_task(
+        db,
+        USER_PID,
+        &CreateParams {
+            task_id: task.id,
+            name: Some("Homework {{week}}".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(saved.attachments.len(), 2);
+    assert!(saved.files.is_empty());
+    assert_eq!(
+        saved
+            .template
+            .variables(&saved.attachments)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec!["week".to_string()]
+    );
+
+    assert!(
+        templates::Model::load_owned(db, OTHER_PID, saved.template.id)
+            .await
+            .is_err()
+    );
+
+    let missing = saved
+        .template
+        .instantiate(db, USER_PID, &InstantiateParams::default())
+        .await;
+    assert!(matches!(missing, Err(ModelError::Message(_))));
+
+    let instance = saved
+        .template
+        .instantiate(
+            db,
+            USER_PID,
+            &InstantiateParams {
+                variables: BTreeMap::from([("week".to_string(), "3".to_string())]),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(instance.task.name, "Homework 3");
+    assert_ne!(instance.task.id, task.id);
+
+    let created = attachments::Model::list_attachments(db, instance.task.id)
+        .await
+        .unwrap();
+    assert_eq!(created.len(), 2);
+
+    let description = created
+        .iter()
+        .find(|attachment| attachment.attachment_type == AttachmentTypeEnum::Description)
+        .unwrap();
+    assert_eq!(description.data, "Week 3");
+
+    let due_date = created
+        .iter()
+        .find(|attachment| attachment.attachment_type == AttachmentTypeEnum::DueDate)
+        .unwrap();
+    let AttachmentData::DueDate(due_at) = AttachmentData::from_model(due_date) else {
+        panic!("due date expected");
+    };
+    let offset = due_at - Utc::now();
+    assert!(offset > Duration::days(2) - Duration::minutes(1));
+    assert!(offset <= Duration::days(2));
+}
+
+#[tokio::test]
+#[serial]
+async fn rejected_attachment_leaves_no_task() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Homework").await;
+    for (attachment_type, data) in [
+        (AttachmentTypeEnum::Text, "Read chapter 1"),
+        (AttachmentTypeEnum::Description, "{{note}}"),
+    ] {
+        attachments::Model::add_attachment(
+            db,
+            USER_PID,
+            task.id,
+            AttachmentAddParams {
+                attachment_type,
+                data: data.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let saved = templates::Model::create_from_task(
+        db,
+        USER_PID,
+        &CreateParams {
+            task_id: task.id,
+            name: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let tasks_before = tasks::Entity::find().count(db).await.unwrap();
+
+    let rejected = saved
+        .template
+        .instantiate(
+            db,
+            USER_PID,
+            &InstantiateParams {
+                variables: BTreeMap::from([("note".to_string(), " ".to_string())]),
+            },
+        )
+        .await;
+    assert!(matches!(rejected, Err(ModelError::Message(_))));
+    assert_eq!(
+        tasks::Entity::find().count(db).await.unwrap(),
+        tasks_before,
+        "Nothing of the Task is kept"
+    );
+}
@@ -358,3 +358,53 @@ async fn magic_link() {
         "Magic link expiration exceeds expected maximum expiration time"
     );
 }
+
+#[tokio::test]
+#[serial]
+async fn can_rotate_calendar_token() {
+    let boot = boot_test::<App>()
+        .await
+        .expect("Failed to boot test application");
+    seed::<App>(&boot.app_context)
+        .await
+        .expect("Failed to seed database");
+    let db = &boot.app_context.db;
+
+    let user = Model::find_by_pid(db, "11111111-1111-1111-1111-111111111111")
+        .await
+        .expect("Failed to find user by PID");
+    assert!(user.calendar_token.is_none());
+
+    let user = user
+        .into_active_model()
+        .rotate_calendar_token(db)
+        .await
+        .expect("Failed to rotate calendar token");
+    let first = user.calendar_token.clone().expect("Token should be set");
+    assert_eq!(first.len(), users::CALENDAR_TOKEN_LENGTH);
+    assert_eq!(
+        Model::find_by_calendar_token(db, &first)
+            .await
+            .expect("Failed to find user by calendar token")
+            .id,
+        user.id
+    );
+
+    let user = user
+        .into_active_model()
+        .rotate_calendar_token(db)
+        .await
+        .expect("Failed to rotate calendar token");
+    let second = user.calendar_token.clone().expect("Token should be set");
+    assert_ne!(first, second);
+    assert!(
+        Model::find_by_calendar_token(db, &first).await.is_err(),
+        "Rotated tokens should no longer be valid"
+    );
+
+    user.into_active_model()
+        .clear_calendar_token(db)
+        .await
+        .expect("Failed to clear calendar token");
+    assert!(Model::find_by_calendar_token(db, &second).await.is_err());
+}
@@ -0,0 +1,69 @@
+use std::net::IpAddr;
+
+use loco_rs::{model::ModelError, testing::prelude::*};
+use rstest::rstest;
+use serial_test::serial;
+use task_hub::{app::App, models::webhooks};
+
+use crate::fixtures::USER_PID;
+
+#[rstest]
+#[case("93.184.216.34", true)]
+#[case("2606:2800:220:1:248:1893:25c8:1946", true)]
+#[case("127.0.0.1", false)]
+#[case("10.1.2.3", false)]
+#[case("172.16.0.1", false)]
+#[case("192.168.1.1", false)]
+#[case("169.254.169.254", false)]
+#[case("100.100.100.200", false)]
+#[case("0.0.0.0", false)]
+#[case("::1", false)]
+#[case("fe80::1", false)]
+#[case("fd00:ec2::254", false)]
+#[case("::ffff:127.0.0.1", false)]
+fn can_tell_public_addresses(#[case] ip: &str, #[case] public: bool) {
+    assert_eq!(
+        webhooks::is_public_address(ip.parse::<IpAddr>().unwrap()),
+        public,
+        "{ip}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_register_hook_to_internal_host() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    for url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://169.254.169.254/latest/meta-data/",
+        "http://[::1]/hook",
+        "ftp://93.184.216.34/hook",
+    ] {
+        let res = webhooks::Model::add(
+            db,
+            USER_PID,
+            &webhooks::CreateParams {
+                url: url.to_string(),
+                task_id: None,
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(ModelError::Message(_))), "{url}");
+    }
+
+    let hook = webhooks::Model::add(
+        db,
+        USER_PID,
+        &webhooks::CreateParams {
+            url: "https://93.184.216.34/hook".to_string(),
+            task_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(hook.enabled);
+}
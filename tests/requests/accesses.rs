@@ -0,0 +1,90 @@
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        invitations,
+        tasks::{self, AccessLevelEnum},
+        users,
+    },
+};
+
+use super::prepare_data;
+use crate::fixtures::add_task;
+
+const INVITEE_EMAIL: &str = "invitee@example.com";
+
+#[tokio::test]
+#[serial]
+async fn invited_email_gets_access_once_verified() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let owner = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&owner.token);
+
+        let task = add_task(&ctx.db, &owner.user.pid.to_string(), "Homework").await;
+
+        let res = request
+            .post(&format!("/api/tasks/access/{}", task.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&serde_json::json!({
+                "email": INVITEE_EMAIL,
+                "accesslevel": "Edit",
+            }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .get(&format!("/api/tasks/access/{}/invitations", task.id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.json::<serde_json::Value>()[0]["email"], INVITEE_EMAIL);
+
+        request
+            .post("/api/auth/register")
+            .json(&serde_json::json!({
+                "name": "invitee",
+                "email": INVITEE_EMAIL,
+                "password": "12341234",
+            }))
+            .await;
+
+        let invitee = users::Model::find_by_email(&ctx.db, INVITEE_EMAIL)
+            .await
+            .unwrap();
+        assert_eq!(
+            tasks::Model::effective_access(&ctx.db, invitee.id, task.id)
+                .await
+                .unwrap(),
+            None,
+            "Registering the email without verifying it gives no access"
+        );
+        assert_eq!(
+            invitations::Model::list_for_task(&ctx.db, task.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        request
+            .get(&format!(
+                "/api/auth/verify/{}",
+                invitee.email_verification_token.unwrap()
+            ))
+            .await;
+
+        assert_eq!(
+            tasks::Model::effective_access(&ctx.db, invitee.id, task.id)
+                .await
+                .unwrap(),
+            Some(AccessLevelEnum::Edit)
+        );
+        assert!(invitations::Model::list_for_task(&ctx.db, task.id)
+            .await
+            .unwrap()
+            .is_empty());
+    })
+    .await;
+}
@@ -1,6 +1,13 @@
-use task_hub::app::App;
+use std::path::PathBuf;
+
+use axum::http::{header, HeaderValue};
+use axum_test::multipart::{MultipartForm, Part};
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
+use task_hub::{app::App, common::storage::StoredFiles};
+
+use super::prepare_data;
+use crate::fixtures::{add_task, USER_PID};
 
 #[tokio::test]
 #[serial]
@@ -15,3 +22,161 @@ async fn can_get_attachments() {
     .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn can_not_download_attachment_without_auth() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/api/tasks/attachments/1/content").await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_download_attachment_ranges() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Reading").await;
+        let (auth_key, auth_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+
+        let res = request
+            .post(&format!("/api/tasks/attachments/{}", task.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .multipart(
+                MultipartForm::new()
+                    .add_text("attachment_type", "File")
+                    .add_text("data", "")
+                    .add_part(
+                        "file",
+                        Part::bytes(&b"0123456789"[..]).file_name("digits.txt"),
+                    ),
+            )
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let content_url = format!(
+            "/api/tasks/attachments/{}/content",
+            res.json::<serde_json::Value>()["id"]
+        );
+
+        let res = request
+            .get(&content_url)
+            .add_header(auth_key.clone(), auth_value.clone())
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.text(), "0123456789");
+        let etag = res.header(header::ETAG);
+
+        let res = request
+            .get(&content_url)
+            .add_header(auth_key.clone(), auth_value.clone())
+            .add_header(header::IF_NONE_MATCH, etag.clone())
+            .await;
+        assert_eq!(res.status_code(), 304);
+        assert!(res.as_bytes().is_empty());
+
+        for (range, status, body, content_range) in [
+            ("bytes=2-4", 206, "234", "bytes 2-4/10"),
+            ("bytes=-3", 206, "789", "bytes 7-9/10"),
+            ("bytes=8-", 206, "89", "bytes 8-9/10"),
+            ("bytes=10-12", 416, "", "bytes */10"),
+        ] {
+            let res = request
+                .get(&content_url)
+                .add_header(auth_key.clone(), auth_value.clone())
+                .add_header(header::RANGE, HeaderValue::from_static(range))
+                .await;
+            assert_eq!(res.status_code(), status, "{range}");
+            assert_eq!(res.text(), body, "{range}");
+            assert_eq!(res.header(header::CONTENT_RANGE), content_range, "{range}");
+        }
+
+        // a Range for an older version of the file is ignored
+        let res = request
+            .get(&content_url)
+            .add_header(auth_key, auth_value)
+            .add_header(header::RANGE, HeaderValue::from_static("bytes=2-4"))
+            .add_header(header::IF_RANGE, HeaderValue::from_static("\"stale\""))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.text(), "0123456789");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn replaced_file_is_removed_after_update() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Reading").await;
+        let (auth_key, auth_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+
+        let res = request
+            .post(&format!("/api/tasks/attachments/{}", task.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .multipart(
+                MultipartForm::new()
+                    .add_text("attachment_type", "File")
+                    .add_text("data", "")
+                    .add_part(
+                        "file",
+                        Part::bytes(&b"0123456789"[..]).file_name("digits.txt"),
+                    ),
+            )
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+
+        let res = request
+            .patch(&format!("/api/tasks/attachments/{id}"))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .multipart(
+                MultipartForm::new()
+                    .add_text("data", "")
+                    .add_part("file", Part::bytes(&b"abc"[..]).file_name("letters.txt")),
+            )
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.json::<serde_json::Value>()["data"], "letters.txt");
+
+        let res = request
+            .get(&format!("/api/tasks/attachments/{id}/content"))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.text(), "abc");
+
+        let files = StoredFiles::from_context(&ctx).unwrap();
+        assert!(files
+            .size(&PathBuf::from(id.to_string()).join("digits.txt"))
+            .await
+            .is_err());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn invalid_attachment_data_is_reported_per_field() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Reading").await;
+        let (auth_key, auth_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+
+        let res = request
+            .post(&format!("/api/tasks/attachments/{}", task.id))
+            .add_header(auth_key, auth_value)
+            .multipart(
+                MultipartForm::new()
+                    .add_text("attachment_type", "Progress")
+                    .add_text("data", "101"),
+            )
+            .await;
+        assert_eq!(res.status_code(), 400);
+        assert_eq!(
+            res.json::<serde_json::Value>()["errors"]["data"][0]["code"],
+            "range"
+        );
+    })
+    .await;
+}
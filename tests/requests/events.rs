@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::events::{EventHub, LocalHub, Subscription, TaskEvent},
+    controllers::events::forward,
+    models::{accesses, tasks::AccessLevelEnum, users},
+};
+use tokio::sync::mpsc;
+
+use crate::fixtures::{add_task, grant, OTHER_EMAIL, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn lagging_stream_rechecks_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Lecture notes").await;
+    grant(db, USER_PID, task.id, OTHER_EMAIL, AccessLevelEnum::View).await;
+    let other = users::Model::find_by_pid(db, OTHER_PID).await.unwrap();
+
+    let hub = LocalHub::new(2);
+    let receiver = hub.subscribe();
+    let subscription = Subscription {
+        user_id: other.id,
+        task_ids: BTreeSet::from([task.id]),
+    };
+
+    // the revoking event is among the ones the stream falls behind on
+    accesses::ActiveModel::deny_access(
+        db,
+        USER_PID,
+        task.id,
+        accesses::DenyParams {
+            pid: OTHER_PID.to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    hub.publish(TaskEvent::AccessChanged {
+        task_id: task.id,
+        user_id: Some(other.id),
+    });
+    for _ in 0..3 {
+        hub.publish(TaskEvent::TaskUpdated { task_id: task.id });
+    }
+
+    let (sender, mut stream) = mpsc::channel(8);
+    forward(db.clone(), subscription, receiver, sender).await;
+
+    let event = stream.recv().await.unwrap();
+    assert!(format!("{event:?}").contains("access_revoked"));
+    assert!(
+        stream.recv().await.is_none(),
+        "Nothing of the Task is sent after access is gone"
+    );
+}
@@ -0,0 +1,97 @@
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{group_accesses, groups},
+};
+
+use super::prepare_data;
+use crate::fixtures::{add_task, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn can_add_group_members() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let owner = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&owner.token);
+
+        let res = request
+            .post("/api/groups/")
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&serde_json::json!({ "name": "Class 7B" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let group_id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+
+        let res = request
+            .post(&format!("/api/groups/{group_id}/members"))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&serde_json::json!({
+                "emails": ["user1@example.com", "user2@example.com"]
+            }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["members"].as_array().unwrap().len(), 2);
+
+        let res = request
+            .post(&format!("/api/groups/{group_id}/members"))
+            .add_header(auth_key, auth_value)
+            .json(&serde_json::json!({ "emails": ["nobody@example.com"] }))
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_only_grant_task_to_own_group() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Homework").await;
+        let (auth_key, auth_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+
+        let foreign = groups::Model::add(
+            &ctx.db,
+            OTHER_PID,
+            &groups::CreateParams {
+                name: "Someone else's class".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let res = request
+            .post(&format!("/api/tasks/access/{}/groups", task.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&serde_json::json!({ "group_id": foreign.id, "accesslevel": "View" }))
+            .await;
+        assert_eq!(res.status_code(), 401);
+        assert!(group_accesses::Model::list_for_task(&ctx.db, task.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let own = groups::Model::add(
+            &ctx.db,
+            USER_PID,
+            &groups::CreateParams {
+                name: "Class 7B".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let res = request
+            .post(&format!("/api/tasks/access/{}/groups", task.id))
+            .add_header(auth_key, auth_value)
+            .json(&serde_json::json!({ "group_id": own.id, "accesslevel": "View" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
@@ -1,7 +1,12 @@
 mod auth;
 mod prepare_data;
 
+pub mod accesses;
 pub mod roles;
+pub mod solutions;
 pub mod tasks;
 pub mod users;
-pub mod attachments;
\ No newline at end of file
+pub mod attachments;
+pub mod groups;
+pub mod payments;
+pub mod events;
@@ -0,0 +1,115 @@
+use axum::http::{HeaderName, HeaderValue};
+use loco_rs::testing::prelude::*;
+use sea_orm::EntityTrait;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::payments::{FakeProvider, SIGNATURE_HEADER},
+    models::{
+        purchases::{self, PurchaseStatusEnum},
+        tasks::{self, AccessLevelEnum, CreateParams, TaskVisibilityEnum},
+    },
+};
+
+use super::prepare_data;
+use crate::fixtures::{create_params, USER_PID};
+
+const WEBHOOK_SECRET: &str = "test-webhook-secret";
+
+#[tokio::test]
+#[serial]
+async fn can_purchase_paid_task() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let buyer = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&buyer.token);
+
+        let task = tasks::Model::add(
+            &ctx.db,
+            USER_PID,
+            CreateParams {
+                visibility: Some(TaskVisibilityEnum::Paid),
+                price_cents: Some(1999),
+                ..create_params("Course")
+            },
+        )
+        .await
+        .unwrap();
+
+        let res = request
+            .post(&format!("/api/payments/checkout/{}", task.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["amount_cents"], 1999);
+
+        let purchase_id = i32::try_from(body["purchase_id"].as_i64().unwrap()).unwrap();
+        let purchase = purchases::Entity::find_by_id(purchase_id)
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let payload = serde_json::to_vec(&serde_json::json!({
+            "status": "succeeded",
+            "reference": purchase.provider_ref.unwrap(),
+        }))
+        .unwrap();
+        let signature = FakeProvider::new(WEBHOOK_SECRET, "")
+            .sign(&payload)
+            .unwrap();
+
+        let res = request
+            .post("/api/payments/webhook")
+            .add_header(
+                HeaderName::from_static(SIGNATURE_HEADER),
+                HeaderValue::from_str(&signature).unwrap(),
+            )
+            .bytes(payload.into())
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let purchase = purchases::Entity::find_by_id(purchase_id)
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(purchase.status, PurchaseStatusEnum::Paid);
+
+        assert!(tasks::Model::has_access(
+            &ctx.db,
+            &buyer.user.pid.to_string(),
+            task.id,
+            vec![AccessLevelEnum::View]
+        )
+        .await
+        .is_ok());
+
+        // already entitled, nothing to buy twice
+        let res = request
+            .post(&format!("/api/payments/checkout/{}", task.id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 409);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_unsigned_webhook() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/payments/webhook")
+            .add_header(
+                HeaderName::from_static(SIGNATURE_HEADER),
+                HeaderValue::from_static("00"),
+            )
+            .json(&serde_json::json!({ "status": "succeeded", "reference": "fake_x" }))
+            .await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
@@ -50,6 +50,14 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
     }
 }
 
+/// Authorization header of a User of the seed data, signed without going through login
+pub async fn seed_user_header(ctx: &AppContext, pid: &str) -> (HeaderName, HeaderValue) {
+    let user = users::Model::find_by_pid(&ctx.db, pid).await.unwrap();
+    let jwt = ctx.config.get_jwt_config().unwrap();
+
+    auth_header(&user.generate_jwt(&jwt.secret, jwt.expiration).unwrap())
+}
+
 pub fn auth_header(token: &str) -> (HeaderName, HeaderValue) {
     let auth_header_value = HeaderValue::from_str(&format!("Bearer {}", &token)).unwrap();
 
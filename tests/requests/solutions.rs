@@ -0,0 +1,203 @@
+use axum_test::multipart::{MultipartForm, Part};
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+use task_hub::{app::App, models::tasks::AccessLevelEnum};
+
+use super::prepare_data;
+use crate::fixtures::{add_task, grant, OTHER_EMAIL, OTHER_PID, USER_PID};
+
+fn solution_form(body: &str, file_name: &str, content: &'static [u8]) -> MultipartForm {
+    MultipartForm::new()
+        .add_text("body", body)
+        .add_part("files", Part::bytes(content).file_name(file_name))
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_list_solutions_without_auth() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/api/tasks/solutions/1").await;
+        assert_eq!(res.status_code(), 401);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_submit_and_review_solution() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Homework").await;
+        grant(
+            &ctx.db,
+            USER_PID,
+            task.id,
+            OTHER_EMAIL,
+            AccessLevelEnum::AddSolution,
+        )
+        .await;
+
+        let (owner_key, owner_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+        let (student_key, student_value) = prepare_data::seed_user_header(&ctx, OTHER_PID).await;
+
+        let res = request
+            .post(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(owner_key.clone(), owner_value.clone())
+            .multipart(solution_form("Mine", "answer.txt", b"42"))
+            .await;
+        assert_eq!(
+            res.status_code(),
+            401,
+            "Only Users with AddSolution can submit"
+        );
+
+        let res = request
+            .post(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(student_key.clone(), student_value.clone())
+            .multipart(solution_form("My answer", "answer.txt", b"42"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let solution = res.json::<serde_json::Value>();
+        assert_eq!(solution["status"], "Pending");
+        assert_eq!(solution["files"], serde_json::json!(["answer.txt"]));
+        let solution_id = solution["id"].as_i64().unwrap();
+
+        let res = request
+            .get(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(owner_key.clone(), owner_value.clone())
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.json::<serde_json::Value>()[0]["id"], solution_id);
+
+        let res = request
+            .patch(&format!("/api/tasks/solutions/{solution_id}"))
+            .add_header(student_key.clone(), student_value.clone())
+            .json(&serde_json::json!({ "status": "Accepted" }))
+            .await;
+        assert_eq!(
+            res.status_code(),
+            401,
+            "Authors can not review their own Solution"
+        );
+
+        let res = request
+            .patch(&format!("/api/tasks/solutions/{solution_id}"))
+            .add_header(owner_key.clone(), owner_value.clone())
+            .json(&serde_json::json!({ "status": "Pending" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .patch(&format!("/api/tasks/solutions/{solution_id}"))
+            .add_header(owner_key, owner_value)
+            .json(&serde_json::json!({ "status": "Accepted" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.json::<serde_json::Value>()["status"], "Accepted");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_not_submit_solution_with_bad_file_names() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let task = add_task(&ctx.db, USER_PID, "Homework").await;
+        grant(
+            &ctx.db,
+            USER_PID,
+            task.id,
+            OTHER_EMAIL,
+            AccessLevelEnum::AddSolution,
+        )
+        .await;
+
+        let (student_key, student_value) = prepare_data::seed_user_header(&ctx, OTHER_PID).await;
+
+        let res = request
+            .post(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(student_key.clone(), student_value.clone())
+            .multipart(solution_form("My answer", "../7/answer.txt", b"42"))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .post(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(student_key, student_value)
+            .multipart(solution_form("My answer", "answer.txt", b"42").add_part(
+                "files",
+                Part::bytes(b"43".as_slice()).file_name("answer.txt"),
+            ))
+            .await;
+        assert_eq!(res.status_code(), 400, "File names must be unique");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn only_author_and_reviewers_see_solution() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let classmate = prepare_data::init_user_login(&request, &ctx).await;
+        let task = add_task(&ctx.db, USER_PID, "Homework").await;
+        for email in [OTHER_EMAIL, classmate.user.email.as_str()] {
+            grant(
+                &ctx.db,
+                USER_PID,
+                task.id,
+                email,
+                AccessLevelEnum::AddSolution,
+            )
+            .await;
+        }
+
+        let (owner_key, owner_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+        let (student_key, student_value) = prepare_data::seed_user_header(&ctx, OTHER_PID).await;
+        let (classmate_key, classmate_value) = prepare_data::auth_header(&classmate.token);
+
+        let res = request
+            .post(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(student_key.clone(), student_value.clone())
+            .multipart(solution_form("My answer", "answer.txt", b"42"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let solution_id = res.json::<serde_json::Value>()["id"].as_i64().unwrap();
+        let file_url = format!("/api/tasks/solutions/{solution_id}/files/answer.txt");
+
+        let res = request
+            .get(&format!("/api/tasks/solutions/{}", task.id))
+            .add_header(classmate_key.clone(), classmate_value.clone())
+            .await;
+        assert_eq!(
+            res.status_code(),
+            401,
+            "Other submitters can not list Solutions"
+        );
+
+        let res = request
+            .get(&file_url)
+            .add_header(classmate_key, classmate_value)
+            .await;
+        assert_eq!(res.status_code(), 401);
+
+        for (key, value) in [
+            (student_key, student_value),
+            (owner_key.clone(), owner_value.clone()),
+        ] {
+            let res = request.get(&file_url).add_header(key, value).await;
+            assert_eq!(res.status_code(), 200);
+            assert_eq!(res.as_bytes().as_ref(), b"42");
+        }
+
+        let res = request
+            .get(&format!(
+                "/api/tasks/solutions/{solution_id}/files/missing.txt"
+            ))
+            .add_header(owner_key, owner_value)
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
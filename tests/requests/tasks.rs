@@ -1,6 +1,15 @@
-use task_hub::app::App;
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        attachments::{self, AttachmentAddParams, AttachmentTypeEnum},
+        tasks::{self, CreateParams, TaskVisibilityEnum},
+    },
+};
+
+use super::prepare_data;
+use crate::fixtures::{add_task, create_params, OTHER_PID, USER_PID};
 
 #[tokio::test]
 #[serial]
@@ -14,3 +23,198 @@ async fn can_get_tasks() {
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+async fn can_list_tasks_page() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .get("/api/tasks/list?page=1&page_size=500&sort_by=name&order=asc")
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let body: serde_json::Value = res.json();
+        assert_eq!(body["page"], 1);
+        assert_eq!(body["page_size"], 100);
+        assert!(body["items"].is_array());
+        assert!(body["next_page"].is_null());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_search_tasks() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+
+        let public = tasks::Model::add(
+            &ctx.db,
+            USER_PID,
+            CreateParams {
+                visibility: Some(TaskVisibilityEnum::Public),
+                ..create_params("Graph traversal")
+            },
+        )
+        .await
+        .unwrap();
+        attachments::Model::add_attachment(
+            &ctx.db,
+            USER_PID,
+            public.id,
+            AttachmentAddParams {
+                attachment_type: AttachmentTypeEnum::Description,
+                data: "Walk the graph <script>alert(1)</script> breadth first".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let private = add_task(&ctx.db, USER_PID, "Graph coloring").await;
+        add_task(&ctx.db, OTHER_PID, "Graph theory notes").await;
+
+        let search = |query: &'static str| {
+            request
+                .post("/api/tasks/search")
+                .json(&serde_json::json!({ "query": query }))
+        };
+
+        let res = search("graph").await;
+        assert_eq!(res.status_code(), 200);
+        let body: serde_json::Value = res.json();
+        let items = body["items"].as_array().unwrap();
+        assert_eq!(items.len(), 1, "Anonymous users only find public Tasks");
+        assert_eq!(items[0]["id"], public.id);
+
+        let snippet = items[0]["snippet"].as_str().unwrap();
+        assert!(snippet.contains("<mark>"), "{snippet}");
+        assert!(!snippet.contains("<script"), "{snippet}");
+
+        let (auth_key, auth_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+        let res = search("graph").add_header(auth_key, auth_value).await;
+        let body: serde_json::Value = res.json();
+        let mut found = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["id"].as_i64().unwrap())
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        assert_eq!(found, vec![i64::from(public.id), i64::from(private.id)]);
+
+        let res = search("breadth").await;
+        let body: serde_json::Value = res.json();
+        assert_eq!(
+            body["items"][0]["id"], public.id,
+            "Descriptions are searched"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn search_matches_wildcards_literally() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+
+        for name in ["100% coverage", "1000 lines of code"] {
+            tasks::Model::add(
+                &ctx.db,
+                USER_PID,
+                CreateParams {
+                    visibility: Some(TaskVisibilityEnum::Public),
+                    ..create_params(name)
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let res = request
+            .post("/api/tasks/search")
+            .json(&serde_json::json!({ "query": "100%" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let body: serde_json::Value = res.json();
+        let items = body["items"].as_array().unwrap();
+        assert_eq!(items.len(), 1, "{body}");
+        assert_eq!(items[0]["name"], "100% coverage");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn private_task_is_not_found_without_access() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let viewer = prepare_data::init_user_login(&request, &ctx).await;
+        let (auth_key, auth_value) = prepare_data::auth_header(&viewer.token);
+
+        let hidden = add_task(&ctx.db, OTHER_PID, "Hidden").await;
+
+        let res = request.get(&format!("/api/tasks/{}", hidden.id)).await;
+        assert_eq!(res.status_code(), 404);
+
+        let res = request
+            .get(&format!("/api/tasks/{}", hidden.id))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .await;
+        assert_eq!(res.status_code(), 404);
+
+        let res = request
+            .post("/api/tasks/full")
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&serde_json::json!({ "task_id": hidden.id }))
+            .await;
+        assert_eq!(res.status_code(), 404);
+
+        let own = add_task(&ctx.db, &viewer.user.pid.to_string(), "Own").await;
+
+        let res = request
+            .get(&format!("/api/tasks/{}", own.id))
+            .add_header(auth_key, auth_value)
+            .await;
+        assert_eq!(res.status_code(), 200);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn status_of_private_task_is_not_revealed_without_access() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed::<App>(&ctx).await.unwrap();
+        let hidden = add_task(&ctx.db, OTHER_PID, "Hidden").await;
+        let status_url = format!("/api/tasks/{}/status", hidden.id);
+
+        let (viewer_key, viewer_value) = prepare_data::seed_user_header(&ctx, USER_PID).await;
+        let (owner_key, owner_value) = prepare_data::seed_user_header(&ctx, OTHER_PID).await;
+
+        for status in ["InProgress", "Open"] {
+            let res = request
+                .post(&status_url)
+                .add_header(viewer_key.clone(), viewer_value.clone())
+                .json(&serde_json::json!({ "status": status }))
+                .await;
+            assert_eq!(res.status_code(), 404, "{status}");
+            assert!(!res.text().contains("Draft"), "{status}");
+        }
+
+        let res = request
+            .post(&status_url)
+            .add_header(owner_key.clone(), owner_value.clone())
+            .json(&serde_json::json!({ "status": "InProgress" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .post(&status_url)
+            .add_header(owner_key, owner_value)
+            .json(&serde_json::json!({ "status": "Open" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        assert_eq!(res.json::<serde_json::Value>()["status"], "Open");
+    })
+    .await;
+}
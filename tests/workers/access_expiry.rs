@@ -0,0 +1,98 @@
+use chrono::{Duration, Utc};
+use loco_rs::{bgworker::BackgroundWorker, testing::prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, IntoActiveModel};
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::{
+        accesses::{self, GrantParams},
+        tasks::{self, AccessLevelEnum},
+    },
+    workers::access_expiry::{AccessExpiryWorker, AccessExpiryWorkerArgs},
+};
+
+use crate::fixtures::{add_task, OTHER_EMAIL, OTHER_PID, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn removes_expired_access() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Exam").await;
+
+    accesses::Model::grant_access(
+        &ctx.db,
+        USER_PID,
+        task.id,
+        GrantParams {
+            email: OTHER_EMAIL.to_string(),
+            accesslevel: AccessLevelEnum::Edit,
+            starts_at: Some((Utc::now() - Duration::hours(2)).into()),
+            expires_at: Some((Utc::now() - Duration::hours(1)).into()),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        tasks::Model::has_access(&ctx.db, OTHER_PID, task.id, vec![AccessLevelEnum::Edit])
+            .await
+            .is_err(),
+        "Expired access should not apply before the job runs"
+    );
+
+    AccessExpiryWorker::build(ctx)
+        .perform(AccessExpiryWorkerArgs::default())
+        .await
+        .unwrap();
+
+    let remaining = accesses::Model::list_for_task(&ctx.db, task.id)
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 1, "Only the owner's access should remain");
+
+    let deliveries = ctx.mailer.as_ref().unwrap().deliveries();
+    assert_eq!(deliveries.count, 1, "The owner should be notified once");
+}
+
+#[tokio::test]
+#[serial]
+async fn keeps_expired_full_access_of_owner() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Exam").await;
+
+    // written around `update_access`, which refuses a window on the owner's Access
+    let mut owner_access = accesses::Model::find_by_pid(&ctx.db, task.id, USER_PID)
+        .await
+        .unwrap()
+        .into_active_model();
+    owner_access.expires_at = Set(Some((Utc::now() - Duration::hours(1)).into()));
+    owner_access.update(&ctx.db).await.unwrap();
+
+    AccessExpiryWorker::build(ctx)
+        .perform(AccessExpiryWorkerArgs::default())
+        .await
+        .unwrap();
+
+    let kept = accesses::Model::find_by_pid(&ctx.db, task.id, USER_PID)
+        .await
+        .unwrap();
+    assert_eq!(kept.accesslevel, AccessLevelEnum::FullAccess);
+    assert_eq!(kept.expires_at, None);
+    assert!(tasks::Model::has_access(
+        &ctx.db,
+        USER_PID,
+        task.id,
+        vec![AccessLevelEnum::FullAccess]
+    )
+    .await
+    .is_ok());
+
+    let deliveries = ctx.mailer.as_ref().unwrap().deliveries();
+    assert_eq!(deliveries.count, 0, "Nothing expired, nobody is notified");
+}
@@ -0,0 +1,144 @@
+use chrono::{Duration, Utc};
+use loco_rs::{bgworker::BackgroundWorker, testing::prelude::*};
+use sea_orm::IntoActiveModel;
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    models::attachments::{self, AttachmentAddParams, AttachmentTypeEnum, AttachmentUpdateParams},
+    workers::due_reminder::{DueReminderWorker, DueReminderWorkerArgs},
+};
+
+use crate::fixtures::{add_task, USER_PID};
+
+#[tokio::test]
+#[serial]
+async fn sends_due_reminder_once() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Homework").await;
+
+    attachments::Model::add_attachment(
+        &ctx.db,
+        USER_PID,
+        task.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::DueDate,
+            data: (Utc::now() + Duration::hours(2)).to_rfc3339(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let worker = DueReminderWorker::build(ctx);
+    worker
+        .perform(DueReminderWorkerArgs::default())
+        .await
+        .unwrap();
+    worker
+        .perform(DueReminderWorkerArgs::default())
+        .await
+        .unwrap();
+
+    let deliveries = ctx.mailer.as_ref().unwrap().deliveries();
+    assert_eq!(deliveries.count, 1, "Exactly one reminder should be sent");
+}
+
+#[tokio::test]
+#[serial]
+async fn skips_due_dates_outside_window() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Exam").await;
+
+    attachments::Model::add_attachment(
+        &ctx.db,
+        USER_PID,
+        task.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::DueDate,
+            data: (Utc::now() + Duration::days(7)).to_rfc3339(),
+        },
+    )
+    .await
+    .unwrap();
+
+    DueReminderWorker::build(ctx)
+        .perform(DueReminderWorkerArgs {
+            window_hours: Some(24),
+        })
+        .await
+        .unwrap();
+
+    let deliveries = ctx.mailer.as_ref().unwrap().deliveries();
+    assert_eq!(deliveries.count, 0, "No reminder should be sent");
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_window_out_of_range() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+
+    let res = DueReminderWorker::build(ctx)
+        .perform(DueReminderWorkerArgs {
+            window_hours: Some(i64::MAX),
+        })
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+#[serial]
+async fn follows_moved_due_date() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Essay").await;
+
+    let due_date = attachments::Model::add_attachment(
+        &ctx.db,
+        USER_PID,
+        task.id,
+        AttachmentAddParams {
+            attachment_type: AttachmentTypeEnum::DueDate,
+            data: (Utc::now() + Duration::days(7)).to_rfc3339(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(due_date.due_at.is_some());
+
+    due_date
+        .into_active_model()
+        .update_attachment(
+            &ctx.db,
+            USER_PID,
+            AttachmentUpdateParams {
+                data: (Utc::now() + Duration::hours(2)).to_rfc3339(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let now = Utc::now();
+    let due = attachments::Model::list_due_between(&ctx.db, now, now + Duration::hours(3))
+        .await
+        .unwrap();
+    assert_eq!(due.len(), 1);
+
+    DueReminderWorker::build(ctx)
+        .perform(DueReminderWorkerArgs::default())
+        .await
+        .unwrap();
+
+    let deliveries = ctx.mailer.as_ref().unwrap().deliveries();
+    assert_eq!(
+        deliveries.count, 1,
+        "The moved due date should be reminded of"
+    );
+}
@@ -1 +1,3 @@
-
+mod access_expiry;
+mod due_reminder;
+mod webhook_delivery;
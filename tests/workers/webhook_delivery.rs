@@ -0,0 +1,170 @@
+use chrono::Utc;
+use loco_rs::{bgworker::BackgroundWorker, testing::prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection};
+use serial_test::serial;
+use task_hub::{
+    app::App,
+    common::events::TaskEvent,
+    models::{
+        tasks::{self, TransferParams},
+        users,
+        webhook_deliveries::{self, MAX_ATTEMPTS},
+        webhooks,
+    },
+    workers::webhook_delivery::{WebhookDeliveryWorker, WebhookDeliveryWorkerArgs},
+};
+
+use crate::fixtures::{add_task, OTHER_EMAIL, USER_PID};
+
+const INTERNAL_URL: &str = "http://127.0.0.1:9/hook";
+
+/// Hooks to hosts that are not public can not be registered, so they are stored
+/// directly. Their deliveries fail before anything is sent
+async fn add_internal_hook(db: &DatabaseConnection, task_id: Option<i32>) -> webhooks::Model {
+    let owner = users::Model::find_by_pid(db, USER_PID).await.unwrap();
+
+    webhooks::ActiveModel {
+        url: Set(INTERNAL_URL.to_string()),
+        secret: Set("secret".to_string()),
+        owner_id: Set(owner.id),
+        task_id: Set(task_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn failed_delivery_is_retried_later() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let task = add_task(&ctx.db, USER_PID, "Exam").await;
+
+    let hook = add_internal_hook(&ctx.db, None).await;
+
+    let hooks = webhooks::Model::list_for_task(&ctx.db, task.id)
+        .await
+        .unwrap();
+    assert_eq!(hooks.len(), 1, "Hooks without a Task cover all owned Tasks");
+
+    let deliveries = webhook_deliveries::ActiveModel::create_for_event(
+        &ctx.db,
+        &hooks,
+        &TaskEvent::TaskUpdated { task_id: task.id },
+    )
+    .await
+    .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].event, "task_updated");
+
+    WebhookDeliveryWorker::build(ctx)
+        .perform(WebhookDeliveryWorkerArgs {
+            delivery_id: deliveries[0].id,
+        })
+        .await
+        .unwrap();
+
+    let delivery = webhook_deliveries::Model::load(&ctx.db, deliveries[0].id)
+        .await
+        .unwrap();
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(
+        delivery.error.as_deref(),
+        Some(webhooks::NOT_PUBLIC_ERROR),
+        "Internal hosts are refused again before every delivery"
+    );
+    assert!(delivery.delivered_at.is_none());
+    assert!(
+        delivery.next_attempt_at.unwrap() > Utc::now(),
+        "The retry should wait for the backoff"
+    );
+
+    let hook = webhooks::Model::load(&ctx.db, hook.id).await.unwrap();
+    assert_eq!(hook.failure_count, 1);
+    assert!(hook.enabled);
+
+    let due = webhook_deliveries::Entity::find_due(
+        &ctx.db,
+        Utc::now() + webhook_deliveries::backoff(MAX_ATTEMPTS),
+    )
+    .await
+    .unwrap();
+    assert_eq!(due.len(), 1, "The delivery should be retried once due");
+}
+
+#[tokio::test]
+#[serial]
+async fn hook_is_disabled_after_repeated_failures() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let mut hook = add_internal_hook(db, None).await;
+
+    for _ in 0..webhooks::MAX_FAILURES {
+        assert!(hook.enabled);
+        hook = webhooks::ActiveModel::record_failure(db, hook)
+            .await
+            .unwrap();
+    }
+    assert!(!hook.enabled);
+
+    let hook = webhooks::ActiveModel::enable(db, hook).await.unwrap();
+    assert!(hook.enabled);
+    assert_eq!(hook.failure_count, 0);
+}
+
+#[test]
+fn backoff_doubles_with_every_attempt() {
+    assert_eq!(
+        webhook_deliveries::backoff(1),
+        webhook_deliveries::backoff(0) * 2
+    );
+    assert_eq!(
+        webhook_deliveries::backoff(MAX_ATTEMPTS + 5),
+        webhook_deliveries::backoff(MAX_ATTEMPTS)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn task_hooks_stop_after_transfer() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let task = add_task(db, USER_PID, "Exam").await;
+
+    add_internal_hook(db, Some(task.id)).await;
+    assert_eq!(
+        webhooks::Model::list_for_task(db, task.id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    tasks::ActiveModel::transfer(
+        db,
+        USER_PID,
+        task.clone(),
+        &TransferParams {
+            email: OTHER_EMAIL.to_string(),
+            require_confirmation: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        webhooks::Model::list_for_task(db, task.id)
+            .await
+            .unwrap()
+            .is_empty(),
+        "The former owner no longer gets the events of the Task"
+    );
+}